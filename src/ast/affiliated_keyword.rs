@@ -17,21 +17,31 @@ impl AffiliatedKeyword {
             .expect("keyword must contains TEXT")
     }
 
+    /// Returns optional value in brackets, e.g. the short caption in
+    /// `#+CAPTION[short]: long`
+    ///
+    /// Org uses short captions in lists of figures and tables, which
+    /// [`HtmlExport`](crate::export::HtmlExport) doesn't generate, so exporters
+    /// always render the long caption.
     ///
     /// ```rust
     /// use orgize::{Org, ast::AffiliatedKeyword};
     ///
     /// let keyword = Org::parse("#+CAPTION: VALUE\nabc").first_node::<AffiliatedKeyword>().unwrap();
-    /// assert!(keyword.optional().is_none());
+    /// assert!(keyword.optional_value().is_none());
     /// let keyword = Org::parse("#+CAPTION[OPTIONAL]: VALUE\nabc").first_node::<AffiliatedKeyword>().unwrap();
-    /// assert_eq!(keyword.optional().unwrap(), "OPTIONAL");
+    /// assert_eq!(keyword.optional_value().unwrap(), "OPTIONAL");
+    /// assert_eq!(keyword.value().unwrap(), " VALUE");
     /// ```
-    pub fn optional(&self) -> Option<Token> {
+    pub fn optional_value(&self) -> Option<Token> {
         self.syntax
             .children_with_tokens()
-            .skip_while(|it| it.kind() != SyntaxKind::L_BRACKET)
-            .nth(1)
-            .and_then(filter_token(SyntaxKind::TEXT))
+            .find_map(filter_token(SyntaxKind::KEYWORD_OPTIONAL))
+    }
+
+    #[deprecated(note = "use `optional_value` instead")]
+    pub fn optional(&self) -> Option<Token> {
+        self.optional_value()
     }
 
     ///
@@ -49,4 +59,76 @@ impl AffiliatedKeyword {
             .filter_map(filter_token(SyntaxKind::TEXT))
             .last()
     }
+
+    /// Returns backend name of an `ATTR_<BACKEND>` keyword, in lowercase
+    ///
+    /// ```rust
+    /// use orgize::{Org, ast::AffiliatedKeyword};
+    ///
+    /// let keyword = Org::parse("#+ATTR_HTML: :width 100\nabc").first_node::<AffiliatedKeyword>().unwrap();
+    /// assert_eq!(keyword.backend().unwrap(), "html");
+    /// let keyword = Org::parse("#+ATTR_LaTeX: :float t\nabc").first_node::<AffiliatedKeyword>().unwrap();
+    /// assert_eq!(keyword.backend().unwrap(), "latex");
+    /// let keyword = Org::parse("#+CAPTION: VALUE\nabc").first_node::<AffiliatedKeyword>().unwrap();
+    /// assert!(keyword.backend().is_none());
+    /// ```
+    pub fn backend(&self) -> Option<String> {
+        let key = self.key();
+        let (prefix, backend) = key.split_at_checked(5)?;
+        (prefix.eq_ignore_ascii_case("ATTR_") && !backend.is_empty())
+            .then(|| backend.to_ascii_lowercase())
+    }
+
+    /// Parses `:key value` pairs in the value, like `org-export-read-attribute`
+    ///
+    /// Keys are returned without the leading colon. A value spans until the
+    /// next key, and surrounding double quotes are removed. Text before the
+    /// first key is ignored.
+    ///
+    /// Unlike other accessors, pairs are owned strings rather than [`Token`]s:
+    /// the whole value is a single token, and a value spanning several words
+    /// is joined by single spaces and unquoted, so it isn't a slice of the
+    /// source.
+    ///
+    /// ```rust
+    /// use orgize::{Org, ast::AffiliatedKeyword};
+    ///
+    /// let keyword = Org::parse("#+ATTR_HTML: :width 100 :alt \"a cat\" :controls\nabc")
+    ///     .first_node::<AffiliatedKeyword>()
+    ///     .unwrap();
+    /// assert_eq!(
+    ///     keyword.attrs().collect::<Vec<_>>(),
+    ///     [
+    ///         ("width".to_string(), "100".to_string()),
+    ///         ("alt".to_string(), "a cat".to_string()),
+    ///         ("controls".to_string(), "".to_string()),
+    ///     ]
+    /// );
+    /// ```
+    pub fn attrs(&self) -> impl Iterator<Item = (String, String)> {
+        let value = self.value();
+        let value = value.as_ref().map_or("", |v| v.as_ref());
+
+        let mut attrs: Vec<(String, String)> = vec![];
+        for word in value.split_whitespace() {
+            match word.strip_prefix(':').filter(|key| !key.is_empty()) {
+                Some(key) => attrs.push((key.to_string(), String::new())),
+                None => {
+                    if let Some((_, value)) = attrs.last_mut() {
+                        if !value.is_empty() {
+                            value.push(' ');
+                        }
+                        value.push_str(word);
+                    }
+                }
+            }
+        }
+
+        attrs.into_iter().map(|(key, value)| {
+            match value.strip_prefix('"').and_then(|v| v.strip_suffix('"')) {
+                Some(unquoted) => (key, unquoted.to_string()),
+                None => (key, value),
+            }
+        })
+    }
 }
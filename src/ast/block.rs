@@ -4,6 +4,15 @@ use super::{
 };
 use rowan::TextSize;
 
+/// Line numbering switch of source block, see [`SourceBlock::line_numbers`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LineNumbers {
+    /// `-n N`, numbering starts at `N`, defaults to one
+    New(usize),
+    /// `+n N`, numbering continues from previous numbered block, skipping `N - 1` lines
+    Continued(usize),
+}
+
 impl SourceBlock {
     /// ```rust
     /// use orgize::{Org, ast::SourceBlock};
@@ -32,6 +41,10 @@ impl SourceBlock {
     /// assert_eq!(block.switches().unwrap(), "-n 20");
     /// let block = Org::parse("#+begin_src emacs-lisp -n 20 -r :tangle yes \n#+end_src").first_node::<SourceBlock>().unwrap();
     /// assert_eq!(block.switches().unwrap(), "-n 20 -r");
+    /// let block = Org::parse("#+begin_src emacs-lisp -r +n 5 :tangle yes\n#+end_src").first_node::<SourceBlock>().unwrap();
+    /// assert_eq!(block.switches().unwrap(), "-r +n 5");
+    /// let block = Org::parse("#+begin_src emacs-lisp -n :tangle yes\n#+end_src").first_node::<SourceBlock>().unwrap();
+    /// assert_eq!(block.switches().unwrap(), "-n");
     ///
     /// let block = Org::parse("#+begin_src emacs-lisp\n#+end_src").first_node::<SourceBlock>().unwrap();
     /// assert!(block.switches().is_none());
@@ -69,6 +82,91 @@ impl SourceBlock {
             .find_map(filter_token(SyntaxKind::SRC_BLOCK_PARAMETERS))
     }
 
+    /// Returns line numbering defined by `-n` or `+n` switch
+    ///
+    /// ```rust
+    /// use orgize::{Org, ast::{SourceBlock, LineNumbers}};
+    ///
+    /// let block = Org::parse("#+begin_src c -n 20 -r\n#+end_src").first_node::<SourceBlock>().unwrap();
+    /// assert_eq!(block.line_numbers(), Some(LineNumbers::New(20)));
+    /// let block = Org::parse("#+begin_src c -n\n#+end_src").first_node::<SourceBlock>().unwrap();
+    /// assert_eq!(block.line_numbers(), Some(LineNumbers::New(1)));
+    /// let block = Org::parse("#+begin_src c -r +n 5\n#+end_src").first_node::<SourceBlock>().unwrap();
+    /// assert_eq!(block.line_numbers(), Some(LineNumbers::Continued(5)));
+    /// let block = Org::parse("#+begin_src c +n\n#+end_src").first_node::<SourceBlock>().unwrap();
+    /// assert_eq!(block.line_numbers(), Some(LineNumbers::Continued(1)));
+    ///
+    /// let block = Org::parse("#+begin_src c -r\n#+end_src").first_node::<SourceBlock>().unwrap();
+    /// assert!(block.line_numbers().is_none());
+    /// ````
+    pub fn line_numbers(&self) -> Option<LineNumbers> {
+        let switches = self.switches()?;
+        let mut words = switches.split_ascii_whitespace().peekable();
+
+        while let Some(word) = words.next() {
+            if word != "-n" && word != "+n" {
+                continue;
+            }
+            let n = words
+                .peek()
+                .and_then(|w| w.parse::<usize>().ok())
+                .unwrap_or(1);
+            return Some(if word == "-n" {
+                LineNumbers::New(n)
+            } else {
+                LineNumbers::Continued(n)
+            });
+        }
+
+        None
+    }
+
+    /// Returns `true` if `-r` switch is present, aka. coderef labels are removed from exported code
+    ///
+    /// ```rust
+    /// use orgize::{Org, ast::SourceBlock};
+    ///
+    /// let block = Org::parse("#+begin_src c -n 20 -r\n#+end_src").first_node::<SourceBlock>().unwrap();
+    /// assert!(block.removes_labels());
+    /// let block = Org::parse("#+begin_src c -n 20\n#+end_src").first_node::<SourceBlock>().unwrap();
+    /// assert!(!block.removes_labels());
+    /// ````
+    pub fn removes_labels(&self) -> bool {
+        self.switches()
+            .is_some_and(|s| s.split_ascii_whitespace().any(|w| w == "-r"))
+    }
+
+    /// Returns value of given header argument, e.g. `:exports`
+    ///
+    /// Values spanning multiple words are joined with a single space.
+    ///
+    /// ```rust
+    /// use orgize::{Org, ast::SourceBlock};
+    ///
+    /// let block = Org::parse("#+begin_src c :exports both :var x=1 y=2 :tangle\n#+end_src").first_node::<SourceBlock>().unwrap();
+    /// assert_eq!(block.header_argument("exports").unwrap(), "both");
+    /// assert_eq!(block.header_argument(":VAR").unwrap(), "x=1 y=2");
+    /// assert_eq!(block.header_argument("tangle").unwrap(), "");
+    /// assert!(block.header_argument("results").is_none());
+    /// ````
+    pub fn header_argument(&self, key: &str) -> Option<String> {
+        let parameters = self.parameters()?;
+        let key = key.trim_start_matches(':');
+
+        let mut words = parameters.split_ascii_whitespace();
+        words.find(|w| {
+            w.strip_prefix(':')
+                .is_some_and(|w| w.eq_ignore_ascii_case(key))
+        })?;
+
+        Some(
+            words
+                .take_while(|w| !w.starts_with(':'))
+                .collect::<Vec<_>>()
+                .join(" "),
+        )
+    }
+
     /// Return unescaped source code string
     ///
     /// ```rust
@@ -99,6 +197,19 @@ impl SourceBlock {
     }
 }
 
+/// Splits trailing coderef label, e.g. `(ref:name)`, from given line of code
+///
+/// Returns code before the label, with trailing whitespace trimmed, and the label name.
+pub(crate) fn split_coderef(line: &str) -> Option<(&str, &str)> {
+    let rest = line.trim_end().strip_suffix(')')?;
+    let start = rest.rfind("(ref:")?;
+    let label = &rest[start + 5..];
+    if label.is_empty() || label.contains(|c: char| c == '(' || c.is_whitespace()) {
+        return None;
+    }
+    Some((line[..start].trim_end(), label))
+}
+
 impl ExportBlock {
     /// ```rust
     /// use orgize::{Org, ast::ExportBlock};
@@ -153,17 +264,62 @@ impl ExportBlock {
     }
 }
 
+impl DynBlock {
+    /// Returns name of dynamic block, e.g. `clocktable`
+    ///
+    /// ```rust
+    /// use orgize::{Org, ast::DynBlock};
+    ///
+    /// let block = Org::parse("#+BEGIN: clocktable :scope file\n#+END:").first_node::<DynBlock>().unwrap();
+    /// assert_eq!(block.block_name(), "clocktable");
+    ///
+    /// let block = Org::parse("#+NAME: report\n#+BEGIN: clocktable\n#+END:").first_node::<DynBlock>().unwrap();
+    /// assert_eq!(block.block_name(), "clocktable");
+    /// assert_eq!(block.name().unwrap().value().unwrap(), " report");
+    /// ```
+    pub fn block_name(&self) -> Token {
+        self.syntax
+            .children()
+            .find(|e| e.kind() == SyntaxKind::DYN_BLOCK_BEGIN)
+            .into_iter()
+            .flat_map(|n| n.children_with_tokens())
+            .find_map(filter_token(SyntaxKind::DYN_BLOCK_NAME))
+            .expect("dynamic block must contains DYN_BLOCK_NAME")
+    }
+
+    /// ```rust
+    /// use orgize::{Org, ast::DynBlock};
+    ///
+    /// let block = Org::parse("#+BEGIN: clocktable :scope file :maxlevel 2\n#+END:").first_node::<DynBlock>().unwrap();
+    /// assert_eq!(block.parameters().unwrap(), ":scope file :maxlevel 2");
+    ///
+    /// let block = Org::parse("#+BEGIN: columnview\n#+END:").first_node::<DynBlock>().unwrap();
+    /// assert!(block.parameters().is_none());
+    /// ```
+    pub fn parameters(&self) -> Option<Token> {
+        self.syntax
+            .children()
+            .find(|e| e.kind() == SyntaxKind::DYN_BLOCK_BEGIN)
+            .into_iter()
+            .flat_map(|n| n.children_with_tokens())
+            .find_map(filter_token(SyntaxKind::DYN_BLOCK_PARAMETERS))
+    }
+}
+
 macro_rules! impl_content_border {
     ($block:ident) => {
+        impl_content_border!($block, BLOCK_BEGIN, BLOCK_END);
+    };
+    ($block:ident, $begin:ident, $end:ident) => {
         impl $block {
             /// Beginning position of block content
             pub fn content_start(&self) -> TextSize {
                 self.syntax
                     .children()
-                    .find(|n| n.kind() == SyntaxKind::BLOCK_BEGIN)
+                    .find(|n| n.kind() == SyntaxKind::$begin)
                     .map(|n| n.text_range().end())
                     .unwrap_or_else(|| {
-                        debug_assert!(false, "block must contains BLOCK_BEGIN");
+                        debug_assert!(false, concat!("block must contains ", stringify!($begin)));
                         TextSize::default()
                     })
             }
@@ -172,10 +328,10 @@ macro_rules! impl_content_border {
             pub fn content_end(&self) -> TextSize {
                 self.syntax
                     .children()
-                    .find(|n| n.kind() == SyntaxKind::BLOCK_END)
+                    .find(|n| n.kind() == SyntaxKind::$end)
                     .map(|n| n.text_range().start())
                     .unwrap_or_else(|| {
-                        debug_assert!(false, "block must contains BLOCK_END");
+                        debug_assert!(false, concat!("block must contains ", stringify!($end)));
                         TextSize::default()
                     })
             }
@@ -191,4 +347,4 @@ impl_content_border!(ExampleBlock);
 impl_content_border!(QuoteBlock);
 impl_content_border!(SpecialBlock);
 impl_content_border!(VerseBlock);
-impl_content_border!(DynBlock);
+impl_content_border!(DynBlock, DYN_BLOCK_BEGIN, DYN_BLOCK_END);
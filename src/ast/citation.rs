@@ -0,0 +1,96 @@
+use crate::syntax::{SyntaxKind, SyntaxNode};
+use rowan::NodeOrToken;
+
+use super::{Citation, CitationReference};
+
+impl Citation {
+    /// Returns global prefix, placed before the first reference
+    ///
+    /// ```rust
+    /// use orgize::{Org, ast::Citation};
+    ///
+    /// let cite = Org::parse("[cite:see ;@doe2020;@roe]").first_node::<Citation>().unwrap();
+    /// assert_eq!(cite.prefix().unwrap(), "see ");
+    ///
+    /// let cite = Org::parse("[cite:see @doe2020]").first_node::<Citation>().unwrap();
+    /// assert!(cite.prefix().is_none());
+    /// ```
+    pub fn prefix(&self) -> Option<String> {
+        child_text(&self.syntax, SyntaxKind::CITATION_PREFIX)
+    }
+
+    /// Returns global suffix, placed after the last reference
+    ///
+    /// ```rust
+    /// use orgize::{Org, ast::Citation};
+    ///
+    /// let cite = Org::parse("[cite:@doe2020;@roe; for example]").first_node::<Citation>().unwrap();
+    /// assert_eq!(cite.suffix().unwrap(), " for example");
+    /// ```
+    pub fn suffix(&self) -> Option<String> {
+        child_text(&self.syntax, SyntaxKind::CITATION_SUFFIX)
+    }
+
+    /// Returns keys of all references, without the leading `@`
+    ///
+    /// ```rust
+    /// use orgize::{Org, ast::Citation};
+    ///
+    /// let cite = Org::parse("[cite/t:@doe2020;@roe p. 3]").first_node::<Citation>().unwrap();
+    /// assert_eq!(cite.style().unwrap(), "t");
+    /// assert_eq!(cite.keys(), vec!["doe2020", "roe"]);
+    /// ```
+    pub fn keys(&self) -> Vec<String> {
+        self.references()
+            .filter_map(|reference| reference.key())
+            .map(|key| key.to_string())
+            .collect()
+    }
+
+    /// Returns everything between `:` and the closing bracket, e.g.
+    /// `see @doe2020, p. 3` in `[cite/t:see @doe2020, p. 3]`
+    pub(crate) fn body(&self) -> String {
+        self.syntax
+            .children_with_tokens()
+            .skip_while(|e| e.kind() != SyntaxKind::COLON)
+            .skip(1)
+            .filter(|e| e.kind() != SyntaxKind::R_BRACKET)
+            .map(|e| match e {
+                NodeOrToken::Node(n) => n.to_string(),
+                NodeOrToken::Token(t) => t.text().to_string(),
+            })
+            .collect()
+    }
+}
+
+impl CitationReference {
+    /// Returns key prefix, placed before `@key`
+    ///
+    /// ```rust
+    /// use orgize::{Org, ast::CitationReference};
+    ///
+    /// let reference = Org::parse("[cite:see @doe2020, p. 3]").first_node::<CitationReference>().unwrap();
+    /// assert_eq!(reference.key().unwrap(), "doe2020");
+    /// assert_eq!(reference.prefix().unwrap(), "see ");
+    /// assert_eq!(reference.suffix().unwrap(), ", p. 3");
+    ///
+    /// let reference = Org::parse("[cite:@doe2020]").first_node::<CitationReference>().unwrap();
+    /// assert!(reference.prefix().is_none());
+    /// assert!(reference.suffix().is_none());
+    /// ```
+    pub fn prefix(&self) -> Option<String> {
+        child_text(&self.syntax, SyntaxKind::CITATION_PREFIX)
+    }
+
+    /// Returns key suffix, placed after `@key`
+    pub fn suffix(&self) -> Option<String> {
+        child_text(&self.syntax, SyntaxKind::CITATION_SUFFIX)
+    }
+}
+
+fn child_text(parent: &SyntaxNode, kind: SyntaxKind) -> Option<String> {
+    parent
+        .children()
+        .find(|n| n.kind() == kind)
+        .map(|n| n.to_string())
+}
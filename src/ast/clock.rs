@@ -1,3 +1,5 @@
+use std::time::Duration;
+
 use rowan::ast::support;
 
 use crate::{ast::Token, SyntaxKind};
@@ -9,16 +11,18 @@ impl Clock {
         support::child(&self.syntax)
     }
 
+    /// Returns the raw duration text after `=>`
+    ///
     /// ```rust
     /// use orgize::{Org, ast::Clock};
     ///
     /// let clock = Org::parse("CLOCK: [2003-09-16 Tue 09:39]").first_node::<Clock>().unwrap();
-    /// assert!(clock.duration().is_none());
+    /// assert!(clock.duration_raw().is_none());
     /// let clock = Org::parse("CLOCK: [2003-09-16 Tue 09:39] =>12:00").first_node::<Clock>().unwrap();
-    /// assert_eq!(clock.duration().unwrap(), "12:00");
+    /// assert_eq!(clock.duration_raw().unwrap(), "12:00");
     ///
     /// ```
-    pub fn duration(&self) -> Option<Token> {
+    pub fn duration_raw(&self) -> Option<Token> {
         self.syntax
             .children_with_tokens()
             .skip_while(|t| t.kind() != SyntaxKind::DOUBLE_ARROW)
@@ -30,6 +34,45 @@ impl Clock {
             })
     }
 
+    /// Returns the logged time of this clock
+    ///
+    /// Parses the `=> H:MM` part if present. Otherwise, when the `chrono` feature
+    /// is enabled, computes it from the timestamp range. Returns `None` for a
+    /// running clock or a malformed duration.
+    ///
+    /// ```rust
+    /// use orgize::{Org, ast::Clock};
+    /// use std::time::Duration;
+    ///
+    /// let clock = Org::parse("CLOCK: [2003-09-16 Tue 09:39]--[2003-09-16 Tue 11:09] =>  1:30").first_node::<Clock>().unwrap();
+    /// assert_eq!(clock.duration(), Some(Duration::from_secs(90 * 60)));
+    /// let clock = Org::parse("CLOCK: [2003-09-16 Tue 09:39]--[2003-09-17 Wed 09:39] => 24:00").first_node::<Clock>().unwrap();
+    /// assert_eq!(clock.duration(), Some(Duration::from_secs(24 * 60 * 60)));
+    /// let clock = Org::parse("CLOCK: [2003-09-16 Tue 09:39]--[2003-09-16 Tue 11:09] => 1:75").first_node::<Clock>().unwrap();
+    /// assert_eq!(clock.duration(), None);
+    /// let clock = Org::parse("CLOCK: [2003-09-16 Tue 09:39]").first_node::<Clock>().unwrap();
+    /// assert_eq!(clock.duration(), None);
+    /// ```
+    pub fn duration(&self) -> Option<Duration> {
+        if self.is_closed() {
+            if let Some(raw) = self.duration_raw() {
+                return parse_duration(&raw);
+            }
+        }
+
+        #[cfg(feature = "chrono")]
+        {
+            let value = self.value()?;
+            if value.is_range() {
+                return value.time_delta()?.to_std().ok();
+            }
+        }
+
+        None
+    }
+
+    /// Returns `true` if this clock has a duration or an end time
+    ///
     /// ```rust
     /// use orgize::{Org, ast::Clock};
     ///
@@ -37,13 +80,18 @@ impl Clock {
     /// assert!(!clock.is_closed());
     /// let clock = Org::parse("CLOCK: [2003-09-16 Tue 09:39] =>12:00").first_node::<Clock>().unwrap();
     /// assert!(clock.is_closed());
+    /// let clock = Org::parse("CLOCK: [2003-09-16 Tue 09:39]--[2003-09-16 Tue 10:39]").first_node::<Clock>().unwrap();
+    /// assert!(clock.is_closed());
     /// ```
     pub fn is_closed(&self) -> bool {
         self.syntax
             .children_with_tokens()
             .any(|t| t.kind() == SyntaxKind::DOUBLE_ARROW)
+            || self.value().is_some_and(|value| value.is_range())
     }
 
+    /// Returns `true` if this clock has no end time
+    ///
     /// ```rust
     /// use orgize::{Org, ast::Clock};
     ///
@@ -56,3 +104,49 @@ impl Clock {
         !self.is_closed()
     }
 }
+
+/// Parses `H:MM` into duration
+fn parse_duration(s: &str) -> Option<Duration> {
+    let (hours, minutes) = s.trim().split_once(':')?;
+    let hours: u64 = hours.parse().ok()?;
+    let minutes: u64 = minutes.parse().ok()?;
+    if minutes >= 60 {
+        return None;
+    }
+    let secs = hours
+        .checked_mul(60)?
+        .checked_add(minutes)?
+        .checked_mul(60)?;
+    Some(Duration::from_secs(secs))
+}
+
+#[test]
+fn duration() {
+    assert_eq!(parse_duration("0:05"), Some(Duration::from_secs(5 * 60)));
+    assert_eq!(
+        parse_duration("120:00"),
+        Some(Duration::from_secs(120 * 3600))
+    );
+    assert_eq!(parse_duration(""), None);
+    assert_eq!(parse_duration("1"), None);
+    assert_eq!(parse_duration("a:00"), None);
+    assert_eq!(parse_duration("1:-1"), None);
+    assert_eq!(parse_duration("1:60"), None);
+    assert_eq!(parse_duration("9999999999999999:00"), None);
+    assert_eq!(parse_duration("99999999999999999999:00"), None);
+
+    let org = crate::Org::parse(
+        "CLOCK: [2003-09-16 Tue 09:39]--[2003-09-16 Tue 10:09] => 9999999999999999:00",
+    );
+    let clock = org.first_node::<Clock>().unwrap();
+    assert_eq!(clock.duration(), None);
+
+    #[cfg(feature = "chrono")]
+    {
+        use crate::Org;
+
+        let org = Org::parse("CLOCK: [2003-09-16 Tue 09:39]--[2003-09-16 Tue 10:09]");
+        let clock = org.first_node::<Clock>().unwrap();
+        assert_eq!(clock.duration(), Some(Duration::from_secs(30 * 60)));
+    }
+}
@@ -5,11 +5,17 @@ use super::{filter_token, Comment};
 impl Comment {
     /// Contents without pound signs
     ///
+    /// Only the pound sign and one whitespace following it are removed from
+    /// each line, so extra indentation and blank lines are preserved.
+    ///
     /// ```rust
     /// use orgize::{ast::Comment, Org};
     ///
     /// let fixed = Org::parse("# A\n#\n# B\n# C").first_node::<Comment>().unwrap();
     /// assert_eq!(fixed.value(), "A\n\nB\nC");
+    ///
+    /// let fixed = Org::parse("  # if a:\n  #     b\n").first_node::<Comment>().unwrap();
+    /// assert_eq!(fixed.value(), "if a:\n    b\n");
     /// ```
     pub fn value(&self) -> String {
         self.syntax
@@ -0,0 +1,43 @@
+use crate::SyntaxKind;
+
+use super::Cookie;
+
+impl Cookie {
+    /// Returns `true` if this is a percent cookie like `[66%]`
+    ///
+    /// ```rust
+    /// use orgize::{Org, ast::Cookie};
+    ///
+    /// let cookie = Org::parse("[66%]").first_node::<Cookie>().unwrap();
+    /// assert!(cookie.is_percent());
+    /// let cookie = Org::parse("[2/3]").first_node::<Cookie>().unwrap();
+    /// assert!(!cookie.is_percent());
+    /// ```
+    pub fn is_percent(&self) -> bool {
+        self.syntax
+            .children_with_tokens()
+            .any(|e| e.kind() == SyntaxKind::PERCENT)
+    }
+
+    /// Formats given statistics in the same style as this cookie
+    ///
+    /// Useful to regenerate cookies from [`Headline::statistics`](super::Headline::statistics)
+    /// or [`List::statistics`](super::List::statistics).
+    ///
+    /// ```rust
+    /// use orgize::{Org, ast::Cookie};
+    ///
+    /// let cookie = Org::parse("[0/0]").first_node::<Cookie>().unwrap();
+    /// assert_eq!(cookie.format_statistics(2, 3), "[2/3]");
+    /// let cookie = Org::parse("[%]").first_node::<Cookie>().unwrap();
+    /// assert_eq!(cookie.format_statistics(2, 3), "[66%]");
+    /// assert_eq!(cookie.format_statistics(0, 0), "[0%]");
+    /// ```
+    pub fn format_statistics(&self, done: usize, total: usize) -> String {
+        if self.is_percent() {
+            format!("[{}%]", done * 100 / total.max(1))
+        } else {
+            format!("[{done}/{total}]")
+        }
+    }
+}
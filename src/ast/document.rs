@@ -1,9 +1,52 @@
 use rowan::ast::AstNode;
+use std::collections::HashMap;
 
 use crate::Org;
 
 use super::{Document, Keyword, PropertyDrawer};
 
+/// Value of an item in `#+OPTIONS`, e.g. `toc:nil` or `H:3`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OptionValue {
+    /// `t` or `nil`
+    Bool(bool),
+    /// Non-negative integer, e.g. headline level
+    Integer(usize),
+    /// Any other value, e.g. `{}` or `verbatim`
+    Special(String),
+}
+
+impl OptionValue {
+    fn parse(value: &str) -> Self {
+        match value {
+            "t" => OptionValue::Bool(true),
+            "nil" => OptionValue::Bool(false),
+            _ => value
+                .parse()
+                .map_or_else(|_| OptionValue::Special(value.into()), OptionValue::Integer),
+        }
+    }
+}
+
+/// Common export keywords in zeroth section, see [`Document::metadata`]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DocumentMetadata {
+    /// `#+TITLE`, multiple values are joined with spaces
+    pub title: Option<String>,
+    /// `#+AUTHOR`, multiple values are joined with spaces
+    pub author: Option<String>,
+    /// `#+DATE`, multiple values are joined with spaces
+    pub date: Option<String>,
+    /// `#+EMAIL`, the last value wins
+    pub email: Option<String>,
+    /// `#+LANGUAGE`, the last value wins
+    pub language: Option<String>,
+    /// `#+DESCRIPTION`, multiple values are joined with newlines
+    pub description: Option<String>,
+    /// `#+KEYWORDS`, split by commas
+    pub keywords: Vec<String>,
+}
+
 impl Document {
     /// Returns an iterator of keywords in zeroth section
     ///
@@ -51,11 +94,47 @@ impl Document {
                 if !s.is_empty() {
                     s.push(' ');
                 }
-                s.push_str(cur.value().trim());
+                s.push_str(&cur.value_trimmed());
                 Some(s)
             })
     }
 
+    /// Returns export options from `#+OPTIONS` keywords in zeroth section
+    ///
+    /// Keys are kept as written, e.g. `toc`, `^` or `\n`. Multiple `#+OPTIONS`
+    /// lines are merged from left to right, so later items override earlier ones.
+    ///
+    /// ```rust
+    /// use orgize::{Org, ast::OptionValue};
+    ///
+    /// let org = Org::parse("#+OPTIONS: toc:nil num:t ^:{} \\n:t\n#+OPTIONS: H:3 num:nil ::t");
+    /// let options = org.document().options();
+    /// assert_eq!(options.len(), 6);
+    /// assert_eq!(options["toc"], OptionValue::Bool(false));
+    /// assert_eq!(options["num"], OptionValue::Bool(false));
+    /// assert_eq!(options["^"], OptionValue::Special("{}".into()));
+    /// assert_eq!(options["\\n"], OptionValue::Bool(true));
+    /// assert_eq!(options["H"], OptionValue::Integer(3));
+    /// assert_eq!(options[":"], OptionValue::Bool(true));
+    /// ```
+    pub fn options(&self) -> HashMap<String, OptionValue> {
+        let mut options = HashMap::new();
+        for keyword in self
+            .keywords()
+            .filter(|kw| kw.key().eq_ignore_ascii_case("OPTIONS"))
+        {
+            for item in keyword.value().split_ascii_whitespace() {
+                // key itself may be a colon, e.g. `::t`
+                let Some(idx) = item.get(1..).and_then(|s| s.find(':')) else {
+                    continue;
+                };
+                let (key, value) = (&item[..idx + 1], &item[idx + 2..]);
+                options.insert(key.to_string(), OptionValue::parse(value));
+            }
+        }
+        options
+    }
+
     /// Returns top-level properties drawer
     ///
     /// ```rust
@@ -73,6 +152,71 @@ impl Document {
     pub fn properties(&self) -> Option<PropertyDrawer> {
         rowan::ast::support::child(&self.syntax)
     }
+
+    /// Collects common export keywords in zeroth section
+    ///
+    /// Keys are case-insensitive and values are trimmed. Keywords with an
+    /// empty value are ignored.
+    ///
+    /// ```rust
+    /// use orgize::Org;
+    ///
+    /// let org = Org::parse(r#"#+TITLE: Hello
+    /// #+TITLE: World
+    /// #+author: Alice
+    /// #+EMAIL: old@example.com
+    /// #+EMAIL: alice@example.com
+    /// #+DESCRIPTION: first line
+    /// #+DESCRIPTION: second line
+    /// #+KEYWORDS: org, rust,
+    /// #+KEYWORDS: parser
+    /// * headline
+    /// #+LANGUAGE: fr"#);
+    /// let metadata = org.document().metadata();
+    /// assert_eq!(metadata.title.unwrap(), "Hello World");
+    /// assert_eq!(metadata.author.unwrap(), "Alice");
+    /// assert_eq!(metadata.email.unwrap(), "alice@example.com");
+    /// assert_eq!(metadata.description.unwrap(), "first line\nsecond line");
+    /// assert_eq!(metadata.keywords, ["org", "rust", "parser"]);
+    /// assert!(metadata.date.is_none());
+    /// assert!(metadata.language.is_none());
+    /// ```
+    pub fn metadata(&self) -> DocumentMetadata {
+        let mut metadata = DocumentMetadata::default();
+
+        let join = |field: &mut Option<String>, value: String, separator: char| match field {
+            Some(s) => {
+                s.push(separator);
+                s.push_str(&value);
+            }
+            None => *field = Some(value),
+        };
+
+        for keyword in self.keywords() {
+            let value = keyword.value_trimmed();
+            if value.is_empty() {
+                continue;
+            }
+            match &*keyword.key().to_ascii_uppercase() {
+                "TITLE" => join(&mut metadata.title, value, ' '),
+                "AUTHOR" => join(&mut metadata.author, value, ' '),
+                "DATE" => join(&mut metadata.date, value, ' '),
+                "EMAIL" => metadata.email = Some(value),
+                "LANGUAGE" => metadata.language = Some(value),
+                "DESCRIPTION" => join(&mut metadata.description, value, '\n'),
+                "KEYWORDS" => metadata.keywords.extend(
+                    value
+                        .split(',')
+                        .map(str::trim)
+                        .filter(|s| !s.is_empty())
+                        .map(String::from),
+                ),
+                _ => {}
+            }
+        }
+
+        metadata
+    }
 }
 
 impl Org {
@@ -81,6 +225,16 @@ impl Org {
         self.document().title()
     }
 
+    /// Equals to `self.document().options()`, see [Document::options]
+    pub fn options(&self) -> HashMap<String, OptionValue> {
+        self.document().options()
+    }
+
+    /// Equals to `self.document().metadata()`, see [Document::metadata]
+    pub fn metadata(&self) -> DocumentMetadata {
+        self.document().metadata()
+    }
+
     /// Equals to `self.document().keywords()`, see [Document::keywords]
     pub fn keywords(&self) -> impl Iterator<Item = Keyword> {
         self.document().keywords()
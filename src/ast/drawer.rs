@@ -1,7 +1,9 @@
 use rowan::TextSize;
 use std::collections::HashMap;
 
-use super::{filter_token, Drawer, PropertyDrawer, SyntaxKind, Token};
+use rowan::ast::AstNode;
+
+use super::{filter_token, Clock, Drawer, PropertyDrawer, SyntaxKind, Token};
 
 impl PropertyDrawer {
     /// ```rust
@@ -22,6 +24,12 @@ impl PropertyDrawer {
         })
     }
 
+    /// Returns value of the first property with given key
+    ///
+    /// Keys are compared case-insensitively, like [`get_all`](Self::get_all)
+    /// and [`get_accumulated`](Self::get_accumulated). A `:KEY+:` property
+    /// matches as a plain one.
+    ///
     /// ```rust
     /// use orgize::{Org, ast::PropertyDrawer};
     ///
@@ -29,9 +37,81 @@ impl PropertyDrawer {
     /// let drawer = org.first_node::<PropertyDrawer>().unwrap();
     /// assert_eq!(drawer.get("CUSTOM_ID").unwrap(), "someid");
     /// assert_eq!(drawer.get("ID").unwrap(), "id");
+    /// assert_eq!(drawer.get("id").unwrap(), "id");
+    ///
+    /// let org = Org::parse("* Heading\n:PROPERTIES:\n:ID: a\n:ID: b\n:END:");
+    /// let drawer = org.first_node::<PropertyDrawer>().unwrap();
+    /// assert_eq!(drawer.get("ID").unwrap(), "a");
     /// ```
     pub fn get(&self, key: &str) -> Option<Token> {
-        self.iter().find_map(|(k, v)| (k == key).then_some(v))
+        self.iter()
+            .find_map(|(k, v)| k.eq_ignore_ascii_case(key).then_some(v))
+    }
+
+    /// Returns all values of given key in drawer order, including ones from
+    /// `:KEY+:` form
+    ///
+    /// Keys are compared case-insensitively.
+    ///
+    /// ```rust
+    /// use orgize::{Org, ast::PropertyDrawer};
+    ///
+    /// let org = Org::parse("* Heading\n:PROPERTIES:\n:VAR: x=1\n:ID: id\n:var+: y=2\n:END:");
+    /// let drawer = org.first_node::<PropertyDrawer>().unwrap();
+    /// assert_eq!(drawer.get_all("VAR"), ["x=1", "y=2"]);
+    /// assert!(drawer.get_all("TAGS").is_empty());
+    /// ```
+    pub fn get_all(&self, key: &str) -> Vec<Token> {
+        self.values(key).map(|(value, _)| value).collect()
+    }
+
+    /// Returns value of given key, concatenating `:KEY+:` values with spaces
+    ///
+    /// A plain `:KEY:` replaces all values before it. Keys are compared
+    /// case-insensitively. Use [`Headline::property_accumulated`] to also
+    /// take ancestor headlines into account.
+    ///
+    /// [`Headline::property_accumulated`]: crate::ast::Headline::property_accumulated
+    ///
+    /// ```rust
+    /// use orgize::{Org, ast::PropertyDrawer};
+    ///
+    /// let org = Org::parse("* Heading\n:PROPERTIES:\n:VAR: x=1\n:VAR+: y=2\n:VAR+: z=3\n:END:");
+    /// let drawer = org.first_node::<PropertyDrawer>().unwrap();
+    /// assert_eq!(drawer.get_accumulated("VAR").unwrap(), "x=1 y=2 z=3");
+    /// assert_eq!(drawer.get("VAR").unwrap(), "x=1");
+    ///
+    /// let org = Org::parse("* Heading\n:PROPERTIES:\n:VAR+: x=1\n:VAR: y=2\n:VAR+: z=3\n:END:");
+    /// let drawer = org.first_node::<PropertyDrawer>().unwrap();
+    /// assert_eq!(drawer.get_accumulated("VAR").unwrap(), "y=2 z=3");
+    /// assert!(drawer.get_accumulated("TAGS").is_none());
+    /// ```
+    pub fn get_accumulated(&self, key: &str) -> Option<String> {
+        accumulate_values(std::iter::once(self.clone()), key)
+    }
+
+    /// Returns an iterator of values of given key, in drawer order
+    ///
+    /// The boolean is `true` if the value comes from `:KEY+:` form, which
+    /// appends to the current value instead of replacing it. Keys are
+    /// compared case-insensitively.
+    pub(crate) fn values(&self, key: &str) -> impl Iterator<Item = (Token, bool)> + '_ {
+        let key = key.to_string();
+        self.node_properties().filter_map(move |property| {
+            let mut texts = property
+                .syntax
+                .children_with_tokens()
+                .filter_map(filter_token(SyntaxKind::TEXT));
+            let (k, v) = (texts.next()?, texts.next()?);
+            if !k.eq_ignore_ascii_case(&key) {
+                return None;
+            }
+            let plus = property
+                .syntax
+                .children_with_tokens()
+                .any(|e| e.kind() == SyntaxKind::PLUS);
+            Some((v, plus))
+        })
     }
 
     /// ```rust
@@ -87,6 +167,45 @@ impl PropertyDrawer {
     }
 }
 
+/// Returns value of given key from `drawers`, nearest first, concatenating
+/// `:KEY+:` values with spaces
+///
+/// Values are collected until a drawer containing a plain `:KEY:`, which
+/// replaces all values before it.
+pub(crate) fn accumulate_values(
+    drawers: impl Iterator<Item = PropertyDrawer>,
+    key: &str,
+) -> Option<String> {
+    let mut values: Vec<Token> = vec![];
+    let mut found = false;
+
+    for drawer in drawers {
+        let mut local = vec![];
+        let mut replaced = false;
+        for (value, plus) in drawer.values(key) {
+            if !plus {
+                local.clear();
+                replaced = true;
+            }
+            local.push(value);
+        }
+        found |= !local.is_empty();
+        local.append(&mut values);
+        values = local;
+        if replaced {
+            break;
+        }
+    }
+
+    found.then(|| {
+        values
+            .iter()
+            .map(|value| value.as_ref())
+            .collect::<Vec<&str>>()
+            .join(" ")
+    })
+}
+
 impl Drawer {
     /// ```rust
     /// use orgize::{Org, ast::Drawer};
@@ -137,4 +256,52 @@ impl Drawer {
             .map(|n| n.to_string())
             .unwrap_or_default()
     }
+
+    /// Returns `true` if this drawer is named `LOGBOOK`, case-insensitively
+    ///
+    /// ```rust
+    /// use orgize::{Org, ast::Drawer};
+    ///
+    /// let org = Org::parse("* a\n:logbook:\n:END:");
+    /// assert!(org.first_node::<Drawer>().unwrap().is_logbook());
+    /// let org = Org::parse("* a\n:NOTES:\n:END:");
+    /// assert!(!org.first_node::<Drawer>().unwrap().is_logbook());
+    /// ```
+    pub fn is_logbook(&self) -> bool {
+        self.name().eq_ignore_ascii_case("LOGBOOK")
+    }
+
+    /// Returns clock lines of a logbook drawer, empty for other drawers
+    ///
+    /// State-change notes and other content are skipped.
+    ///
+    /// ```rust
+    /// use orgize::{Org, ast::Drawer};
+    ///
+    /// let org = Org::parse(r#"* TODO task
+    /// :LOGBOOK:
+    /// - State "TODO"       from              [2023-01-01 Sun 09:00]
+    /// CLOCK: [2023-01-02 Mon 10:00]--[2023-01-02 Mon 11:30] =>  1:30
+    /// CLOCK: [2023-01-03 Tue 10:00]--[2023-01-03 Tue 10:15] =>  0:15
+    /// :END:"#);
+    /// let drawer = org.first_node::<Drawer>().unwrap();
+    /// let minutes: u32 = drawer
+    ///     .clocks()
+    ///     .filter_map(|clock| clock.duration())
+    ///     .map(|duration| duration.as_secs() as u32 / 60)
+    ///     .sum();
+    /// assert_eq!(drawer.clocks().count(), 2);
+    /// assert_eq!(minutes, 105);
+    ///
+    /// let org = Org::parse("* a\n:NOTES:\nCLOCK: [2023-01-03 Tue 10:00]\n:END:");
+    /// assert_eq!(org.first_node::<Drawer>().unwrap().clocks().count(), 0);
+    /// ```
+    pub fn clocks(&self) -> impl Iterator<Item = Clock> {
+        let is_logbook = self.is_logbook();
+        self.syntax
+            .children()
+            .filter(move |n| is_logbook && n.kind() == SyntaxKind::DRAWER_CONTENT)
+            .flat_map(|n| n.children())
+            .filter_map(Clock::cast)
+    }
 }
@@ -1,6 +1,20 @@
-use crate::{entities::ENTITIES, SyntaxKind};
+use crate::{
+    entities::{lookup, EntityInfo, ENTITIES},
+    ParseConfig, SyntaxKind,
+};
 
-use super::{filter_token, Entity};
+use super::{filter_token, Entity, Token};
+
+/// Representation of an entity in different export targets
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum EntityRepresentation {
+    Latex,
+    #[default]
+    Html,
+    Ascii,
+    Latin1,
+    Utf8,
+}
 
 impl Entity {
     fn entity(&self) -> Option<&(&str, &str, bool, &str, &str, &str, &str)> {
@@ -12,24 +26,36 @@ impl Entity {
         ENTITIES.iter().find(|i| i.0 == token.as_ref())
     }
 
-    /// Entity name
+    /// Entity name, for both built-in and user-defined entities
     ///
     /// ```rust
-    /// use orgize::{ast::Entity, Org};
+    /// use orgize::{ast::Entity, entities::EntityInfo, Org, ParseConfig};
     ///
     /// let e = Org::parse("\\alpha{}").first_node::<Entity>().unwrap();
     /// assert_eq!(e.name(), "alpha");
     /// let e = Org::parse("\\_     ").first_node::<Entity>().unwrap();
     /// assert_eq!(e.name(), "     ");
+    ///
+    /// let config = ParseConfig {
+    ///     user_entities: vec![EntityInfo {
+    ///         name: "check".into(),
+    ///         latex: "\\checkmark".into(),
+    ///         latex_math: false,
+    ///         html: "&check;".into(),
+    ///         ascii: "v".into(),
+    ///         latin1: "v".into(),
+    ///         utf8: "✓".into(),
+    ///     }],
+    ///     ..Default::default()
+    /// };
+    /// let e = config.parse("\\check").first_node::<Entity>().unwrap();
+    /// assert_eq!(e.name(), "check");
     /// ```
-    pub fn name(&self) -> &str {
-        self.entity().map_or_else(
-            || {
-                debug_assert!(false);
-                ""
-            },
-            |e| e.0,
-        )
+    pub fn name(&self) -> Token {
+        self.syntax
+            .children_with_tokens()
+            .find_map(filter_token(SyntaxKind::TEXT))
+            .expect("entity must contains TEXT")
     }
 
     /// Entity LaTeX representation
@@ -41,13 +67,7 @@ impl Entity {
     /// assert_eq!(e.latex(), "\\textperiodcentered{}");
     /// ```
     pub fn latex(&self) -> &str {
-        self.entity().map_or_else(
-            || {
-                debug_assert!(false);
-                ""
-            },
-            |e| e.1,
-        )
+        self.entity().map_or("", |e| e.1)
     }
 
     /// Whether entity needs to be in math mode
@@ -61,13 +81,7 @@ impl Entity {
     /// assert!(e.is_latex_math());
     /// ```
     pub fn is_latex_math(&self) -> bool {
-        self.entity().map_or_else(
-            || {
-                debug_assert!(false);
-                false
-            },
-            |e| e.2,
-        )
+        self.entity().is_some_and(|e| e.2)
     }
 
     /// Entity HTML representation
@@ -79,13 +93,7 @@ impl Entity {
     /// assert_eq!(e.html(), "&sect;");
     /// ```
     pub fn html(&self) -> &str {
-        self.entity().map_or_else(
-            || {
-                debug_assert!(false);
-                ""
-            },
-            |e| e.3,
-        )
+        self.entity().map_or("", |e| e.3)
     }
 
     /// Entity ASCII representation
@@ -97,13 +105,7 @@ impl Entity {
     /// assert_eq!(e.ascii(), "section");
     /// ```
     pub fn ascii(&self) -> &str {
-        self.entity().map_or_else(
-            || {
-                debug_assert!(false);
-                ""
-            },
-            |e| e.4,
-        )
+        self.entity().map_or("", |e| e.4)
     }
 
     /// Entity Latin1 encoding representation
@@ -117,13 +119,7 @@ impl Entity {
     /// assert_eq!(e.latin1(), ">");
     /// ```
     pub fn latin1(&self) -> &str {
-        self.entity().map_or_else(
-            || {
-                debug_assert!(false);
-                ""
-            },
-            |e| e.5,
-        )
+        self.entity().map_or("", |e| e.5)
     }
 
     /// Entity UTF-8 encoding representation
@@ -137,13 +133,82 @@ impl Entity {
     /// assert_eq!(e.utf8(), "›");
     /// ```
     pub fn utf8(&self) -> &str {
-        self.entity().map_or_else(
-            || {
-                debug_assert!(false);
-                ""
-            },
-            |e| e.6,
-        )
+        self.entity().map_or("", |e| e.6)
+    }
+
+    /// Entity UTF-8 representation, same as [`Entity::utf8`]
+    ///
+    /// ```rust
+    /// use orgize::{ast::Entity, Org};
+    ///
+    /// let e = Org::parse("\\alpha").first_node::<Entity>().unwrap();
+    /// assert_eq!(e.unicode(), "α");
+    /// ```
+    pub fn unicode(&self) -> &str {
+        self.utf8()
+    }
+
+    /// Returns all representations of this entity, looking up user-defined
+    /// entities in given config first
+    ///
+    /// Accessors like [`Entity::html`] only consider builtin entities, and return
+    /// an empty string for user-defined ones.
+    ///
+    /// ```rust
+    /// use orgize::{ast::Entity, Org};
+    ///
+    /// let org = Org::parse("\\alpha");
+    /// let e = org.first_node::<Entity>().unwrap();
+    /// assert_eq!(e.info(org.config()).unwrap().latex, "\\alpha");
+    /// ```
+    pub fn info(&self, config: &ParseConfig) -> Option<EntityInfo> {
+        match self.user_entity(&config.user_entities) {
+            Some(entity) => Some(entity.clone()),
+            None => lookup(&self.name()),
+        }
+    }
+
+    /// Returns given representation, looking up user-defined entities first
+    pub(crate) fn representation_with<'a>(
+        &'a self,
+        repr: EntityRepresentation,
+        entities: &'a [EntityInfo],
+    ) -> &'a str {
+        match self.user_entity(entities) {
+            Some(info) => info.representation(repr),
+            None => self.representation(repr),
+        }
+    }
+
+    /// Finds this entity in given user-defined entities
+    pub(crate) fn user_entity<'a>(&self, entities: &'a [EntityInfo]) -> Option<&'a EntityInfo> {
+        let token = self
+            .syntax
+            .children_with_tokens()
+            .find_map(filter_token(SyntaxKind::TEXT))?;
+
+        entities.iter().find(|e| e.name == token.as_ref())
+    }
+
+    /// Entity representation in given export target
+    ///
+    /// ```rust
+    /// use orgize::{ast::{Entity, EntityRepresentation}, Org};
+    ///
+    /// let e = Org::parse("\\rarr").first_node::<Entity>().unwrap();
+    /// assert_eq!(e.representation(EntityRepresentation::Latex), "\\rightarrow");
+    /// assert_eq!(e.representation(EntityRepresentation::Html), "&rarr;");
+    /// assert_eq!(e.representation(EntityRepresentation::Ascii), "->");
+    /// assert_eq!(e.representation(EntityRepresentation::Utf8), "→");
+    /// ```
+    pub fn representation(&self, repr: EntityRepresentation) -> &str {
+        match repr {
+            EntityRepresentation::Latex => self.latex(),
+            EntityRepresentation::Html => self.html(),
+            EntityRepresentation::Ascii => self.ascii(),
+            EntityRepresentation::Latin1 => self.latin1(),
+            EntityRepresentation::Utf8 => self.utf8(),
+        }
     }
 
     /// Entity contains optional brackets
@@ -5,11 +5,17 @@ use super::{filter_token, FixedWidth};
 impl FixedWidth {
     /// Contents without colons prefix
     ///
+    /// Only the colon and one whitespace following it are removed from each
+    /// line, so extra indentation and blank lines are preserved.
+    ///
     /// ```rust
     /// use orgize::{ast::FixedWidth, Org};
     ///
     /// let fixed = Org::parse(": A\n:\n: B\n: C").first_node::<FixedWidth>().unwrap();
     /// assert_eq!(fixed.value(), "A\n\nB\nC");
+    ///
+    /// let fixed = Org::parse("  : def f():\n  :     return 1\n").first_node::<FixedWidth>().unwrap();
+    /// assert_eq!(fixed.value(), "def f():\n    return 1\n");
     /// ```
     pub fn value(&self) -> String {
         self.syntax
@@ -32,6 +32,16 @@ const nodes = [
     children: [["headlines", "Headline"]],
     post_blank: true,
   },
+  {
+    struct: "InlineTask",
+    kind: ["INLINE_TASK"],
+    first_child: [
+      ["section", "Section"],
+      ["planning", "Planning"],
+      ["properties", "PropertyDrawer"],
+    ],
+    post_blank: true,
+  },
   {
     struct: "PropertyDrawer",
     kind: ["PROPERTY_DRAWER"],
@@ -200,6 +210,17 @@ const nodes = [
     kind: ["FN_REF"],
     token: [["label", "FN_LABEL"]],
   },
+  {
+    struct: "Citation",
+    kind: ["CITATION"],
+    token: [["style", "CITATION_STYLE"]],
+    children: [["references", "CitationReference"]],
+  },
+  {
+    struct: "CitationReference",
+    kind: ["CITATION_REFERENCE"],
+    token: [["key", "CITATION_KEY"]],
+  },
   {
     struct: "Macros",
     kind: ["MACROS"],
@@ -214,6 +214,53 @@ impl Headline {
     }
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct InlineTask {
+    pub(crate) syntax: SyntaxNode,
+}
+impl AstNode for InlineTask {
+    type Language = OrgLanguage;
+    fn can_cast(kind: SyntaxKind) -> bool {
+        kind == INLINE_TASK
+    }
+    fn cast(node: SyntaxNode) -> Option<InlineTask> {
+        Self::can_cast(node.kind()).then(|| InlineTask { syntax: node })
+    }
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
+}
+impl InlineTask {
+    /// Beginning position of this element
+    pub fn start(&self) -> TextSize {
+        self.syntax.text_range().start()
+    }
+    /// Ending position of this element
+    pub fn end(&self) -> TextSize {
+        self.syntax.text_range().end()
+    }
+    /// Range of this element
+    pub fn text_range(&self) -> TextRange {
+        self.syntax.text_range()
+    }
+    /// Raw text of this element
+    pub fn raw(&self) -> String {
+        self.syntax.to_string()
+    }
+    pub fn section(&self) -> Option<Section> {
+        support::child(&self.syntax)
+    }
+    pub fn planning(&self) -> Option<Planning> {
+        support::child(&self.syntax)
+    }
+    pub fn properties(&self) -> Option<PropertyDrawer> {
+        support::child(&self.syntax)
+    }
+    pub fn post_blank(&self) -> usize {
+        super::blank_lines(&self.syntax)
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct PropertyDrawer {
     pub(crate) syntax: SyntaxNode,
@@ -1749,6 +1796,85 @@ impl FnRef {
     }
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Citation {
+    pub(crate) syntax: SyntaxNode,
+}
+impl AstNode for Citation {
+    type Language = OrgLanguage;
+    fn can_cast(kind: SyntaxKind) -> bool {
+        kind == CITATION
+    }
+    fn cast(node: SyntaxNode) -> Option<Citation> {
+        Self::can_cast(node.kind()).then(|| Citation { syntax: node })
+    }
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
+}
+impl Citation {
+    /// Beginning position of this element
+    pub fn start(&self) -> TextSize {
+        self.syntax.text_range().start()
+    }
+    /// Ending position of this element
+    pub fn end(&self) -> TextSize {
+        self.syntax.text_range().end()
+    }
+    /// Range of this element
+    pub fn text_range(&self) -> TextRange {
+        self.syntax.text_range()
+    }
+    /// Raw text of this element
+    pub fn raw(&self) -> String {
+        self.syntax.to_string()
+    }
+    pub fn style(&self) -> Option<super::Token> {
+        super::token(&self.syntax, CITATION_STYLE)
+    }
+    pub fn references(&self) -> AstChildren<CitationReference> {
+        support::children(&self.syntax)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CitationReference {
+    pub(crate) syntax: SyntaxNode,
+}
+impl AstNode for CitationReference {
+    type Language = OrgLanguage;
+    fn can_cast(kind: SyntaxKind) -> bool {
+        kind == CITATION_REFERENCE
+    }
+    fn cast(node: SyntaxNode) -> Option<CitationReference> {
+        Self::can_cast(node.kind()).then(|| CitationReference { syntax: node })
+    }
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
+}
+impl CitationReference {
+    /// Beginning position of this element
+    pub fn start(&self) -> TextSize {
+        self.syntax.text_range().start()
+    }
+    /// Ending position of this element
+    pub fn end(&self) -> TextSize {
+        self.syntax.text_range().end()
+    }
+    /// Range of this element
+    pub fn text_range(&self) -> TextRange {
+        self.syntax.text_range()
+    }
+    /// Raw text of this element
+    pub fn raw(&self) -> String {
+        self.syntax.to_string()
+    }
+    pub fn key(&self) -> Option<super::Token> {
+        super::token(&self.syntax, CITATION_KEY)
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Macros {
     pub(crate) syntax: SyntaxNode,
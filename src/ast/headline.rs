@@ -1,8 +1,14 @@
-use rowan::{ast::AstNode, NodeOrToken};
+use rowan::{ast::AstNode, NodeOrToken, TextRange, TextSize, WalkEvent};
 
-use crate::{syntax::SyntaxKind, SyntaxElement};
+use std::collections::{HashMap, HashSet};
 
-use super::{filter_token, Clock, Drawer, Headline, Section, Timestamp, Token};
+use crate::export::{Container, Event, ExportTags, PlainTextExport, TraversalContext, Traverser};
+use crate::{syntax::SyntaxKind, Org, ParseConfig, SyntaxElement, SyntaxNode};
+
+use super::{
+    accumulate_values, checkbox_statistics, filter_token, Clock, Cookie, Document, Drawer,
+    Headline, List, ListItem, OptionValue, PropertyDrawer, Section, Timestamp, Token,
+};
 
 #[derive(Debug, Copy, Clone, PartialEq)]
 pub enum TodoType {
@@ -10,6 +16,37 @@ pub enum TodoType {
     Done,
 }
 
+/// Controls what [`Headline::word_count`] and [`Headline::char_count`] exclude
+///
+/// Everything listed is excluded by default.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WordCountOptions {
+    /// Exclude child headlines tagged with `:noexport:`
+    pub skip_noexport: bool,
+    /// Exclude property drawers
+    pub skip_property_drawers: bool,
+    /// Exclude planning lines, e.g. `SCHEDULED: <2024-01-01>`
+    pub skip_planning: bool,
+}
+
+impl Default for WordCountOptions {
+    fn default() -> Self {
+        WordCountOptions {
+            skip_noexport: true,
+            skip_property_drawers: true,
+            skip_planning: true,
+        }
+    }
+}
+
+/// Todo state of headline, see [`Headline::effective_todo_state`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TodoState {
+    Todo(String),
+    Done(String),
+    None,
+}
+
 impl Headline {
     /// Return level of this headline
     ///
@@ -35,10 +72,24 @@ impl Headline {
     }
 
     /// ```rust
-    /// use orgize::{Org, ast::Headline};
+    /// use orgize::{Org, ParseConfig, ast::Headline};
     ///
     /// let hdl = Org::parse("* TODO a").first_node::<Headline>().unwrap();
     /// assert_eq!(hdl.todo_keyword().unwrap(), "TODO");
+    /// let hdl = Org::parse("* TODOa").first_node::<Headline>().unwrap();
+    /// assert!(hdl.todo_keyword().is_none());
+    /// let hdl = Org::parse("* WAITING a").first_node::<Headline>().unwrap();
+    /// assert!(hdl.todo_keyword().is_none());
+    ///
+    /// // keywords are taken from parse config
+    /// let config = ParseConfig {
+    ///     todo_keywords: (vec!["WAITING".into()], vec!["CANCELLED".into()]),
+    ///     ..Default::default()
+    /// };
+    /// let hdl = config.clone().parse("* WAITING a").first_node::<Headline>().unwrap();
+    /// assert_eq!(hdl.todo_keyword().unwrap(), "WAITING");
+    /// let hdl = config.parse("* TODO a").first_node::<Headline>().unwrap();
+    /// assert!(hdl.todo_keyword().is_none());
     /// ```
     pub fn todo_keyword(&self) -> Option<Token> {
         self.syntax
@@ -89,17 +140,111 @@ impl Headline {
     }
 
     /// ```rust
-    /// use orgize::{Org, ast::Headline};
+    /// use orgize::{Org, ParseConfig, ast::Headline};
     ///
     /// let hdl = Org::parse("* DONE a").first_node::<Headline>().unwrap();
     /// assert!(hdl.is_done());
     /// let hdl = Org::parse("* a").first_node::<Headline>().unwrap();
     /// assert!(!hdl.is_done());
+    ///
+    /// let config = ParseConfig {
+    ///     todo_keywords: (vec![], vec!["CANCELLED".into()]),
+    ///     ..Default::default()
+    /// };
+    /// let hdl = config.clone().parse("* CANCELLED a").first_node::<Headline>().unwrap();
+    /// assert!(hdl.is_done());
+    /// let hdl = config.parse("* DONE a").first_node::<Headline>().unwrap();
+    /// assert!(!hdl.is_done());
     /// ```
     pub fn is_done(&self) -> bool {
         matches!(self.todo_type(), Some(TodoType::Done))
     }
 
+    /// Classifies todo state of this headline against keywords in given config
+    ///
+    /// Unlike [`Headline::todo_type`], which reflects the config this headline was
+    /// parsed with, the first word of title is also checked, so headlines can be
+    /// re-classified with another workflow without reparsing.
+    ///
+    /// ```rust
+    /// use orgize::{Org, ParseConfig, ast::{Headline, TodoState}};
+    ///
+    /// let config = ParseConfig {
+    ///     todo_keywords: (
+    ///         vec!["TODO".into(), "WAITING".into()],
+    ///         vec!["DONE".into(), "CANCELLED".into()],
+    ///     ),
+    ///     ..Default::default()
+    /// };
+    ///
+    /// let org = config.clone().parse("* WAITING a\n* CANCELLED b\n* c");
+    /// let states: Vec<_> = org
+    ///     .document()
+    ///     .headlines()
+    ///     .map(|hdl| hdl.effective_todo_state(&config))
+    ///     .collect();
+    /// assert_eq!(
+    ///     states,
+    ///     vec![
+    ///         TodoState::Todo("WAITING".into()),
+    ///         TodoState::Done("CANCELLED".into()),
+    ///         TodoState::None
+    ///     ]
+    /// );
+    ///
+    /// // parsed with default config
+    /// let hdl = Org::parse("* WAITING a").first_node::<Headline>().unwrap();
+    /// assert_eq!(hdl.effective_todo_state(&config), TodoState::Todo("WAITING".into()));
+    /// assert_eq!(hdl.effective_todo_state(&ParseConfig::default()), TodoState::None);
+    /// ```
+    pub fn effective_todo_state(&self, config: &ParseConfig) -> TodoState {
+        let keyword = match self.todo_keyword() {
+            Some(keyword) => keyword.to_string(),
+            None => self
+                .title_raw()
+                .split_ascii_whitespace()
+                .next()
+                .unwrap_or_default()
+                .to_string(),
+        };
+
+        let (todo, done) = &config.todo_keywords;
+
+        if todo.contains(&keyword) {
+            TodoState::Todo(keyword)
+        } else if done.contains(&keyword) {
+            TodoState::Done(keyword)
+        } else {
+            TodoState::None
+        }
+    }
+
+    /// Returns anchor id of this headline
+    ///
+    /// It's the value of `CUSTOM_ID` property if present, otherwise a slug of title.
+    /// Slugs are deduplicated across the whole document by appending `-1`, `-2`, etc.,
+    /// skipping any suffix already taken by another slug or `CUSTOM_ID`.
+    ///
+    /// ```rust
+    /// use orgize::{Org, ast::Headline};
+    ///
+    /// let org = Org::parse("* Hello, *World*!\n* Intro\n:PROPERTIES:\n:CUSTOM_ID: custom\n:END:\n* Intro\n* Intro");
+    /// let anchors: Vec<_> = org.document().headlines().map(|hdl| hdl.anchor()).collect();
+    /// assert_eq!(anchors, vec!["hello-world", "custom", "intro", "intro-1"]);
+    /// ```
+    pub fn anchor(&self) -> String {
+        let root = self
+            .syntax
+            .ancestors()
+            .last()
+            .unwrap_or(self.syntax.clone());
+        headline_anchors(&root)
+            .into_iter()
+            .find(|(hdl, _)| hdl == self)
+            .map(|(_, anchor)| anchor)
+            .unwrap_or_default()
+    }
+
     /// Returns parsed title
     ///
     /// ```rust
@@ -137,6 +282,181 @@ impl Headline {
             .unwrap_or_default()
     }
 
+    /// Returns range of content before the first child headline, i.e.
+    /// planning, property drawer and section
+    ///
+    /// Range starts at the end of headline line, before its line break, and
+    /// ends at the end of the last non-blank line, so folding it keeps the
+    /// headline line and the following line break visible. Range is empty if
+    /// this headline has no content.
+    ///
+    /// ```rust
+    /// use orgize::{Org, ast::Headline, rowan::TextRange};
+    ///
+    /// let org = Org::parse("* a\ntext\n\n** b\nmore\n\n* c\n");
+    /// let hdl = org.first_node::<Headline>().unwrap();
+    /// assert_eq!(hdl.content_range(), TextRange::new(3.into(), 8.into()));
+    /// assert_eq!(&org.to_org()[hdl.content_range()], "\ntext");
+    ///
+    /// let hdl = org.document().headlines().last().unwrap();
+    /// assert_eq!(hdl.content_range(), TextRange::empty(24.into()));
+    /// ```
+    pub fn content_range(&self) -> TextRange {
+        let end = self
+            .headlines()
+            .next()
+            .map_or_else(|| self.end(), |hdl| hdl.start());
+        self.fold_range(end)
+    }
+
+    /// Returns range of this subtree without headline line, i.e. what an
+    /// editor collapses when folding this headline
+    ///
+    /// Boundaries follow [`Headline::content_range`], so trailing blank lines
+    /// stay visible and separate it from the next headline.
+    ///
+    /// ```rust
+    /// use orgize::{Org, ast::Headline};
+    ///
+    /// let org = Org::parse("* a\ntext\n\n** b\nmore  \n\n* c\n");
+    /// let hdl = org.first_node::<Headline>().unwrap();
+    /// assert_eq!(&org.to_org()[hdl.subtree_range()], "\ntext\n\n** b\nmore  ");
+    /// ```
+    pub fn subtree_range(&self) -> TextRange {
+        self.fold_range(self.end())
+    }
+
+    /// Returns range from the end of headline line to the end of the last
+    /// non-blank line before `end`
+    fn fold_range(&self, end: TextSize) -> TextRange {
+        let text = self.syntax.to_string();
+        let text = &text[..usize::from(end - self.start())];
+
+        let line_end = |from: usize| {
+            text[from..]
+                .find(['\n', '\r'])
+                .map_or(text.len(), |i| i + from)
+        };
+
+        let start = line_end(0);
+        let end = line_end(text.trim_end().len()).max(start);
+
+        TextRange::new(
+            self.start() + TextSize::from(start as u32),
+            self.start() + TextSize::from(end as u32),
+        )
+    }
+
+    /// Returns the first statistics cookie in title, e.g. `[1/3]` or `[33%]`
+    ///
+    /// ```rust
+    /// use orgize::{Org, ast::Headline};
+    ///
+    /// let hdl = Org::parse("* Tasks [1/3] :tag:").first_node::<Headline>().unwrap();
+    /// assert_eq!(hdl.cookie().unwrap().raw(), "[1/3]");
+    /// let hdl = Org::parse("* Tasks").first_node::<Headline>().unwrap();
+    /// assert!(hdl.cookie().is_none());
+    /// ```
+    pub fn cookie(&self) -> Option<Cookie> {
+        self.syntax
+            .children()
+            .find(|n| n.kind() == SyntaxKind::HEADLINE_TITLE)?
+            .children()
+            .find_map(Cookie::cast)
+    }
+
+    /// Returns `(done, total)` statistics of this headline, used by statistics cookies
+    ///
+    /// Counts child headlines with todo keywords, or checkboxes in the section of
+    /// this headline. `:COOKIE_DATA:` property selects which one is counted, by
+    /// containing `todo` or `checkbox`. Without it, todo headlines are counted if
+    /// there's any, otherwise checkboxes. If it contains `recursive`, all
+    /// descendant headlines or nested checkboxes are counted too.
+    ///
+    /// ```rust
+    /// use orgize::{Org, ast::Headline};
+    ///
+    /// let org = Org::parse(r#"* Tasks [/]
+    /// ** DONE a
+    /// ** TODO b
+    /// *** DONE b.1
+    /// ** note
+    /// * Recursive [%]
+    /// :PROPERTIES:
+    /// :COOKIE_DATA: todo recursive
+    /// :END:
+    /// ** DONE a
+    /// ** TODO b
+    /// *** DONE b.1
+    /// * Checkboxes [/]
+    /// - [X] a
+    /// - [ ] b
+    ///   - [X] b.1"#);
+    /// let mut headlines = org.document().headlines();
+    /// assert_eq!(headlines.next().unwrap().statistics(), (1, 2));
+    /// assert_eq!(headlines.next().unwrap().statistics(), (2, 3));
+    /// assert_eq!(headlines.next().unwrap().statistics(), (1, 2));
+    ///
+    /// let org = Org::parse("* Checkboxes [/]\n:PROPERTIES:\n:COOKIE_DATA: checkbox\n:END:\n- [X] a\n- [ ] b\n** DONE ignored");
+    /// let hdl = org.first_node::<Headline>().unwrap();
+    /// assert_eq!(hdl.statistics(), (1, 2));
+    /// assert_eq!(hdl.cookie().unwrap().format_statistics(1, 2), "[1/2]");
+    /// ```
+    pub fn statistics(&self) -> (usize, usize) {
+        let cookie_data = self
+            .properties()
+            .and_then(|p| p.get("COOKIE_DATA"))
+            .map(|v| v.to_ascii_lowercase())
+            .unwrap_or_default();
+        let recursive = cookie_data.contains("recursive");
+
+        let headlines: Vec<Headline> = if recursive {
+            self.syntax
+                .descendants()
+                .skip(1)
+                .filter_map(Headline::cast)
+                .collect()
+        } else {
+            self.headlines().collect()
+        };
+        let todos = || {
+            headlines
+                .iter()
+                .filter(|hdl| hdl.todo_keyword().is_some())
+                .fold((0, 0), |(done, total), hdl| {
+                    (done + hdl.is_done() as usize, total + 1)
+                })
+        };
+
+        let checkboxes = || {
+            let Some(section) = self.section() else {
+                return (0, 0);
+            };
+            if recursive {
+                checkbox_statistics(section.syntax.descendants().filter_map(ListItem::cast))
+            } else {
+                checkbox_statistics(
+                    section
+                        .syntax
+                        .children()
+                        .filter_map(List::cast)
+                        .flat_map(|list| list.items()),
+                )
+            }
+        };
+
+        if cookie_data.contains("checkbox") {
+            checkboxes()
+        } else if cookie_data.contains("todo") {
+            todos()
+        } else {
+            match todos() {
+                (_, 0) => checkboxes(),
+                statistics => statistics,
+            }
+        }
+    }
+
     /// Return `true` if this headline contains a COMMENT keyword
     ///      
     /// ```rust
@@ -148,6 +468,14 @@ impl Headline {
     /// assert!(hdl.is_commented());
     /// let hdl = Org::parse("* hello").first_node::<Headline>().unwrap();
     /// assert!(!hdl.is_commented());
+    /// let hdl = Org::parse("* TODO [#A] COMMENT hello :tag:").first_node::<Headline>().unwrap();
+    /// assert!(hdl.is_commented());
+    /// let hdl = Org::parse("* DONE COMMENT").first_node::<Headline>().unwrap();
+    /// assert!(hdl.is_commented());
+    /// let hdl = Org::parse("* COMMENTS").first_node::<Headline>().unwrap();
+    /// assert!(!hdl.is_commented());
+    /// let hdl = Org::parse("* hello COMMENT").first_node::<Headline>().unwrap();
+    /// assert!(!hdl.is_commented());
     /// ```
     pub fn is_commented(&self) -> bool {
         self.title()
@@ -180,11 +508,42 @@ impl Headline {
     }
 
     /// Returns this headline's closed timestamp, or `None` if not set.
+    ///
+    /// Planning keywords are only recognized on the line directly after the
+    /// headline, and can appear in any order on that line.
+    ///
+    /// ```rust
+    /// use orgize::{Org, ast::Headline};
+    ///
+    /// let org = Org::parse("* DONE a\nCLOSED: [2019-04-08 Mon 10:00] DEADLINE: <2019-04-09 Tue>");
+    /// let hdl = org.first_node::<Headline>().unwrap();
+    /// let closed = hdl.closed().unwrap();
+    /// assert!(closed.is_inactive());
+    /// assert_eq!(closed.hour_start().unwrap(), "10");
+    /// assert_eq!(hdl.deadline().unwrap().day_start().unwrap(), "09");
+    /// assert!(hdl.scheduled().is_none());
+    ///
+    /// let org = Org::parse("* a\n\nCLOSED: [2019-04-08 Mon 10:00]");
+    /// let hdl = org.first_node::<Headline>().unwrap();
+    /// assert!(hdl.planning().is_none());
+    /// assert!(hdl.closed().is_none());
+    /// ```
     pub fn closed(&self) -> Option<Timestamp> {
         self.planning().and_then(|planning| planning.closed())
     }
 
     /// Returns this headline's scheduled timestamp, or `None` if not set.
+    ///
+    /// ```rust
+    /// use orgize::{Org, ast::Headline};
+    ///
+    /// let org = Org::parse("* a\nDEADLINE: <2019-04-09 Tue> SCHEDULED: <2019-04-01 Mon +1w>");
+    /// let hdl = org.first_node::<Headline>().unwrap();
+    /// let scheduled = hdl.scheduled().unwrap();
+    /// assert!(scheduled.is_active());
+    /// assert_eq!(scheduled.day_start().unwrap(), "01");
+    /// assert_eq!(scheduled.repeater_value(), Some(1));
+    /// ```
     pub fn scheduled(&self) -> Option<Timestamp> {
         self.planning().and_then(|planning| planning.scheduled())
     }
@@ -209,6 +568,8 @@ impl Headline {
     /// assert_eq!(tags_vec("* [#A] :::::a2%:"), vec!["a2%".to_string()]);
     /// assert_eq!(tags_vec("* TODO :tag:  :a2%:"), vec!["tag".to_string(), "a2%".to_string()]);
     /// assert_eq!(tags_vec("* title :tag:a2%:"), vec!["tag".to_string(), "a2%".to_string()]);
+    /// assert_eq!(tags_vec("* title :ARCHIVE:a:"), vec!["ARCHIVE".to_string(), "a".to_string()]);
+    /// assert!(tags_vec("* title :not tag:").is_empty());
     /// ```
     pub fn tags(&self) -> impl Iterator<Item = Token> {
         self.syntax
@@ -228,8 +589,30 @@ impl Headline {
     /// assert_eq!(hdl.priority().unwrap(), "A");
     /// let hdl = Org::parse("** DONE [#B]::").first_node::<Headline>().unwrap();
     /// assert_eq!(hdl.priority().unwrap(), "B");
-    /// let hdl = Org::parse("* [#破]").first_node::<Headline>().unwrap();
-    /// assert_eq!(hdl.priority().unwrap(), "破");
+    /// let hdl = Org::parse("* [#AB]").first_node::<Headline>().unwrap();
+    /// assert!(hdl.priority().is_none());
+    /// ```
+    ///
+    /// Only characters between [`ParseConfig::priority_highest`] and
+    /// [`ParseConfig::priority_lowest`] are recognized:
+    ///
+    /// ```rust
+    /// use orgize::{Org, ParseConfig, ast::Headline};
+    ///
+    /// let hdl = Org::parse("* [#D] a").first_node::<Headline>().unwrap();
+    /// assert!(hdl.priority().is_none());
+    /// assert_eq!(hdl.title_raw(), "[#D] a");
+    ///
+    /// let config = ParseConfig {
+    ///     priority_highest: '1',
+    ///     priority_lowest: '9',
+    ///     priority_default: '5',
+    ///     ..Default::default()
+    /// };
+    /// let hdl = config.clone().parse("* [#3] a").first_node::<Headline>().unwrap();
+    /// assert_eq!(hdl.priority().unwrap(), "3");
+    /// let hdl = config.parse("* [#A] a").first_node::<Headline>().unwrap();
+    /// assert!(hdl.priority().is_none());
     /// ```
     pub fn priority(&self) -> Option<Token> {
         self.syntax
@@ -241,6 +624,106 @@ impl Headline {
             })
     }
 
+    /// Returns priority of this headline, or the default priority if it has none
+    ///
+    /// ```rust
+    /// use orgize::{Org, ParseConfig, ast::Headline};
+    ///
+    /// let config = ParseConfig::default();
+    /// let hdl = Org::parse("* [#A] a").first_node::<Headline>().unwrap();
+    /// assert_eq!(hdl.effective_priority(&config), 'A');
+    /// let hdl = Org::parse("* a").first_node::<Headline>().unwrap();
+    /// assert_eq!(hdl.effective_priority(&config), 'B');
+    /// ```
+    pub fn effective_priority(&self, config: &ParseConfig) -> char {
+        self.priority()
+            .and_then(|p| p.chars().next())
+            .unwrap_or(config.priority_default)
+    }
+
+    /// Returns value of given property, inheriting from ancestor headlines and
+    /// the document-level property drawer
+    ///
+    /// Keys are compared case-insensitively and the nearest value wins. Use
+    /// [`Headline::property_accumulated`] to take `:KEY+:` into account.
+    ///
+    /// ```rust
+    /// use orgize::{Org, ast::Headline};
+    ///
+    /// let org = Org::parse(r#":PROPERTIES:
+    /// :EXPORT_FILE_NAME: index
+    /// :END:
+    /// * a
+    /// :PROPERTIES:
+    /// :AUTHOR: poi
+    /// :END:
+    /// ** b
+    /// :PROPERTIES:
+    /// :AUTHOR: pia
+    /// :END:
+    /// *** c"#);
+    /// let c = org.document().headlines().last().unwrap();
+    /// let c = c.headlines().next().unwrap().headlines().next().unwrap();
+    /// assert_eq!(c.property("author").unwrap(), "pia");
+    /// assert_eq!(c.property("EXPORT_FILE_NAME").unwrap(), "index");
+    /// assert!(c.property("ID").is_none());
+    /// ```
+    pub fn property(&self, key: &str) -> Option<Token> {
+        self.property_drawers().find_map(|drawer| {
+            drawer
+                .values(key)
+                .filter(|(_, plus)| !plus)
+                .last()
+                .map(|(value, _)| value)
+        })
+    }
+
+    /// Returns value of given property, concatenating `:KEY+:` values from
+    /// this headline and its ancestors with spaces
+    ///
+    /// ```rust
+    /// use orgize::{Org, ast::Headline};
+    ///
+    /// let org = Org::parse(r#":PROPERTIES:
+    /// :VAR: x=1
+    /// :END:
+    /// * a
+    /// :PROPERTIES:
+    /// :VAR+: y=2
+    /// :END:
+    /// ** b
+    /// :PROPERTIES:
+    /// :VAR+: z=3
+    /// :END:
+    /// * c
+    /// :PROPERTIES:
+    /// :VAR: w=4
+    /// :VAR+: v=5
+    /// :END:"#);
+    /// let mut headlines = org.document().headlines();
+    /// let a = headlines.next().unwrap();
+    /// let b = a.headlines().next().unwrap();
+    /// let c = headlines.next().unwrap();
+    /// assert_eq!(a.property_accumulated("VAR").unwrap(), "x=1 y=2");
+    /// assert_eq!(b.property_accumulated("VAR").unwrap(), "x=1 y=2 z=3");
+    /// assert_eq!(c.property_accumulated("VAR").unwrap(), "w=4 v=5");
+    /// assert!(c.property_accumulated("TAGS").is_none());
+    /// ```
+    pub fn property_accumulated(&self, key: &str) -> Option<String> {
+        accumulate_values(self.property_drawers(), key)
+    }
+
+    /// Returns property drawers of this headline, its ancestors and the document, nearest first
+    fn property_drawers(&self) -> impl Iterator<Item = PropertyDrawer> {
+        self.syntax.ancestors().filter_map(|node| {
+            if let Some(headline) = Headline::cast(node.clone()) {
+                headline.properties()
+            } else {
+                Document::cast(node).and_then(|doc| doc.properties())
+            }
+        })
+    }
+
     /// Returns an iterator of clock element affiliated with this headline
     ///
     /// ```rust
@@ -273,4 +756,520 @@ impl Headline {
             })
             .flat_map(|x| x.children().filter_map(Clock::cast))
     }
+
+    /// Returns a self-contained org document of this subtree
+    ///
+    /// Headline levels are normalized so this headline becomes level 1, tags and
+    /// properties inherited from ancestors are added to this headline, and a
+    /// `#+TITLE` is synthesized from its title. `#+FILETAGS` and `#+CATEGORY`
+    /// of the document are inherited as well, while `ID` and `CUSTOM_ID` are
+    /// never inherited since they identify a single entry.
+    ///
+    /// ```rust
+    /// use orgize::{Org, ast::Headline};
+    ///
+    /// let org = Org::parse(r#"* Book :book:
+    /// :PROPERTIES:
+    /// :AUTHOR: poi
+    /// :END:
+    /// ** Chapter 1 :draft:
+    /// *** Section 1.1
+    /// text"#);
+    /// let hdl = org.first_node::<Headline>().unwrap().headlines().next().unwrap();
+    /// assert_eq!(hdl.to_standalone_org(&org), r#"#+TITLE: Chapter 1
+    /// * Chapter 1 :book:draft:
+    /// :PROPERTIES:
+    /// :AUTHOR: poi
+    /// :END:
+    /// ** Section 1.1
+    /// text"#);
+    ///
+    /// let org = Org::parse(r#"#+FILETAGS: :work:
+    /// #+CATEGORY: notes
+    /// * Book
+    /// :PROPERTIES:
+    /// :ID: 1
+    /// :CUSTOM_ID: book
+    /// :END:
+    /// ** Chapter"#);
+    /// let hdl = org.first_node::<Headline>().unwrap().headlines().next().unwrap();
+    /// assert_eq!(hdl.to_standalone_org(&org), r#"#+TITLE: Chapter
+    /// * Chapter :work:
+    /// :PROPERTIES:
+    /// :CATEGORY: notes
+    /// :END:
+    /// "#);
+    /// ```
+    pub fn to_standalone_org(&self, org: &Org) -> String {
+        let ancestors: Vec<Headline> = self
+            .syntax
+            .ancestors()
+            .skip(1)
+            .filter_map(Headline::cast)
+            .collect();
+        let document = self.syntax.ancestors().last().and_then(Document::cast);
+        let keywords: Vec<_> = document.iter().flat_map(|doc| doc.keywords()).collect();
+
+        let mut tags: Vec<String> = vec![];
+        let filetags = keywords
+            .iter()
+            .filter(|k| k.key().eq_ignore_ascii_case("FILETAGS"))
+            .flat_map(|k| {
+                k.value()
+                    .split([':', ' ', '\t'])
+                    .filter(|tag| !tag.is_empty())
+                    .map(String::from)
+                    .collect::<Vec<_>>()
+            });
+        let headline_tags = ancestors
+            .iter()
+            .rev()
+            .flat_map(|hdl| hdl.tags())
+            .chain(self.tags())
+            .map(|tag| tag.to_string());
+        for tag in filetags.chain(headline_tags) {
+            if !tags.contains(&tag) {
+                tags.push(tag);
+            }
+        }
+
+        // `#+CATEGORY` comes first, then document properties, and nearer
+        // ancestors override farther ones
+        let mut properties: Vec<(String, String)> = keywords
+            .iter()
+            .rfind(|k| k.key().eq_ignore_ascii_case("CATEGORY"))
+            .map(|k| ("CATEGORY".to_string(), k.value().trim().to_string()))
+            .filter(|(_, value)| !value.is_empty())
+            .into_iter()
+            .collect();
+        let drawers = document
+            .and_then(|doc| doc.properties())
+            .into_iter()
+            .chain(ancestors.iter().rev().filter_map(|hdl| hdl.properties()));
+        for (key, value) in drawers.flat_map(|drawer| drawer.iter().collect::<Vec<_>>()) {
+            if key.eq_ignore_ascii_case("ID") || key.eq_ignore_ascii_case("CUSTOM_ID") {
+                continue;
+            }
+            if let Some(p) = properties.iter_mut().find(|(k, _)| key == *k) {
+                p.1 = value.to_string();
+            } else {
+                properties.push((key.to_string(), value.to_string()));
+            }
+        }
+
+        let mut sub = org.config().clone().parse(self.raw());
+        sub.shift_headlines(1);
+        let mut text = sub.to_org();
+        let Some(hdl) = sub.first_node::<Headline>() else {
+            return text;
+        };
+
+        // edits are applied from the end, so offsets stay valid
+        let mut edits: Vec<(usize, usize, String)> = vec![];
+
+        let own = hdl.properties();
+        let missing: String = properties
+            .iter()
+            .filter(|(k, _)| own.as_ref().is_none_or(|own| own.get(k).is_none()))
+            .map(|(k, v)| format!(":{k}: {v}\n"))
+            .collect();
+
+        let line_end = hdl
+            .syntax
+            .children_with_tokens()
+            .find(|e| e.kind() == SyntaxKind::NEW_LINE)
+            .map(|e| e.text_range().end());
+
+        // tags go first, so they stay before a drawer inserted at the same offset
+        if !tags.is_empty() {
+            let tags = format!(":{}:", tags.join(":"));
+            if let Some(node) = hdl
+                .syntax
+                .children()
+                .find(|n| n.kind() == SyntaxKind::HEADLINE_TAGS)
+            {
+                let range = node.text_range();
+                edits.push((range.start().into(), range.end().into(), tags));
+            } else {
+                let end = line_end.map_or(text.len(), usize::from);
+                let title_end = text[..end].trim_end().len();
+                edits.push((title_end, title_end, format!(" {tags}")));
+            }
+        }
+
+        if !missing.is_empty() {
+            if let Some(own) = &own {
+                let end = usize::from(own.content_end());
+                edits.push((end, end, missing));
+            } else {
+                let offset = hdl
+                    .planning()
+                    .map(|p| p.syntax.text_range().end())
+                    .or(line_end)
+                    .map(usize::from);
+                let drawer = format!(":PROPERTIES:\n{missing}:END:\n");
+                match offset {
+                    Some(offset) => edits.push((offset, offset, drawer)),
+                    None => edits.push((text.len(), text.len(), format!("\n{drawer}"))),
+                }
+            }
+        }
+
+        edits.sort_by_key(|e| e.0);
+        for (start, end, replace_with) in edits.into_iter().rev() {
+            text.replace_range(start..end, &replace_with);
+        }
+
+        format!("#+TITLE: {}\n{text}", self.title_raw().trim())
+    }
+    /// Returns number of words in this subtree, including its title
+    ///
+    /// Words are counted in plain text of the subtree, see
+    /// [`PlainTextExport`], so markup, drawers, comments and commented
+    /// headlines are excluded. Source blocks, example blocks and fixed-width
+    /// areas are excluded as well. Property drawers, planning lines and child
+    /// headlines tagged with `:noexport:` are excluded according to `options`.
+    ///
+    /// ```rust
+    /// use orgize::{Org, ast::{Headline, WordCountOptions}};
+    ///
+    /// let org = Org::parse(r#"* Chapter *one*
+    /// SCHEDULED: <2024-01-01>
+    /// :PROPERTIES:
+    /// :ID: 1
+    /// :END:
+    /// Some /emphasized/ text.
+    /// #+BEGIN_SRC rust
+    /// fn main() {}
+    /// #+END_SRC
+    /// ** Notes :noexport:
+    /// draft words
+    /// "#);
+    /// let hdl = org.first_node::<Headline>().unwrap();
+    /// assert_eq!(hdl.word_count(WordCountOptions::default()), 5);
+    ///
+    /// let options = WordCountOptions { skip_noexport: false, ..Default::default() };
+    /// assert_eq!(hdl.word_count(options), 8);
+    /// let options = WordCountOptions { skip_planning: false, ..Default::default() };
+    /// assert_eq!(hdl.word_count(options), 7);
+    /// let options = WordCountOptions { skip_property_drawers: false, ..Default::default() };
+    /// assert_eq!(hdl.word_count(options), 7);
+    /// ```
+    pub fn word_count(&self, options: WordCountOptions) -> usize {
+        self.prose_text(options).split_whitespace().count()
+    }
+
+    /// Returns number of characters in this subtree, including its title
+    ///
+    /// Text is collected in the same way as [`Headline::word_count`]. Words
+    /// are counted as if separated by single spaces, so line breaks and
+    /// indentation don't affect the result.
+    ///
+    /// ```rust
+    /// use orgize::{Org, ast::{Headline, WordCountOptions}};
+    ///
+    /// let org = Org::parse("* Title\n  Hello   *wörld*\n** Draft :noexport:\nxyz");
+    /// let hdl = org.first_node::<Headline>().unwrap();
+    /// let options = WordCountOptions::default();
+    /// assert_eq!(hdl.char_count(options), "Title Hello wörld".chars().count());
+    /// let options = WordCountOptions { skip_noexport: false, ..options };
+    /// assert_eq!(hdl.char_count(options), "Title Hello wörld Draft xyz".chars().count());
+    /// ```
+    pub fn char_count(&self, options: WordCountOptions) -> usize {
+        let text = self.prose_text(options);
+        let (words, chars) = text
+            .split_whitespace()
+            .fold((0usize, 0), |(words, chars), word| {
+                (words + 1, chars + word.chars().count())
+            });
+        chars + words.saturating_sub(1)
+    }
+
+    /// Renders this subtree to plain text, without code
+    fn prose_text(&self, options: WordCountOptions) -> String {
+        struct Prose<'a> {
+            root: &'a SyntaxNode,
+            options: WordCountOptions,
+            plain: PlainTextExport,
+        }
+
+        impl Traverser for Prose<'_> {
+            fn event(&mut self, event: Event, ctx: &mut TraversalContext) {
+                match &event {
+                    Event::Enter(Container::SourceBlock(_))
+                    | Event::Enter(Container::ExampleBlock(_))
+                    | Event::Enter(Container::FixedWidth(_)) => return ctx.skip(),
+                    Event::Enter(Container::Headline(hdl))
+                        if self.options.skip_noexport
+                            && &hdl.syntax != self.root
+                            && hdl.tags().any(|tag| tag == "noexport") =>
+                    {
+                        return ctx.skip()
+                    }
+                    Event::Enter(Container::PropertyDrawer(drawer))
+                        if !self.options.skip_property_drawers =>
+                    {
+                        for (key, value) in drawer.iter() {
+                            self.plain.push_str(format!("{key} {value}\n"));
+                        }
+                        return ctx.skip();
+                    }
+                    _ => {}
+                }
+                let planning = match &event {
+                    Event::Enter(Container::Headline(hdl)) if !self.options.skip_planning => {
+                        hdl.planning()
+                    }
+                    _ => None,
+                };
+                self.plain.event(event, ctx);
+                // planning lines aren't traversed, so they're rendered right after the title
+                if let Some(planning) = planning {
+                    self.plain.push_str(planning.syntax.to_string().trim());
+                    self.plain.push_str("\n");
+                }
+            }
+        }
+
+        let mut prose = Prose {
+            root: &self.syntax,
+            options,
+            plain: PlainTextExport::default(),
+        };
+        prose.element(
+            SyntaxElement::Node(self.syntax.clone()),
+            &mut TraversalContext::default(),
+        );
+        prose.plain.finish()
+    }
+}
+
+/// Returns all headlines under `root` and their anchor ids, in document order
+pub(crate) fn headline_anchors(root: &SyntaxNode) -> Vec<(Headline, String)> {
+    let custom_id = |hdl: &Headline| {
+        hdl.properties()
+            .and_then(|properties| properties.get("CUSTOM_ID"))
+            .map(|id| id.trim().to_string())
+            .filter(|id| !id.is_empty())
+    };
+
+    // custom ids are reserved up front, so slugs never collide with them
+    let mut used: HashSet<String> = root
+        .descendants()
+        .filter_map(Headline::cast)
+        .filter_map(|hdl| custom_id(&hdl))
+        .collect();
+    let mut suffixes = HashMap::<String, usize>::new();
+
+    root.descendants()
+        .filter_map(Headline::cast)
+        .map(|hdl| {
+            let anchor = custom_id(&hdl).unwrap_or_else(|| {
+                let slug = slugify(&hdl.title_raw());
+                let mut anchor = slug.clone();
+                let suffix = suffixes.entry(slug.clone()).or_default();
+                while used.contains(&anchor) {
+                    *suffix += 1;
+                    anchor = format!("{slug}-{suffix}");
+                }
+                used.insert(anchor.clone());
+                anchor
+            });
+
+            (hdl, anchor)
+        })
+        .collect()
+}
+
+/// Returns section numbers of headlines under `root`, e.g. `1.2`, in document order
+///
+/// Commented headlines, headlines excluded by `tags` and headlines with a
+/// non-nil `UNNUMBERED` property, i.e. `t` or `notoc`, are skipped along with
+/// their subtrees, as well as headlines deeper than `max_depth`. Depth is
+/// relative to the shallowest headline, so a document starting with `**` is
+/// still numbered from `1`.
+pub(crate) fn headline_numbers(
+    root: &SyntaxNode,
+    max_depth: usize,
+    tags: Option<&ExportTags>,
+) -> Vec<(Headline, String)> {
+    let excluded = tags
+        .map(|tags| tags.excluded_headlines(root))
+        .unwrap_or_default();
+    let Some(min_level) = root
+        .descendants()
+        .filter_map(Headline::cast)
+        .map(|hdl| hdl.level())
+        .min()
+    else {
+        return vec![];
+    };
+
+    let mut counters: Vec<usize> = vec![];
+    let mut numbers = vec![];
+    let mut preorder = root.preorder();
+
+    while let Some(event) = preorder.next() {
+        let WalkEvent::Enter(node) = event else {
+            continue;
+        };
+        // headlines never appear inside sections
+        if node.kind() == SyntaxKind::SECTION {
+            preorder.skip_subtree();
+            continue;
+        }
+        let Some(hdl) = Headline::cast(node) else {
+            continue;
+        };
+
+        let depth = hdl.level() + 1 - min_level;
+        if depth > max_depth
+            || hdl.is_commented()
+            || excluded.contains(hdl.syntax())
+            || hdl
+                .property("UNNUMBERED")
+                .is_some_and(|value| value.trim() != "nil")
+        {
+            preorder.skip_subtree();
+            continue;
+        }
+
+        counters.resize(depth, 0);
+        counters[depth - 1] += 1;
+        let number = counters
+            .iter()
+            .map(|n| n.to_string())
+            .collect::<Vec<_>>()
+            .join(".");
+        numbers.push((hdl, number));
+    }
+
+    numbers
+}
+
+/// Converts headline title to a lowercase, hyphen-separated slug
+pub(crate) fn slugify(title: &str) -> String {
+    let mut slug = String::with_capacity(title.len());
+    for c in title.chars() {
+        if c.is_alphanumeric() {
+            slug.extend(c.to_lowercase());
+        } else if (c.is_whitespace() || c == '-' || c == '_') && !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+    let slug = slug.trim_matches('-');
+    if slug.is_empty() {
+        "headline".into()
+    } else {
+        slug.into()
+    }
+}
+
+impl Org {
+    /// Returns all headlines and their anchor ids, in document order, see [Headline::anchor]
+    ///
+    /// ```rust
+    /// use orgize::Org;
+    ///
+    /// let org = Org::parse("* a\n** b\n* a");
+    /// let anchors: Vec<_> = org.headline_anchors().into_iter().map(|(_, anchor)| anchor).collect();
+    /// assert_eq!(anchors, vec!["a", "b", "a-1"]);
+    ///
+    /// // generated suffixes and custom ids are never reused
+    /// let org = Org::parse("* a\n* a\n* a-1\n* x\n* y\n:PROPERTIES:\n:CUSTOM_ID: x\n:END:");
+    /// let anchors: Vec<_> = org.headline_anchors().into_iter().map(|(_, anchor)| anchor).collect();
+    /// assert_eq!(anchors, vec!["a", "a-1", "a-1-1", "x-1", "x"]);
+    /// ```
+    pub fn headline_anchors(&self) -> Vec<(Headline, String)> {
+        headline_anchors(self.document().syntax())
+    }
+
+    /// Returns section numbers of headlines as `(anchor, number)` tuples in
+    /// document order, e.g. `("usage", "1.2")`
+    ///
+    /// Headlines excluded from numbering are omitted, see
+    /// [`HtmlExport::with_section_numbers`](crate::export::HtmlExport::with_section_numbers).
+    /// Export tags are read from the document, see [`ExportTags::from_document`].
+    /// Depth is further limited by `num:N` in `#+OPTIONS`, and `num:nil`
+    /// disables numbering entirely. Anchors are the same as in
+    /// [`Org::table_of_contents`], so both can be joined together.
+    ///
+    /// ```rust
+    /// use orgize::Org;
+    ///
+    /// let org = Org::parse(r#"* Introduction
+    /// ** Motivation
+    /// * Appendix
+    /// :PROPERTIES:
+    /// :UNNUMBERED: t
+    /// :END:
+    /// ** Details
+    /// * Usage
+    /// *** Deep
+    /// "#);
+    /// assert_eq!(
+    ///     org.section_numbers(3),
+    ///     vec![
+    ///         ("introduction".into(), "1".into()),
+    ///         ("motivation".into(), "1.1".into()),
+    ///         ("usage".into(), "2".into()),
+    ///         ("deep".into(), "2.0.1".into()),
+    ///     ]
+    /// );
+    ///
+    /// let org = Org::parse("#+OPTIONS: num:1\n* a\n** b");
+    /// assert_eq!(org.section_numbers(3), vec![("a".into(), "1".into())]);
+    ///
+    /// let org = Org::parse("#+OPTIONS: num:nil\n* a\n** b");
+    /// assert!(org.section_numbers(3).is_empty());
+    ///
+    /// let org = Org::parse("#+EXPORT_EXCLUDE_TAGS: draft\n* a :draft:\n* b :noexport:\n* c");
+    /// assert_eq!(
+    ///     org.section_numbers(3),
+    ///     vec![("b".into(), "1".into()), ("c".into(), "2".into())]
+    /// );
+    ///
+    /// let org = Org::parse("* a\n* b :export:\n** c");
+    /// assert_eq!(
+    ///     org.section_numbers(3),
+    ///     vec![("b".into(), "1".into()), ("c".into(), "1.1".into())]
+    /// );
+    /// ```
+    pub fn section_numbers(&self, max_depth: usize) -> Vec<(String, String)> {
+        let max_depth = match self.options().remove("num") {
+            Some(OptionValue::Bool(false)) => return vec![],
+            Some(OptionValue::Integer(depth)) => max_depth.min(depth),
+            _ => max_depth,
+        };
+
+        let anchors: HashMap<_, _> = headline_anchors(self.document().syntax())
+            .into_iter()
+            .map(|(hdl, anchor)| (hdl.syntax, anchor))
+            .collect();
+
+        let tags = ExportTags::from_document(&self.document());
+        headline_numbers(self.document().syntax(), max_depth, Some(&tags))
+            .into_iter()
+            .map(|(hdl, number)| {
+                let anchor = anchors.get(&hdl.syntax).cloned().unwrap_or_default();
+                (anchor, number)
+            })
+            .collect()
+    }
+
+    /// Equals to `headline.to_standalone_org(self)`, see [Headline::to_standalone_org]
+    ///
+    /// ```rust
+    /// use orgize::{Org, ast::Headline};
+    ///
+    /// let org = Org::parse("* a :x:\n:PROPERTIES:\n:A: 1\n:END:\n** TODO b\n:PROPERTIES:\n:B: 2\n:END:\n");
+    /// let hdl = org.first_node::<Headline>().unwrap().headlines().next().unwrap();
+    /// assert_eq!(
+    ///     org.subtree_to_org(&hdl),
+    ///     "#+TITLE: b\n* TODO b :x:\n:PROPERTIES:\n:B: 2\n:A: 1\n:END:\n"
+    /// );
+    /// ```
+    pub fn subtree_to_org(&self, headline: &Headline) -> String {
+        headline.to_standalone_org(self)
+    }
 }
@@ -0,0 +1,138 @@
+use rowan::NodeOrToken;
+
+use crate::{syntax::SyntaxKind, SyntaxElement};
+
+use super::{filter_token, InlineTask, TodoType, Token};
+
+impl InlineTask {
+    /// Return level of this inline task
+    ///
+    /// ```rust
+    /// use orgize::{Org, ast::InlineTask};
+    ///
+    /// let task = Org::parse("*************** task").first_node::<InlineTask>().unwrap();
+    /// assert_eq!(task.level(), 15);
+    /// ```
+    pub fn level(&self) -> usize {
+        self.syntax
+            .children_with_tokens()
+            .find_map(filter_token(SyntaxKind::HEADLINE_STARS))
+            .map_or_else(
+                || {
+                    debug_assert!(false, "inline task must contains HEADLINE_STARS");
+                    0
+                },
+                |stars| stars.len(),
+            )
+    }
+
+    /// ```rust
+    /// use orgize::{Org, ast::{InlineTask, TodoType}};
+    ///
+    /// let task = Org::parse("*************** TODO task").first_node::<InlineTask>().unwrap();
+    /// assert_eq!(task.todo_keyword().unwrap(), "TODO");
+    /// assert_eq!(task.todo_type().unwrap(), TodoType::Todo);
+    /// let task = Org::parse("*************** task").first_node::<InlineTask>().unwrap();
+    /// assert!(task.todo_keyword().is_none());
+    /// ```
+    pub fn todo_keyword(&self) -> Option<Token> {
+        self.syntax
+            .children_with_tokens()
+            .find_map(|elem| match elem {
+                NodeOrToken::Token(tk)
+                    if tk.kind() == SyntaxKind::HEADLINE_KEYWORD_TODO
+                        || tk.kind() == SyntaxKind::HEADLINE_KEYWORD_DONE =>
+                {
+                    Some(Token(tk))
+                }
+                _ => None,
+            })
+    }
+
+    pub fn todo_type(&self) -> Option<TodoType> {
+        self.syntax
+            .children_with_tokens()
+            .find_map(|elem| match elem {
+                NodeOrToken::Token(tk) if tk.kind() == SyntaxKind::HEADLINE_KEYWORD_TODO => {
+                    Some(TodoType::Todo)
+                }
+                NodeOrToken::Token(tk) if tk.kind() == SyntaxKind::HEADLINE_KEYWORD_DONE => {
+                    Some(TodoType::Done)
+                }
+                _ => None,
+            })
+    }
+
+    /// Returns parsed title
+    ///
+    /// ```rust
+    /// use orgize::{Org, ast::InlineTask, SyntaxKind};
+    ///
+    /// let task = Org::parse("*************** abc *abc*").first_node::<InlineTask>().unwrap();
+    /// let title = task.title().collect::<Vec<_>>();
+    /// assert_eq!(title[1].kind(), SyntaxKind::BOLD);
+    /// assert_eq!(task.title_raw(), "abc *abc*");
+    /// ```
+    pub fn title(&self) -> impl Iterator<Item = SyntaxElement> {
+        self.syntax
+            .children()
+            .find(|n| n.kind() == SyntaxKind::HEADLINE_TITLE)
+            .into_iter()
+            .flat_map(|n| n.children_with_tokens())
+    }
+
+    /// Returns title raw string
+    pub fn title_raw(&self) -> String {
+        self.syntax
+            .children()
+            .find(|n| n.kind() == SyntaxKind::HEADLINE_TITLE)
+            .map(|n| n.to_string())
+            .unwrap_or_default()
+    }
+
+    /// Returns tags
+    ///
+    /// ```rust
+    /// use orgize::{Org, ast::InlineTask};
+    ///
+    /// let task = Org::parse("*************** [#A] task :a:b:").first_node::<InlineTask>().unwrap();
+    /// assert_eq!(task.tags().map(|t| t.to_string()).collect::<Vec<_>>(), ["a", "b"]);
+    /// assert_eq!(task.priority().unwrap(), "A");
+    /// ```
+    pub fn tags(&self) -> impl Iterator<Item = Token> {
+        self.syntax
+            .children()
+            .find(|n| n.kind() == SyntaxKind::HEADLINE_TAGS)
+            .into_iter()
+            .flat_map(|t| t.children_with_tokens())
+            .filter_map(filter_token(SyntaxKind::TEXT))
+    }
+
+    /// Returns priority text
+    pub fn priority(&self) -> Option<Token> {
+        self.syntax
+            .children()
+            .find(|n| n.kind() == SyntaxKind::HEADLINE_PRIORITY)
+            .and_then(|n| {
+                n.children_with_tokens()
+                    .find_map(filter_token(SyntaxKind::TEXT))
+            })
+    }
+
+    /// Returns `true` if this inline task is closed by an `END` line
+    ///
+    /// ```rust
+    /// use orgize::{Org, ast::InlineTask};
+    ///
+    /// let task = Org::parse("*************** task\nbody\n*************** END").first_node::<InlineTask>().unwrap();
+    /// assert!(task.has_end());
+    /// assert_eq!(task.section().unwrap().raw(), "body\n");
+    /// let task = Org::parse("*************** task\nbody").first_node::<InlineTask>().unwrap();
+    /// assert!(!task.has_end());
+    /// ```
+    pub fn has_end(&self) -> bool {
+        self.syntax
+            .children()
+            .any(|n| n.kind() == SyntaxKind::INLINE_TASK_END)
+    }
+}
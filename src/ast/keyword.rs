@@ -17,6 +17,24 @@ impl Keyword {
             .expect("keyword must contains TEXT")
     }
 
+    /// Returns optional value in brackets, e.g. `short` in `#+CAPTION[short]: long`
+    ///
+    /// ```rust
+    /// use orgize::{Org, ast::Keyword};
+    ///
+    /// let keyword = Org::parse("#+RESULTS[hash]: VALUE").first_node::<Keyword>().unwrap();
+    /// assert_eq!(keyword.optval().unwrap(), "hash");
+    /// assert_eq!(keyword.value(), " VALUE");
+    /// let keyword = Org::parse("#+KEY: VALUE").first_node::<Keyword>().unwrap();
+    /// assert!(keyword.optval().is_none());
+    /// ```
+    pub fn optval(&self) -> Option<Token> {
+        self.syntax
+            .children_with_tokens()
+            .find_map(filter_token(SyntaxKind::KEYWORD_OPTIONAL))
+    }
+
+    /// Returns value as written, including the leading whitespace
     ///
     /// ```rust
     /// use orgize::{Org, ast::Keyword};
@@ -30,7 +48,19 @@ impl Keyword {
         self.syntax
             .children_with_tokens()
             .filter_map(filter_token(SyntaxKind::TEXT))
-            .nth(1)
-            .expect("keyword must contains two TEXT")
+            .last()
+            .expect("keyword must contains TEXT")
+    }
+
+    /// Returns value without surrounding whitespace
+    ///
+    /// ```rust
+    /// use orgize::{Org, ast::Keyword};
+    ///
+    /// let keyword = Org::parse("#+KEY:  VALUE \nabc").first_node::<Keyword>().unwrap();
+    /// assert_eq!(keyword.value_trimmed(), "VALUE");
+    /// ```
+    pub fn value_trimmed(&self) -> String {
+        self.value().trim().to_string()
     }
 }
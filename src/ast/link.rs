@@ -1,7 +1,50 @@
-use rowan::ast::AstNode;
+use rowan::{ast::AstNode, TextSize};
+use std::collections::HashMap;
 
-use super::{token, AffiliatedKeyword, Link, Paragraph, Token};
-use crate::{syntax::SyntaxKind, SyntaxElement};
+use super::{
+    token, AffiliatedKeyword, ExampleBlock, Headline, Link, Paragraph, SourceBlock, Token,
+};
+use crate::{syntax::SyntaxKind, Org, ParseConfig, SyntaxElement, SyntaxNode};
+
+/// Link types recognized by default, equivalent to builtin `org-link-parameters`
+const LINK_TYPES: &[&str] = &[
+    "attachment",
+    "bbdb",
+    "docview",
+    "doi",
+    "elisp",
+    "eww",
+    "file",
+    "file+emacs",
+    "file+sys",
+    "ftp",
+    "gnus",
+    "help",
+    "http",
+    "https",
+    "id",
+    "info",
+    "irc",
+    "mailto",
+    "mhe",
+    "news",
+    "rmail",
+    "shell",
+    "w3m",
+];
+
+/// Flavors of internal link
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum InternalLinkKind {
+    /// `[[#custom-id]]`, points to a headline with `CUSTOM_ID` property
+    CustomId,
+    /// `[[*Headline]]`, points to a headline by its title
+    Headline,
+    /// `[[My Target]]`, points to a target, named element or headline
+    Fuzzy,
+    /// `[[(coderef)]]`, points to a line in source block
+    CodeRef,
+}
 
 impl Link {
     /// Returns link destination
@@ -20,6 +63,130 @@ impl Link {
         token(&self.syntax, SyntaxKind::LINK_PATH).expect("link must contains LINK_PATH")
     }
 
+    /// Returns link protocol, aka. the scheme before the first colon
+    ///
+    /// Returns `None` if the scheme isn't a recognized link type, use
+    /// [`Link::protocol_with`] to accept custom link types.
+    ///
+    /// ```rust
+    /// use orgize::{Org, ast::Link};
+    ///
+    /// let protocol = |input: &str| Org::parse(input).first_node::<Link>().unwrap().protocol();
+    ///
+    /// assert_eq!(protocol("[[https://google.com]]").unwrap(), "https");
+    /// assert_eq!(protocol("[[file:a.org::*headline]]").unwrap(), "file");
+    /// assert_eq!(protocol("[[mailto:me@example.com]]").unwrap(), "mailto");
+    /// assert_eq!(protocol("[[ID:4f1e3a]]").unwrap(), "id");
+    /// assert!(protocol("[[*Headline: with colon]]").is_none());
+    /// assert!(protocol("[[Target: with colon]]").is_none());
+    /// assert!(protocol("[[./a.org]]").is_none());
+    /// ```
+    pub fn protocol(&self) -> Option<String> {
+        self.protocol_with::<&str>(&[])
+    }
+
+    /// Returns link protocol, also accepting given custom link types
+    ///
+    /// ```rust
+    /// use orgize::{Org, ast::Link};
+    ///
+    /// let link = Org::parse("[[zotero:ABCD][paper]]").first_node::<Link>().unwrap();
+    /// assert!(link.protocol().is_none());
+    /// assert_eq!(link.protocol_with(&["zotero"]).unwrap(), "zotero");
+    /// ```
+    pub fn protocol_with<S: AsRef<str>>(&self, custom: &[S]) -> Option<String> {
+        let path = self.path();
+        let (protocol, _) = path.split_once(':')?;
+        let protocol = protocol.to_ascii_lowercase();
+
+        (LINK_TYPES.contains(&protocol.as_str())
+            || custom
+                .iter()
+                .any(|t| t.as_ref().eq_ignore_ascii_case(&protocol)))
+        .then_some(protocol)
+    }
+
+    /// Returns search option, aka. the part after `::` in file links
+    ///
+    /// ```rust
+    /// use orgize::{Org, ast::Link};
+    ///
+    /// let search_option = |input: &str| Org::parse(input).first_node::<Link>().unwrap().search_option();
+    ///
+    /// assert_eq!(search_option("[[file:a.org::*headline]]").unwrap(), "*headline");
+    /// assert_eq!(search_option("[[./a.org::#custom-id]]").unwrap(), "#custom-id");
+    /// assert_eq!(search_option("[[file:a.org::255]]").unwrap(), "255");
+    /// assert!(search_option("[[file:a.org]]").is_none());
+    /// assert!(search_option("[[*Headline]]").is_none());
+    /// assert!(search_option("[[https://example.com/a::b]]").is_none());
+    /// ```
+    pub fn search_option(&self) -> Option<String> {
+        let path = self.path();
+        let is_file = match self.protocol() {
+            Some(protocol) => protocol.starts_with("file"),
+            None => path.starts_with(['/', '.', '~']),
+        };
+        if !is_file {
+            return None;
+        }
+        path.split_once("::").map(|(_, option)| option.to_string())
+    }
+
+    /// Returns link destination used by export backends
+    ///
+    /// `file:` protocol and search option are stripped from file links.
+    pub(crate) fn destination(&self) -> String {
+        let path = self.path();
+        match self.protocol() {
+            Some(protocol) if protocol.starts_with("file") => {
+                let path = &path[protocol.len() + 1..];
+                path.split_once("::")
+                    .map_or(path, |(path, _)| path)
+                    .to_string()
+            }
+            None if path.starts_with(['/', '.', '~']) => path
+                .split_once("::")
+                .map_or(&*path, |(path, _)| path)
+                .to_string(),
+            _ => path.to_string(),
+        }
+    }
+
+    /// Returns link path with its abbreviation expanded, see [`ParseConfig::link_abbreviations`]
+    ///
+    /// Paths without a known abbreviation are returned unchanged.
+    ///
+    /// ```rust
+    /// use orgize::{Org, ast::Link, rowan::ast::AstNode};
+    ///
+    /// let org = Org::parse(r#"#+LINK: gh https://github.com/%s
+    /// #+LINK: wiki https://en.wikipedia.org/wiki/%h
+    /// #+LINK: ddg https://duckduckgo.com/?q=
+    /// [[gh:PoiScript/orgize]] [[wiki:Org mode]] [[ddg:rust]] [[ddg]] [[zz:a]]"#);
+    /// let paths: Vec<_> = org
+    ///     .document()
+    ///     .syntax()
+    ///     .descendants()
+    ///     .filter_map(Link::cast)
+    ///     .map(|link| link.expanded_path(org.config()))
+    ///     .collect();
+    /// assert_eq!(
+    ///     paths,
+    ///     vec![
+    ///         "https://github.com/PoiScript/orgize",
+    ///         "https://en.wikipedia.org/wiki/Org%20mode",
+    ///         "https://duckduckgo.com/?q=rust",
+    ///         "https://duckduckgo.com/?q=",
+    ///         "zz:a",
+    ///     ]
+    /// );
+    /// ```
+    pub fn expanded_path(&self, config: &ParseConfig) -> String {
+        let path = self.path();
+        expand_link_abbreviation(&path, &config.link_abbreviations)
+            .unwrap_or_else(|| path.to_string())
+    }
+
     /// Returns `true` if link contains description
     ///
     /// ```rust
@@ -84,7 +251,7 @@ impl Link {
             .fold(String::new(), |acc, e| acc + &e.to_string())
     }
 
-    /// Returns `true` if link is an image link
+    /// Returns `true` if link path points to an image, regardless of its description
     ///
     /// ```rust
     /// use orgize::{Org, ast::Link};
@@ -93,6 +260,8 @@ impl Link {
     /// assert!(!link.is_image());
     /// let link = Org::parse("[[file:/home/dominik/images/jupiter.jpg]]").first_node::<Link>().unwrap();
     /// assert!(link.is_image());
+    /// let link = Org::parse("[[file:jupiter.jpg][Jupiter]]").first_node::<Link>().unwrap();
+    /// assert!(link.is_image());
     /// ```
     pub fn is_image(&self) -> bool {
         const IMAGE_SUFFIX: &[&str] = &[
@@ -103,7 +272,39 @@ impl Link {
 
         let path = self.path();
 
-        IMAGE_SUFFIX.iter().any(|e| path.ends_with(e)) && !self.has_description()
+        IMAGE_SUFFIX.iter().any(|e| path.ends_with(e))
+    }
+
+    /// Returns `true` if link is an image link without description
+    ///
+    /// Equals to `self.is_image() && !self.has_description()`, aka. an image displayed inline.
+    ///
+    /// ```rust
+    /// use orgize::{Org, ast::Link};
+    ///
+    /// let link = Org::parse("[[file:jupiter.jpg]]").first_node::<Link>().unwrap();
+    /// assert!(link.is_inline_image());
+    /// let link = Org::parse("[[file:jupiter.jpg][Jupiter]]").first_node::<Link>().unwrap();
+    /// assert!(!link.is_inline_image());
+    /// ```
+    pub fn is_inline_image(&self) -> bool {
+        self.is_image() && !self.has_description()
+    }
+
+    /// Returns alternate text of image link, taken from its description
+    ///
+    /// ```rust
+    /// use orgize::{Org, ast::Link};
+    ///
+    /// let link = Org::parse("[[file:jupiter.jpg][the *fifth* planet]]").first_node::<Link>().unwrap();
+    /// assert_eq!(link.image_alt().unwrap(), "the *fifth* planet");
+    /// let link = Org::parse("[[file:jupiter.jpg]]").first_node::<Link>().unwrap();
+    /// assert!(link.image_alt().is_none());
+    /// let link = Org::parse("[[https://google.com][Google]]").first_node::<Link>().unwrap();
+    /// assert!(link.image_alt().is_none());
+    /// ```
+    pub fn image_alt(&self) -> Option<String> {
+        (self.is_image() && self.has_description()).then(|| self.description_raw())
     }
 
     /// Returns caption keyword in this link
@@ -118,4 +319,217 @@ impl Link {
         // TODO: support other element type
         Paragraph::cast(self.syntax.parent()?.clone())?.caption()
     }
+
+    /// Returns the kind of this link if it's an internal link
+    ///
+    /// Returns `None` if link has a protocol like `https:` or points to a file path.
+    /// Like [`Link::protocol`], only recognized link types count as protocols,
+    /// so `[[Target: with colon]]` and `[[unknown:path]]` are fuzzy links, just
+    /// as in org-mode.
+    ///
+    /// ```rust
+    /// use orgize::{Org, ast::{Link, InternalLinkKind}};
+    ///
+    /// let kind = |input: &str| Org::parse(input).first_node::<Link>().unwrap().internal_kind();
+    ///
+    /// assert_eq!(kind("[[#custom-id]]"), Some(InternalLinkKind::CustomId));
+    /// assert_eq!(kind("[[*Some Headline]]"), Some(InternalLinkKind::Headline));
+    /// assert_eq!(kind("[[My Target]]"), Some(InternalLinkKind::Fuzzy));
+    /// assert_eq!(kind("[[(jump)]]"), Some(InternalLinkKind::CodeRef));
+    /// assert_eq!(kind("[[https://example.com]]"), None);
+    /// assert_eq!(kind("[[file:a.org::*headline]]"), None);
+    /// assert_eq!(kind("[[./image.png]]"), None);
+    /// assert_eq!(kind("[[Target: with colon]]"), Some(InternalLinkKind::Fuzzy));
+    /// assert_eq!(kind("[[zotero:ABCD]]"), Some(InternalLinkKind::Fuzzy));
+    /// ```
+    pub fn internal_kind(&self) -> Option<InternalLinkKind> {
+        let path = self.path();
+
+        if path.starts_with('#') {
+            Some(InternalLinkKind::CustomId)
+        } else if path.starts_with('*') {
+            Some(InternalLinkKind::Headline)
+        } else if path.starts_with('(') && path.ends_with(')') {
+            Some(InternalLinkKind::CodeRef)
+        } else if path.starts_with(['/', '.', '~']) || self.protocol().is_some() {
+            None
+        } else {
+            Some(InternalLinkKind::Fuzzy)
+        }
+    }
+}
+
+impl Link {
+    /// Finds the element this link points to in the same document
+    ///
+    /// Returns `None` if it's not an internal link or `id:` link, or target can't be found.
+    pub(crate) fn resolve(&self) -> Option<SyntaxNode> {
+        let root = self.syntax.ancestors().last()?;
+        LinkTargets::new(&root).resolve(self)
+    }
+}
+
+/// Elements internal links can point to, collected once per document
+///
+/// Each map keeps the first element in document order for each key.
+#[derive(Default)]
+pub(crate) struct LinkTargets {
+    ids: HashMap<String, SyntaxNode>,
+    custom_ids: HashMap<String, SyntaxNode>,
+    titles: HashMap<String, SyntaxNode>,
+    coderefs: HashMap<String, SyntaxNode>,
+    targets: HashMap<String, SyntaxNode>,
+    names: HashMap<String, SyntaxNode>,
+}
+
+impl LinkTargets {
+    pub(crate) fn new(root: &SyntaxNode) -> Self {
+        let mut targets = LinkTargets::default();
+
+        let insert = |map: &mut HashMap<String, SyntaxNode>, key: &str, node: &SyntaxNode| {
+            map.entry(key.trim().to_string())
+                .or_insert_with(|| node.clone());
+        };
+
+        for node in root.descendants() {
+            if let Some(hdl) = Headline::cast(node.clone()) {
+                insert(&mut targets.titles, &hdl.title_raw(), &node);
+                if let Some(properties) = hdl.properties() {
+                    if let Some(id) = properties.get("ID") {
+                        insert(&mut targets.ids, &id, &node);
+                    }
+                    if let Some(id) = properties.get("CUSTOM_ID") {
+                        insert(&mut targets.custom_ids, &id, &node);
+                    }
+                }
+            } else if node.kind() == SyntaxKind::TARGET {
+                if let Some(text) = token(&node, SyntaxKind::TEXT) {
+                    insert(&mut targets.targets, &text, &node);
+                }
+            } else if let Some(kw) = AffiliatedKeyword::cast(node.clone()) {
+                if let (true, Some(value), Some(parent)) = (
+                    kw.key().eq_ignore_ascii_case("NAME"),
+                    kw.value(),
+                    node.parent(),
+                ) {
+                    insert(&mut targets.names, &value, &parent);
+                }
+            } else {
+                let code = if let Some(block) = SourceBlock::cast(node.clone()) {
+                    block.value()
+                } else if let Some(block) = ExampleBlock::cast(node.clone()) {
+                    block.syntax.to_string()
+                } else {
+                    continue;
+                };
+                for (start, _) in code.match_indices("(ref:") {
+                    let label = &code[start + "(ref:".len()..];
+                    if let Some(end) = label.find(')') {
+                        targets
+                            .coderefs
+                            .entry(label[..end].to_string())
+                            .or_insert_with(|| node.clone());
+                    }
+                }
+            }
+        }
+
+        targets
+    }
+
+    /// Finds the element given link points to, see [`Org::resolve_link`]
+    pub(crate) fn resolve(&self, link: &Link) -> Option<SyntaxNode> {
+        let path = link.path();
+
+        if link.protocol().as_deref() == Some("id") {
+            return self.ids.get(path[3..].trim()).cloned();
+        }
+
+        match link.internal_kind()? {
+            InternalLinkKind::CustomId => self.custom_ids.get(path[1..].trim()).cloned(),
+            InternalLinkKind::Headline => self.titles.get(path[1..].trim()).cloned(),
+            InternalLinkKind::CodeRef => self.coderefs.get(&path[1..path.len() - 1]).cloned(),
+            InternalLinkKind::Fuzzy => {
+                let target = path.trim();
+                self.targets
+                    .get(target)
+                    .or_else(|| self.names.get(target))
+                    .or_else(|| self.titles.get(target))
+                    .cloned()
+            }
+        }
+    }
+}
+
+impl Org {
+    /// Resolves internal link to the offset of element it points to
+    ///
+    /// - `[[#custom-id]]` points to headline with given `CUSTOM_ID` property
+    /// - `[[id:xxx]]` points to headline with given `ID` property
+    /// - `[[*Headline]]` points to headline with given title
+    /// - `[[(coderef)]]` points to source block containing `(ref:coderef)`
+    /// - `[[target]]` points to `<<target>>`, element with `#+NAME: target` or headline
+    ///   with given title, in order
+    ///
+    /// Returns `None` if link target can't be found.
+    ///
+    /// ```rust
+    /// use orgize::{Org, ast::Link, rowan::ast::AstNode};
+    ///
+    /// let org = Org::parse(r#"* Intro
+    /// :PROPERTIES:
+    /// :CUSTOM_ID: intro
+    /// :ID: 1f2e
+    /// :END:
+    /// #+NAME: table
+    /// | a |
+    /// Some <<target>>.
+    ///
+    /// [[#intro]] [[id:1f2e]] [[*Intro]] [[table]] [[target]] [[Intro]] [[missing]] [[https://a.b]]
+    /// "#);
+    /// let offsets: Vec<_> = org
+    ///     .document()
+    ///     .syntax()
+    ///     .descendants()
+    ///     .filter_map(Link::cast)
+    ///     .map(|link| org.resolve_link(&link).map(u32::from))
+    ///     .collect();
+    /// assert_eq!(
+    ///     offsets,
+    ///     vec![Some(0), Some(0), Some(0), Some(55), Some(80), Some(0), None, None]
+    /// );
+    /// ```
+    pub fn resolve_link(&self, link: &Link) -> Option<TextSize> {
+        link.resolve().map(|node| node.text_range().start())
+    }
+}
+
+/// Expands link abbreviation like `gh:user/repo`, returns `None` if it's not a known abbreviation
+pub(crate) fn expand_link_abbreviation(
+    path: &str,
+    abbreviations: &HashMap<String, String>,
+) -> Option<String> {
+    let (abbrev, tag) = path.split_once(':').unwrap_or((path, ""));
+    let replacement = abbreviations.get(abbrev)?;
+
+    if replacement.contains("%s") {
+        Some(replacement.replace("%s", tag))
+    } else if replacement.contains("%h") {
+        Some(replacement.replace("%h", &url_encode(tag)))
+    } else {
+        Some(format!("{replacement}{tag}"))
+    }
+}
+
+/// Percent-encodes all characters except unreserved ones, like `url-hexify-string`
+fn url_encode(s: &str) -> String {
+    let mut encoded = String::with_capacity(s.len());
+    for b in s.bytes() {
+        if b.is_ascii_alphanumeric() || matches!(b, b'-' | b'_' | b'.' | b'~') {
+            encoded.push(b as char);
+        } else {
+            encoded += &format!("%{b:02X}");
+        }
+    }
+    encoded
 }
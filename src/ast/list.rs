@@ -1,6 +1,58 @@
 use super::{filter_token, List, ListItem, Token};
 use crate::{syntax::SyntaxKind, SyntaxElement};
 
+/// State of list item checkbox
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Checkbox {
+    /// `[ ]`
+    Unchecked,
+    /// `[X]`
+    Checked,
+    /// `[-]`
+    Indeterminate,
+}
+
+/// Bullet of unordered list items
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub enum ListBullet {
+    /// `-`
+    #[default]
+    Minus,
+    /// `+`
+    Plus,
+    /// `*`
+    Star,
+}
+
+impl ListBullet {
+    fn as_char(self) -> char {
+        match self {
+            ListBullet::Minus => '-',
+            ListBullet::Plus => '+',
+            ListBullet::Star => '*',
+        }
+    }
+}
+
+/// Delimiter following the counter of ordered list items
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub enum CounterDelimiter {
+    /// `1.`
+    #[default]
+    Period,
+    /// `1)`
+    Parenthesis,
+}
+
+impl CounterDelimiter {
+    fn as_char(self) -> char {
+        match self {
+            CounterDelimiter::Period => '.',
+            CounterDelimiter::Parenthesis => ')',
+        }
+    }
+}
+
 impl List {
     /// Returns `true` if this list is an ordered link
     ///
@@ -49,9 +101,35 @@ impl List {
             },
         )
     }
+
+    /// Returns `(done, total)` of checkboxes in items of this list
+    ///
+    /// Only direct items are counted, nested lists are ignored. Indeterminate
+    /// checkboxes are counted as not done.
+    ///
+    /// ```rust
+    /// use orgize::{Org, ast::List};
+    ///
+    /// let list = Org::parse("- [X] a\n- [ ] b\n  - [X] b.1\n- [-] c\n- d").first_node::<List>().unwrap();
+    /// assert_eq!(list.statistics(), (1, 3));
+    /// ```
+    pub fn statistics(&self) -> (usize, usize) {
+        checkbox_statistics(self.items())
+    }
+}
+
+/// Counts checked and total checkboxes in given items
+pub(crate) fn checkbox_statistics(items: impl Iterator<Item = ListItem>) -> (usize, usize) {
+    items
+        .filter_map(|item| item.checkbox_state())
+        .fold((0, 0), |(done, total), checkbox| {
+            (done + (checkbox == Checkbox::Checked) as usize, total + 1)
+        })
 }
 
 impl ListItem {
+    /// Returns length of leading whitespace in bytes, tabs are not expanded
+    ///
     /// ```rust
     /// use orgize::{Org, ast::ListItem};
     ///
@@ -73,6 +151,15 @@ impl ListItem {
             )
     }
 
+    /// Returns leading whitespace as written, so exporters keep tabs instead
+    /// of guessing their width
+    pub(crate) fn indent_token(&self) -> Token {
+        self.syntax
+            .children_with_tokens()
+            .find_map(filter_token(SyntaxKind::LIST_ITEM_INDENT))
+            .expect("list item must contains LIST_ITEM_INDENT")
+    }
+
     /// ```rust
     /// use orgize::{Org, ast::ListItem};
     ///
@@ -88,6 +175,28 @@ impl ListItem {
             .expect("list item must contains LIST_ITEM_BULLET")
     }
 
+    /// Returns bullet with its marker replaced by given bullet or delimiter,
+    /// `None` keeps the original one
+    ///
+    /// Trailing whitespace is kept, so content and nested items stay aligned.
+    pub(crate) fn restyled_bullet(
+        &self,
+        bullet: Option<ListBullet>,
+        delimiter: Option<CounterDelimiter>,
+    ) -> String {
+        let text = self.bullet();
+        let rest = text.trim_start_matches(|c: char| c.is_ascii_digit());
+        let counter = &text[..text.len() - rest.len()];
+        let mut chars = rest.chars();
+        let original = chars.next().unwrap_or_default();
+        let marker = if counter.is_empty() {
+            bullet.map_or(original, ListBullet::as_char)
+        } else {
+            delimiter.map_or(original, CounterDelimiter::as_char)
+        };
+        format!("{counter}{marker}{}", chars.as_str())
+    }
+
     /// ```rust
     /// use orgize::{Org, ast::ListItem};
     ///
@@ -108,6 +217,28 @@ impl ListItem {
             })
     }
 
+    /// Returns parsed checkbox state
+    ///
+    /// ```rust
+    /// use orgize::{Org, ast::{Checkbox, ListItem}};
+    ///
+    /// let item = Org::parse("- [-] item 1").first_node::<ListItem>().unwrap();
+    /// assert_eq!(item.checkbox_state(), Some(Checkbox::Indeterminate));
+    /// let item = Org::parse("2. [X] item 2").first_node::<ListItem>().unwrap();
+    /// assert_eq!(item.checkbox_state(), Some(Checkbox::Checked));
+    /// let item = Org::parse("3) [ ] item 3").first_node::<ListItem>().unwrap();
+    /// assert_eq!(item.checkbox_state(), Some(Checkbox::Unchecked));
+    /// let item = Org::parse("- item 4").first_node::<ListItem>().unwrap();
+    /// assert_eq!(item.checkbox_state(), None);
+    /// ```
+    pub fn checkbox_state(&self) -> Option<Checkbox> {
+        match self.checkbox()?.as_ref() {
+            "X" | "x" => Some(Checkbox::Checked),
+            "-" => Some(Checkbox::Indeterminate),
+            _ => Some(Checkbox::Unchecked),
+        }
+    }
+
     pub fn counter(&self) -> Option<Token> {
         self.syntax
             .children()
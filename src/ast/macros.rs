@@ -1,6 +1,13 @@
-use crate::SyntaxKind;
+use std::collections::HashMap;
 
-use super::{filter_token, Macros, Token};
+use rowan::ast::AstNode;
+
+use crate::{Org, SyntaxKind, SyntaxNode};
+
+use super::{filter_token, Document, Keyword, Macros, Token};
+
+/// Maximum depth of nested macro expansion
+const MAX_EXPANSION_DEPTH: usize = 16;
 
 impl Macros {
     /// ```rust
@@ -32,4 +39,202 @@ impl Macros {
             .filter_map(filter_token(SyntaxKind::TEXT))
             .nth(1)
     }
+
+    /// Expands this macro with given definitions, see [`Org::macros`]
+    ///
+    /// `$1`, `$2`, etc. in definition are replaced by arguments, and macros inside
+    /// expanded text are expanded recursively. `{{{keyword(NAME)}}}` expands to the
+    /// value of `#+NAME` keyword.
+    ///
+    /// Returns `None` if macro is not defined, or expansion is nested too deeply.
+    ///
+    /// ```rust
+    /// use orgize::{Org, ast::Macros, rowan::ast::AstNode};
+    ///
+    /// let org = Org::parse(r#"#+TITLE: Doc
+    /// #+MACRO: greet Hello, $1 and $2!
+    /// #+MACRO: nested {{{greet(A,B)}}} in {{{title}}}
+    /// #+MACRO: loop {{{loop}}}
+    /// {{{greet(world, you\, too)}}} {{{nested}}} {{{keyword(title)}}} {{{loop}}} {{{undefined}}}"#);
+    /// let defs = org.macros();
+    /// let expanded: Vec<_> = org
+    ///     .document()
+    ///     .syntax()
+    ///     .descendants()
+    ///     .filter_map(Macros::cast)
+    ///     .map(|m| m.expand(&defs))
+    ///     .collect();
+    /// assert_eq!(
+    ///     expanded,
+    ///     vec![
+    ///         Some("Hello, world and you, too!".into()),
+    ///         Some("Hello, A and B! in Doc".into()),
+    ///         Some("Doc".into()),
+    ///         None,
+    ///         None,
+    ///     ]
+    /// );
+    /// ```
+    pub fn expand(&self, defs: &HashMap<String, String>) -> Option<String> {
+        let args = self
+            .args()
+            .map(|args| split_args(&args))
+            .unwrap_or_default();
+        let root = self.syntax.ancestors().last()?;
+        expand_macro(&self.key(), &args, defs, &root, 0)
+    }
+}
+
+impl Org {
+    /// Collects macro definitions from `#+MACRO` keywords, lowercased name as key
+    ///
+    /// Built-in macros `title`, `author`, `email` and `date` are included if the
+    /// corresponding keyword is present.
+    ///
+    /// ```rust
+    /// use orgize::Org;
+    ///
+    /// let org = Org::parse("#+TITLE: Doc\n#+AUTHOR: Poi\n#+MACRO: Color @@html:<span style=\"color:$1\">$2</span>@@");
+    /// let macros = org.macros();
+    /// assert_eq!(macros["title"], "Doc");
+    /// assert_eq!(macros["author"], "Poi");
+    /// assert_eq!(macros["color"], "@@html:<span style=\"color:$1\">$2</span>@@");
+    /// assert!(!macros.contains_key("email"));
+    /// ```
+    pub fn macros(&self) -> HashMap<String, String> {
+        collect_macros(&self.document().syntax)
+    }
+}
+
+/// Collects macro definitions in given document
+pub(crate) fn collect_macros(root: &SyntaxNode) -> HashMap<String, String> {
+    let mut macros = HashMap::new();
+
+    if let Some(document) = Document::cast(root.clone()) {
+        for keyword in document.keywords() {
+            let key = keyword.key().to_ascii_lowercase();
+            if ["title", "author", "email", "date"].contains(&key.as_str()) {
+                let value = keyword.value_trimmed();
+                macros
+                    .entry(key)
+                    .and_modify(|v: &mut String| {
+                        v.push(' ');
+                        v.push_str(&value);
+                    })
+                    .or_insert(value);
+            }
+        }
+    }
+
+    for keyword in root.descendants().filter_map(Keyword::cast) {
+        if !keyword.key().eq_ignore_ascii_case("MACRO") {
+            continue;
+        }
+        let value = keyword.value_trimmed();
+        let value = value.as_str();
+        let (name, template) = value
+            .split_once([' ', '\t'])
+            .map_or((value, ""), |(name, template)| (name, template.trim()));
+        if !name.is_empty() {
+            macros.insert(name.to_ascii_lowercase(), template.to_string());
+        }
+    }
+
+    macros
+}
+
+fn expand_macro(
+    key: &str,
+    args: &[String],
+    defs: &HashMap<String, String>,
+    root: &SyntaxNode,
+    depth: usize,
+) -> Option<String> {
+    if depth > MAX_EXPANSION_DEPTH {
+        return None;
+    }
+
+    let key = key.to_ascii_lowercase();
+
+    if key == "keyword" && !defs.contains_key(&key) {
+        let name = args.first()?;
+        let document = Document::cast(root.clone())?;
+        let values: Vec<_> = document
+            .keywords()
+            .filter(|kw| kw.key().eq_ignore_ascii_case(name))
+            .map(|kw| kw.value_trimmed())
+            .collect();
+        return Some(values.join(" "));
+    }
+
+    let template = defs.get(&key)?;
+
+    let mut expanded = String::with_capacity(template.len());
+    let mut chars = template.char_indices().peekable();
+    while let Some((i, c)) = chars.next() {
+        let digits = template[i + 1..]
+            .bytes()
+            .take_while(u8::is_ascii_digit)
+            .count();
+        if c == '$' && digits > 0 {
+            let n: usize = template[i + 1..i + 1 + digits].parse().unwrap_or(0);
+            if let Some(arg) = n.checked_sub(1).and_then(|n| args.get(n)) {
+                expanded.push_str(arg);
+            }
+            for _ in 0..digits {
+                chars.next();
+            }
+        } else {
+            expanded.push(c);
+        }
+    }
+
+    expand_text(&expanded, defs, root, depth + 1)
+}
+
+/// Expands all macros in given text
+fn expand_text(
+    text: &str,
+    defs: &HashMap<String, String>,
+    root: &SyntaxNode,
+    depth: usize,
+) -> Option<String> {
+    let mut output = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(start) = rest.find("{{{") {
+        let Some(len) = rest[start + 3..].find("}}}") else {
+            break;
+        };
+        output.push_str(&rest[..start]);
+
+        let call = &rest[start + 3..start + 3 + len];
+        let (key, args) = match call.split_once('(') {
+            Some((key, args)) => (key, split_args(args.strip_suffix(')').unwrap_or(args))),
+            None => (call, vec![]),
+        };
+        output.push_str(&expand_macro(key.trim(), &args, defs, root, depth)?);
+
+        rest = &rest[start + 3 + len + 3..];
+    }
+
+    output.push_str(rest);
+    Some(output)
+}
+
+/// Splits macro arguments by commas, `\,` is an escaped comma
+fn split_args(args: &str) -> Vec<String> {
+    let mut result = vec![String::new()];
+    let mut chars = args.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' if chars.peek() == Some(&',') => {
+                result.last_mut().unwrap().push(',');
+                chars.next();
+            }
+            ',' => result.push(String::new()),
+            c => result.last_mut().unwrap().push(c),
+        }
+    }
+    result.iter().map(|arg| arg.trim().to_string()).collect()
 }
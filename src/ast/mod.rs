@@ -2,10 +2,12 @@ mod generated;
 
 mod affiliated_keyword;
 mod block;
+mod citation;
 mod clock;
 #[cfg(feature = "syntax-org-fc")]
 mod cloze;
 mod comment;
+mod cookie;
 mod document;
 mod drawer;
 mod entity;
@@ -13,20 +15,33 @@ mod fixed_width;
 mod headline;
 mod inline_call;
 mod inline_src;
+mod inline_task;
 mod keyword;
 mod link;
 mod list;
 mod macros;
 mod planning;
+mod radio_target;
 mod snippet;
 mod table;
 mod timestamp;
 
+pub(crate) use block::split_coderef;
+pub use block::LineNumbers;
 #[cfg(feature = "syntax-org-fc")]
 pub use cloze::*;
+pub use document::{DocumentMetadata, OptionValue};
+pub(crate) use drawer::accumulate_values;
+pub use entity::EntityRepresentation;
 pub use generated::*;
 pub use headline::*;
+pub use link::*;
+pub(crate) use list::checkbox_statistics;
+pub use list::{Checkbox, CounterDelimiter, ListBullet};
+pub(crate) use macros::collect_macros;
+pub(crate) use radio_target::{find_radio_target, radio_targets};
 pub use rowan::ast::support::*;
+pub use table::TableAlignment;
 pub use timestamp::*;
 
 use crate::{
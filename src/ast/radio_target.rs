@@ -0,0 +1,108 @@
+use rowan::{ast::AstNode, TextRange};
+
+use crate::{Org, SyntaxKind, SyntaxNode};
+
+use super::RadioTarget;
+
+impl RadioTarget {
+    /// Returns text inside `<<<` and `>>>`
+    ///
+    /// ```rust
+    /// use orgize::{Org, ast::RadioTarget};
+    ///
+    /// let target = Org::parse("<<<My Target>>>").first_node::<RadioTarget>().unwrap();
+    /// assert_eq!(target.text_raw(), "My Target");
+    /// ```
+    pub fn text_raw(&self) -> String {
+        self.syntax
+            .children_with_tokens()
+            .filter(|e| !matches!(e.kind(), SyntaxKind::L_ANGLE3 | SyntaxKind::R_ANGLE3))
+            .map(|e| e.to_string())
+            .collect()
+    }
+}
+
+impl Org {
+    /// Returns text and range of all radio targets in this document
+    ///
+    /// Plain text matching a radio target, case-insensitively, is linked to it
+    /// during HTML export.
+    ///
+    /// ```rust
+    /// use orgize::{Org, rowan::TextRange};
+    ///
+    /// let org = Org::parse("<<<My Target>>> and <<<other>>>");
+    /// assert_eq!(
+    ///     org.radio_targets(),
+    ///     vec![
+    ///         ("My Target".to_string(), TextRange::new(0.into(), 15.into())),
+    ///         ("other".to_string(), TextRange::new(20.into(), 31.into())),
+    ///     ]
+    /// );
+    /// ```
+    pub fn radio_targets(&self) -> Vec<(String, TextRange)> {
+        radio_targets(&self.document().syntax)
+    }
+}
+
+pub(crate) fn radio_targets(root: &SyntaxNode) -> Vec<(String, TextRange)> {
+    root.descendants()
+        .filter_map(RadioTarget::cast)
+        .map(|target| (target.text_raw(), target.syntax.text_range()))
+        .filter(|(text, _)| !text.trim().is_empty())
+        .collect()
+}
+
+/// Finds the first occurrence of any radio target in given text
+///
+/// Returns byte range of the occurrence and index of matched target. Matching
+/// is case-insensitive, any whitespace matches any whitespace, and occurrences
+/// must not be surrounded by alphanumeric characters. Longer targets win if
+/// several of them start at the same position.
+pub(crate) fn find_radio_target(
+    text: &str,
+    targets: &[String],
+) -> Option<(std::ops::Range<usize>, usize)> {
+    let mut prev: Option<char> = None;
+    for (start, c) in text.char_indices() {
+        if prev.is_none_or(|p| !p.is_alphanumeric()) {
+            let longest = targets
+                .iter()
+                .enumerate()
+                .filter_map(|(idx, target)| Some((match_len(&text[start..], target)?, idx)))
+                .filter(|(len, _)| {
+                    text[start + len..]
+                        .chars()
+                        .next()
+                        .is_none_or(|c| !c.is_alphanumeric())
+                })
+                .max_by_key(|(len, _)| *len);
+            if let Some((len, idx)) = longest {
+                return Some((start..start + len, idx));
+            }
+        }
+        prev = Some(c);
+    }
+    None
+}
+
+/// Returns length of text prefix matching given target
+fn match_len(text: &str, target: &str) -> Option<usize> {
+    let mut text_chars = text.char_indices().peekable();
+    let mut target_chars = target.trim().chars().peekable();
+
+    while let Some(t) = target_chars.next() {
+        let (_, c) = text_chars.next()?;
+        if t.is_whitespace() {
+            if !c.is_whitespace() {
+                return None;
+            }
+            while target_chars.next_if(|c| c.is_whitespace()).is_some() {}
+            while text_chars.next_if(|(_, c)| c.is_whitespace()).is_some() {}
+        } else if !c.to_lowercase().eq(t.to_lowercase()) {
+            return None;
+        }
+    }
+
+    Some(text_chars.peek().map_or(text.len(), |(i, _)| *i))
+}
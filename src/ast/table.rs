@@ -1,7 +1,16 @@
 use rowan::ast::AstNode;
 
-use super::{filter_token, OrgTable, OrgTableRow, Token};
-use crate::syntax::SyntaxKind;
+use super::{filter_token, OrgTable, OrgTableCell, OrgTableRow, Token};
+use crate::{syntax::SyntaxKind, Org};
+
+/// Alignment of table column, specified by `<l>`, `<c>` or `<r>` cookie
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub enum TableAlignment {
+    #[default]
+    Left,
+    Center,
+    Right,
+}
 
 impl OrgTable {
     /// Returns `true` if this table has a header
@@ -49,6 +58,108 @@ impl OrgTable {
             .any(|row| !row.is_rule())
     }
 
+    /// Returns alignment of each column, computed from alignment cookies
+    ///
+    /// Columns without cookie are aligned to left.
+    ///
+    /// ```rust
+    /// use orgize::{Org, ast::{OrgTable, TableAlignment}};
+    ///
+    /// let org = Org::parse(r#"
+    /// | <r> | <c10> | <l> |
+    /// | a   | b     | c   | d |"#);
+    /// let table = org.first_node::<OrgTable>().unwrap();
+    /// assert_eq!(
+    ///     table.column_alignments(),
+    ///     vec![TableAlignment::Right, TableAlignment::Center, TableAlignment::Left, TableAlignment::Left]
+    /// );
+    ///
+    /// let table = Org::parse("| a | <r> |").first_node::<OrgTable>().unwrap();
+    /// assert_eq!(table.column_alignments(), vec![TableAlignment::Left, TableAlignment::Left]);
+    /// ```
+    pub fn column_alignments(&self) -> Vec<TableAlignment> {
+        self.column_cookies()
+            .into_iter()
+            .map(Option::unwrap_or_default)
+            .collect()
+    }
+
+    /// Returns alignment cookie of each column, `None` if column has no cookie
+    pub(crate) fn column_cookies(&self) -> Vec<Option<TableAlignment>> {
+        let rows: Vec<_> = self
+            .syntax
+            .children()
+            .filter_map(OrgTableRow::cast)
+            .filter(|row| row.is_standard())
+            .collect();
+
+        let columns = rows
+            .iter()
+            .flat_map(|row| row.syntax.children().filter_map(OrgTableCell::cast))
+            .map(|cell| cell.column() + 1)
+            .max()
+            .unwrap_or_default();
+
+        let mut alignments: Vec<Option<TableAlignment>> = vec![None; columns];
+
+        for row in rows.iter().filter(|row| row.is_alignment()) {
+            for cell in row.syntax.children().filter_map(OrgTableCell::cast) {
+                let alignment = match cell.syntax.to_string().trim().as_bytes().get(1) {
+                    Some(b'l') => TableAlignment::Left,
+                    Some(b'c') => TableAlignment::Center,
+                    Some(b'r') => TableAlignment::Right,
+                    _ => continue,
+                };
+                alignments[cell.column()].get_or_insert(alignment);
+            }
+        }
+
+        alignments
+    }
+
+    /// Returns `true` if at least half of the non-empty body cells in given
+    /// column are numbers, see [`OrgTableCell::is_number`]
+    ///
+    /// Org right-aligns such columns when they have no alignment cookie.
+    /// Header rows and alignment rows are not sampled.
+    ///
+    /// ```rust
+    /// use orgize::{Org, ast::OrgTable};
+    ///
+    /// let org = Org::parse(r#"
+    /// | item  | price | note |
+    /// |-------+-------+------|
+    /// | apple | $1.50 | 10%  |
+    /// | pear  | 2     | n/a  |
+    /// | fig   |       | -    |"#);
+    /// let table = org.first_node::<OrgTable>().unwrap();
+    /// assert!(!table.column_is_numeric(0));
+    /// assert!(table.column_is_numeric(1));
+    /// assert!(!table.column_is_numeric(2));
+    /// assert!(!table.column_is_numeric(3));
+    /// ```
+    pub fn column_is_numeric(&self, idx: usize) -> bool {
+        let has_header = self.has_header();
+        let (numbers, total) = self
+            .syntax
+            .children()
+            .filter_map(OrgTableRow::cast)
+            .skip_while(|row| has_header && !row.is_rule())
+            .filter(|row| row.is_standard() && !row.is_alignment())
+            .filter_map(|row| {
+                row.syntax
+                    .children()
+                    .filter_map(OrgTableCell::cast)
+                    .find(|cell| cell.column() == idx)
+            })
+            .filter(|cell| !cell.syntax.to_string().trim().is_empty())
+            .fold((0, 0), |(numbers, total), cell| {
+                (numbers + usize::from(cell.is_number()), total + 1)
+            });
+
+        total > 0 && numbers * 2 >= total
+    }
+
     /// Formulas associated to the table
     ///
     /// ```rust
@@ -79,6 +190,26 @@ impl OrgTable {
             }
         })
     }
+
+    /// Returns values of `#+TBLFM` lines directly following the table, in order
+    ///
+    /// Each token is the raw value of one line, including leading whitespace.
+    /// Formulas aren't evaluated, and a line may contain several formulas
+    /// separated by `::`.
+    ///
+    /// ```rust
+    /// use orgize::{Org, ast::OrgTable};
+    ///
+    /// let org = Org::parse("| 1 | 2 | |\n#+TBLFM: $3=$1+$2::@1$1=0\n#+tblfm: $4=vsum($1..$3)\n\n#+TBLFM: $5=1");
+    /// let table = org.first_node::<OrgTable>().unwrap();
+    /// let formulas = table.formulas();
+    /// assert_eq!(formulas.len(), 2);
+    /// assert_eq!(formulas[0].as_ref().trim(), "$3=$1+$2::@1$1=0");
+    /// assert_eq!(formulas[1].as_ref().trim(), "$4=vsum($1..$3)");
+    /// ```
+    pub fn formulas(&self) -> Vec<Token> {
+        self.tblfm().collect()
+    }
 }
 
 impl OrgTableRow {
@@ -107,4 +238,165 @@ impl OrgTableRow {
     pub fn is_standard(&self) -> bool {
         self.syntax.kind() == SyntaxKind::ORG_TABLE_STANDARD_ROW
     }
+
+    /// Returns `true` if this row only contains alignment or width cookies, e.g. `<l>`, `<r10>`
+    ///
+    /// ```rust
+    /// use orgize::{Org, ast::OrgTableRow};
+    ///
+    /// let org = Org::parse("| <l> | <c10> | | <5> |");
+    /// let row = org.first_node::<OrgTableRow>().unwrap();
+    /// assert!(row.is_alignment());
+    ///
+    /// let org = Org::parse("| <l> | foo |");
+    /// let row = org.first_node::<OrgTableRow>().unwrap();
+    /// assert!(!row.is_alignment());
+    /// ```
+    pub fn is_alignment(&self) -> bool {
+        let mut cells = self
+            .syntax
+            .children()
+            .filter_map(OrgTableCell::cast)
+            .peekable();
+
+        self.is_standard()
+            && cells.peek().is_some()
+            && cells.all(|cell| is_cookie(cell.syntax.to_string().trim()))
+    }
+}
+
+impl OrgTableCell {
+    /// Returns zero-based column index of this cell
+    ///
+    /// ```rust
+    /// use orgize::{Org, ast::OrgTableCell};
+    ///
+    /// let org = Org::parse("| | b |");
+    /// let cell = org.first_node::<OrgTableCell>().unwrap();
+    /// assert_eq!(cell.column(), 1);
+    /// ```
+    pub fn column(&self) -> usize {
+        self.syntax
+            .siblings_with_tokens(rowan::Direction::Prev)
+            .filter(|e| e.kind() == SyntaxKind::PIPE)
+            .count()
+            .saturating_sub(1)
+    }
+
+    /// Returns `true` if content of this cell looks like a number
+    ///
+    /// Follows `org-table-number-regexp`, so units like `1e3`, `12:30` or `3%`
+    /// are accepted, as well as hexadecimal and `16#ff` style numbers.
+    /// Currency symbols before or after the number are also allowed.
+    ///
+    /// ```rust
+    /// use orgize::{Org, ast::OrgTableCell};
+    ///
+    /// let is_number = |s: &str| {
+    ///     Org::parse(format!("| {s} |")).first_node::<OrgTableCell>().unwrap().is_number()
+    /// };
+    /// assert!(is_number("42"));
+    /// assert!(is_number("-3.5e10"));
+    /// assert!(is_number("50%"));
+    /// assert!(is_number("$1.50"));
+    /// assert!(is_number("-€20"));
+    /// assert!(is_number("20 £"));
+    /// assert!(is_number("0xff"));
+    /// assert!(is_number("inf"));
+    /// assert!(!is_number("-"));
+    /// assert!(!is_number("abc"));
+    /// assert!(!is_number("e10"));
+    /// assert!(!is_number("1 2"));
+    /// ```
+    pub fn is_number(&self) -> bool {
+        is_number(self.syntax.to_string().trim())
+    }
+}
+
+const CURRENCY: [char; 5] = ['$', '€', '£', '¥', '₹'];
+
+fn is_number(s: &str) -> bool {
+    let s = s.strip_prefix(['<', '>']).unwrap_or(s);
+
+    if matches!(s, "nan" | "inf" | "-inf" | "+inf" | "uinf") {
+        return true;
+    }
+
+    // currency symbol may appear around the sign or after the number
+    let s = s.strip_prefix(CURRENCY).unwrap_or(s);
+    let s = s.strip_prefix(['-', '+']).unwrap_or(s);
+    let s = match s.strip_prefix(CURRENCY) {
+        Some(rest) => rest,
+        None => s.trim_end_matches(CURRENCY).trim_end(),
+    };
+
+    if let Some(hex) = s.strip_prefix("0x").or_else(|| s.strip_prefix("0X")) {
+        return !hex.is_empty() && hex.chars().all(|c| c.is_ascii_hexdigit() || c == '.');
+    }
+
+    if let Some((radix, digits)) = s.split_once('#') {
+        return !radix.is_empty()
+            && radix.bytes().all(|b| b.is_ascii_digit())
+            && !digits.is_empty()
+            && digits
+                .bytes()
+                .all(|b| b.is_ascii_alphanumeric() || b == b'.');
+    }
+
+    // `[-+^.0-9]*[0-9][-+^.0-9eEdDx()%:]*`
+    let Some(first_digit) = s.find(|c: char| c.is_ascii_digit()) else {
+        return false;
+    };
+    let leading = |c: char| c.is_ascii_digit() || matches!(c, '-' | '+' | '^' | '.');
+    s[..first_digit].chars().all(leading)
+        && s[first_digit..]
+            .chars()
+            .all(|c| leading(c) || matches!(c, 'e' | 'E' | 'd' | 'D' | 'x' | '(' | ')' | '%' | ':'))
+}
+
+/// Matches `<l>`, `<c>`, `<r>`, optionally followed by width, or width only
+fn is_cookie(s: &str) -> bool {
+    let Some(inner) = s.strip_prefix('<').and_then(|s| s.strip_suffix('>')) else {
+        return false;
+    };
+    let width = inner.strip_prefix(['l', 'c', 'r']).unwrap_or(inner);
+    width.bytes().all(|b| b.is_ascii_digit()) && (inner.len() > width.len() || !width.is_empty())
+}
+
+impl Org {
+    /// Returns all tables in this document, each paired with the `#+TBLFM`
+    /// formulas directly following it
+    ///
+    /// Formula lines separated from a table by blank lines or other elements
+    /// aren't associated with it.
+    ///
+    /// ```rust
+    /// use orgize::Org;
+    ///
+    /// let org = Org::parse(r#"
+    /// | 1 | 2 |
+    /// #+TBLFM: $3=$1+$2
+    /// #+TBLFM: @2$1=1
+    ///
+    /// * headline
+    /// | a |
+    ///
+    /// #+TBLFM: $2=$1
+    /// "#);
+    /// let tables = org.tables_with_formulas();
+    /// assert_eq!(tables.len(), 2);
+    /// assert_eq!(tables[0].1, vec![" $3=$1+$2", " @2$1=1"]);
+    /// assert!(tables[1].1.is_empty());
+    /// ```
+    pub fn tables_with_formulas(&self) -> Vec<(OrgTable, Vec<Token>)> {
+        self.document()
+            .syntax
+            .descendants()
+            .filter_map(OrgTable::cast)
+            .map(|table| {
+                let formulas = table.formulas();
+                (table, formulas)
+            })
+            .collect()
+    }
 }
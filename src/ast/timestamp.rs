@@ -1,3 +1,5 @@
+#[cfg(feature = "chrono")]
+use super::Token;
 use super::{filter_token, Timestamp};
 use crate::syntax::SyntaxKind;
 
@@ -23,6 +25,81 @@ pub enum DelayType {
     First,
 }
 
+/// Repeater of timestamp, e.g. `+1w`, `++1m` or `.+1d`
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Repeater {
+    pub ty: RepeaterType,
+    pub value: u32,
+    pub unit: TimeUnit,
+}
+
+/// Warning period of timestamp, e.g. `-2d` or `--1w`
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Delay {
+    pub ty: DelayType,
+    pub value: u32,
+    pub unit: TimeUnit,
+}
+
+#[cfg(feature = "chrono")]
+impl Repeater {
+    /// Adds `times` repeater intervals to given moment, `None` on overflow
+    fn add_to(&self, moment: chrono::NaiveDateTime, times: u32) -> Option<chrono::NaiveDateTime> {
+        let value = self.value.checked_mul(times)?;
+        match self.unit {
+            TimeUnit::Hour => {
+                moment.checked_add_signed(chrono::TimeDelta::try_hours(value.into())?)
+            }
+            TimeUnit::Day => moment.checked_add_days(chrono::Days::new(value.into())),
+            TimeUnit::Week => moment.checked_add_days(chrono::Days::new(u64::from(value) * 7)),
+            TimeUnit::Month => moment.checked_add_months(chrono::Months::new(value)),
+            TimeUnit::Year => {
+                moment.checked_add_months(chrono::Months::new(value.checked_mul(12)?))
+            }
+        }
+    }
+
+    /// Adds the fewest repeater intervals to `start` that land after `after`
+    fn catch_up(
+        &self,
+        start: chrono::NaiveDateTime,
+        after: chrono::NaiveDate,
+    ) -> Option<chrono::NaiveDateTime> {
+        use chrono::Datelike;
+
+        let target = after.succ_opt()?.and_time(chrono::NaiveTime::MIN);
+        if start >= target {
+            return self.add_to(start, 1);
+        }
+
+        let hours = match self.unit {
+            TimeUnit::Hour => 1,
+            TimeUnit::Day => 24,
+            TimeUnit::Week => 24 * 7,
+            TimeUnit::Month | TimeUnit::Year => {
+                // months vary in length, so start from an estimate that can't
+                // overshoot and step from the stored date so month ends don't drift
+                let months = if self.unit == TimeUnit::Month { 1 } else { 12 };
+                let elapsed = (after.year() - start.year()) * 12 + after.month() as i32
+                    - start.month() as i32;
+                let mut times = (elapsed.max(0) as u32 / (self.value.checked_mul(months)?)).max(1);
+                loop {
+                    let next = self.add_to(start, times)?;
+                    if next >= target {
+                        return Some(next);
+                    }
+                    times = times.checked_add(1)?;
+                }
+            }
+        };
+
+        let interval = i64::from(self.value) * hours * 3600;
+        let elapsed = (target - start).num_seconds();
+        let times = (elapsed + interval - 1) / interval;
+        self.add_to(start, u32::try_from(times).ok()?)
+    }
+}
+
 impl Timestamp {
     /// ```rust
     /// use orgize::{Org, ast::Timestamp};
@@ -168,6 +245,36 @@ impl Timestamp {
         self.nth_delay(0).map(|i| i.2)
     }
 
+    /// ```rust
+    /// use orgize::{Org, ast::{Timestamp, Repeater, RepeaterType, TimeUnit}};
+    ///
+    /// let t = Org::parse("<2000-01-01 Sat ++1m -2d>").first_node::<Timestamp>().unwrap();
+    /// assert_eq!(t.repeater(), Some(Repeater { ty: RepeaterType::CatchUp, value: 1, unit: TimeUnit::Month }));
+    /// let t = Org::parse("<2000-01-01 Sat -2d .+3h>").first_node::<Timestamp>().unwrap();
+    /// assert_eq!(t.repeater(), Some(Repeater { ty: RepeaterType::Restart, value: 3, unit: TimeUnit::Hour }));
+    /// let t = Org::parse("<2000-01-01 Sat -2d>").first_node::<Timestamp>().unwrap();
+    /// assert_eq!(t.repeater(), None);
+    /// ```
+    pub fn repeater(&self) -> Option<Repeater> {
+        self.nth_repeater(0)
+            .map(|(ty, value, unit)| Repeater { ty, value, unit })
+    }
+
+    /// ```rust
+    /// use orgize::{Org, ast::{Timestamp, Delay, DelayType, TimeUnit}};
+    ///
+    /// let t = Org::parse("<2000-01-01 Sat ++1m -2d>").first_node::<Timestamp>().unwrap();
+    /// assert_eq!(t.warning(), Some(Delay { ty: DelayType::All, value: 2, unit: TimeUnit::Day }));
+    /// let t = Org::parse("<2000-01-01 Sat --1w +1y>").first_node::<Timestamp>().unwrap();
+    /// assert_eq!(t.warning(), Some(Delay { ty: DelayType::First, value: 1, unit: TimeUnit::Week }));
+    /// let t = Org::parse("<2000-01-01 Sat +1w>").first_node::<Timestamp>().unwrap();
+    /// assert_eq!(t.warning(), None);
+    /// ```
+    pub fn warning(&self) -> Option<Delay> {
+        self.nth_delay(0)
+            .map(|(ty, value, unit)| Delay { ty, value, unit })
+    }
+
     fn nth_repeater(&self, nth: usize) -> Option<(RepeaterType, u32, TimeUnit)> {
         let mut i = nth + 1;
 
@@ -233,6 +340,119 @@ impl Timestamp {
         Some((mark, value, unit))
     }
 
+    /// Returns date of timestamp start
+    ///
+    /// ```rust
+    /// use orgize::{Org, ast::Timestamp};
+    /// use chrono::NaiveDate;
+    ///
+    /// let ts = Org::parse("<2024-01-02 Tue 10:00-11:00>").first_node::<Timestamp>().unwrap();
+    /// assert_eq!(ts.start_date(), NaiveDate::from_ymd_opt(2024, 1, 2));
+    /// let ts = Org::parse("[2024-01-02]").first_node::<Timestamp>().unwrap();
+    /// assert_eq!(ts.start_date(), NaiveDate::from_ymd_opt(2024, 1, 2));
+    /// let ts = Org::parse("<%%(diary-float t 4 2)>").first_node::<Timestamp>().unwrap();
+    /// assert_eq!(ts.start_date(), None);
+    /// ```
+    #[cfg(feature = "chrono")]
+    pub fn start_date(&self) -> Option<chrono::NaiveDate> {
+        chrono::NaiveDate::from_ymd_opt(
+            self.year_start()?.parse().ok()?,
+            self.month_start()?.parse().ok()?,
+            self.day_start()?.parse().ok()?,
+        )
+    }
+
+    /// Returns time of timestamp start, `None` if it doesn't contain a time
+    ///
+    /// ```rust
+    /// use orgize::{Org, ast::Timestamp};
+    /// use chrono::NaiveTime;
+    ///
+    /// let ts = Org::parse("<2024-01-02 Tue 10:00-11:00>").first_node::<Timestamp>().unwrap();
+    /// assert_eq!(ts.start_time(), NaiveTime::from_hms_opt(10, 0, 0));
+    /// let ts = Org::parse("<2024-01-02 Tue>--<2024-01-03 Wed 08:30>").first_node::<Timestamp>().unwrap();
+    /// assert_eq!(ts.start_time(), None);
+    /// let ts = Org::parse("[2024-01-02]").first_node::<Timestamp>().unwrap();
+    /// assert_eq!(ts.start_time(), None);
+    /// ```
+    #[cfg(feature = "chrono")]
+    pub fn start_time(&self) -> Option<chrono::NaiveTime> {
+        let (hour, minute) = self.times().next()?;
+        let second_date = self.second_date_offset();
+        if second_date.is_some_and(|offset| hour.start() > offset) {
+            return None;
+        }
+        chrono::NaiveTime::from_hms_opt(hour.parse().ok()?, minute.parse().ok()?, 0)
+    }
+
+    /// Returns date of timestamp end, same as [`Timestamp::start_date`] if it's not a range
+    ///
+    /// ```rust
+    /// use orgize::{Org, ast::Timestamp};
+    /// use chrono::NaiveDate;
+    ///
+    /// let ts = Org::parse("<2024-01-02 Tue>--<2024-01-05 Fri>").first_node::<Timestamp>().unwrap();
+    /// assert_eq!(ts.end_date(), NaiveDate::from_ymd_opt(2024, 1, 5));
+    /// let ts = Org::parse("<2024-01-02 Tue 10:00-11:00>").first_node::<Timestamp>().unwrap();
+    /// assert_eq!(ts.end_date(), NaiveDate::from_ymd_opt(2024, 1, 2));
+    /// ```
+    #[cfg(feature = "chrono")]
+    pub fn end_date(&self) -> Option<chrono::NaiveDate> {
+        chrono::NaiveDate::from_ymd_opt(
+            self.year_end()?.parse().ok()?,
+            self.month_end()?.parse().ok()?,
+            self.day_end()?.parse().ok()?,
+        )
+    }
+
+    /// Returns time of timestamp end, same as [`Timestamp::start_time`] if it's not a range
+    ///
+    /// ```rust
+    /// use orgize::{Org, ast::Timestamp};
+    /// use chrono::NaiveTime;
+    ///
+    /// let ts = Org::parse("<2024-01-02 Tue 10:00-11:00>").first_node::<Timestamp>().unwrap();
+    /// assert_eq!(ts.end_time(), NaiveTime::from_hms_opt(11, 0, 0));
+    /// let ts = Org::parse("<2024-01-02 Tue 10:00>--<2024-01-03 Wed>").first_node::<Timestamp>().unwrap();
+    /// assert_eq!(ts.end_time(), None);
+    /// let ts = Org::parse("<2024-01-02 Tue>--<2024-01-03 Wed 08:30>").first_node::<Timestamp>().unwrap();
+    /// assert_eq!(ts.end_time(), NaiveTime::from_hms_opt(8, 30, 0));
+    /// let ts = Org::parse("<2024-01-02 10:00>").first_node::<Timestamp>().unwrap();
+    /// assert_eq!(ts.end_time(), NaiveTime::from_hms_opt(10, 0, 0));
+    /// ```
+    #[cfg(feature = "chrono")]
+    pub fn end_time(&self) -> Option<chrono::NaiveTime> {
+        let (hour, minute) = self.times().last()?;
+        let second_date = self.second_date_offset();
+        if second_date.is_some_and(|offset| hour.start() < offset) {
+            return None;
+        }
+        chrono::NaiveTime::from_hms_opt(hour.parse().ok()?, minute.parse().ok()?, 0)
+    }
+
+    /// Returns an iterator of hour and minute pairs
+    #[cfg(feature = "chrono")]
+    fn times(&self) -> impl Iterator<Item = (Token, Token)> {
+        let hours = self
+            .syntax
+            .children_with_tokens()
+            .filter_map(filter_token(SyntaxKind::TIMESTAMP_HOUR));
+        let minutes = self
+            .syntax
+            .children_with_tokens()
+            .filter_map(filter_token(SyntaxKind::TIMESTAMP_MINUTE));
+        hours.zip(minutes)
+    }
+
+    /// Returns offset of the second date in a `<...>--<...>` range
+    pub(crate) fn second_date_offset(&self) -> Option<rowan::TextSize> {
+        self.syntax
+            .children_with_tokens()
+            .filter_map(filter_token(SyntaxKind::TIMESTAMP_YEAR))
+            .nth(1)
+            .map(|year| year.start())
+    }
+
     /// Converts timestamp start to chrono NaiveDateTime
     ///
     /// ```rust
@@ -298,4 +518,176 @@ impl Timestamp {
     pub fn time_delta(&self) -> Option<chrono::TimeDelta> {
         Some(self.end_to_chrono()? - self.start_to_chrono()?)
     }
+
+    /// Compares two timestamps by their start moment
+    ///
+    /// Date-only timestamps start at the beginning of the day, and activeness
+    /// is ignored. Timestamps without a valid start date, e.g. diary
+    /// timestamps, are ordered after all others.
+    ///
+    /// [`Timestamp`] doesn't implement [`Ord`] because its equality compares
+    /// syntax nodes, not moments.
+    ///
+    /// ```rust
+    /// use orgize::{Org, ast::Timestamp};
+    /// use std::cmp::Ordering;
+    ///
+    /// let org = Org::parse(
+    ///     "<2024-01-02 Tue 10:00> [2024-01-02 Tue] <%%(diary-float t 4 2)> [2024-01-01 Mon 23:59]",
+    /// );
+    /// let mut timestamps = org.nodes::<Timestamp>().collect::<Vec<_>>();
+    /// assert_eq!(timestamps[0].cmp_start(&timestamps[1]), Ordering::Greater);
+    ///
+    /// timestamps.sort_by(Timestamp::cmp_start);
+    /// let raw = timestamps.iter().map(|ts| ts.raw()).collect::<Vec<_>>();
+    /// assert_eq!(
+    ///     raw,
+    ///     [
+    ///         "[2024-01-01 Mon 23:59]",
+    ///         "[2024-01-02 Tue]",
+    ///         "<2024-01-02 Tue 10:00>",
+    ///         "<%%(diary-float t 4 2)>",
+    ///     ]
+    /// );
+    /// ```
+    #[cfg(feature = "chrono")]
+    pub fn cmp_start(&self, other: &Timestamp) -> std::cmp::Ordering {
+        match (self.start_moment(), other.start_moment()) {
+            (Some(a), Some(b)) => a.cmp(&b),
+            (Some(_), None) => std::cmp::Ordering::Less,
+            (None, Some(_)) => std::cmp::Ordering::Greater,
+            (None, None) => std::cmp::Ordering::Equal,
+        }
+    }
+
+    /// Returns `true` if timestamp starts before given moment
+    ///
+    /// Date-only timestamps start at the beginning of the day. Returns `false`
+    /// if timestamp doesn't have a valid start date.
+    ///
+    /// ```rust
+    /// use orgize::{Org, ast::Timestamp};
+    /// use chrono::NaiveDateTime;
+    ///
+    /// let now = "2024-01-02T09:00:00".parse::<NaiveDateTime>().unwrap();
+    /// let ts = Org::parse("<2024-01-02 Tue>").first_node::<Timestamp>().unwrap();
+    /// assert!(ts.is_before(now));
+    /// let ts = Org::parse("<2024-01-02 Tue 10:00>").first_node::<Timestamp>().unwrap();
+    /// assert!(!ts.is_before(now));
+    /// let ts = Org::parse("<%%(diary-float t 4 2)>").first_node::<Timestamp>().unwrap();
+    /// assert!(!ts.is_before(now));
+    /// ```
+    #[cfg(feature = "chrono")]
+    pub fn is_before(&self, now: chrono::NaiveDateTime) -> bool {
+        self.start_moment().is_some_and(|start| start < now)
+    }
+
+    /// Returns signed duration from start of this timestamp to start of the other one
+    ///
+    /// Date-only timestamps start at the beginning of the day. Returns `None`
+    /// if either timestamp doesn't have a valid start date.
+    ///
+    /// ```rust
+    /// use orgize::{Org, ast::Timestamp};
+    ///
+    /// let org = Org::parse("* a\nSCHEDULED: <2024-01-02 Tue 10:00> DEADLINE: <2024-01-05 Fri>");
+    /// let mut timestamps = org.nodes::<Timestamp>();
+    /// let (scheduled, deadline) = (timestamps.next().unwrap(), timestamps.next().unwrap());
+    /// assert_eq!(scheduled.duration_to(&deadline).unwrap().num_hours(), 62);
+    /// assert_eq!(deadline.duration_to(&scheduled).unwrap().num_hours(), -62);
+    ///
+    /// let diary = Org::parse("<%%(diary-float t 4 2)>").first_node::<Timestamp>().unwrap();
+    /// assert!(scheduled.duration_to(&diary).is_none());
+    /// ```
+    #[cfg(feature = "chrono")]
+    pub fn duration_to(&self, other: &Timestamp) -> Option<chrono::TimeDelta> {
+        Some(other.start_moment()? - self.start_moment()?)
+    }
+
+    /// Returns duration from start to end of a range timestamp
+    ///
+    /// Unlike [`Timestamp::time_delta`], date-only ends are accepted and
+    /// treated as the beginning of the day. Returns `None` if timestamp isn't
+    /// a range or can't be parsed.
+    ///
+    /// ```rust
+    /// use orgize::{Org, ast::Timestamp};
+    ///
+    /// let ts = Org::parse("<2024-01-02 Tue>--<2024-01-05 Fri>").first_node::<Timestamp>().unwrap();
+    /// assert_eq!(ts.range_duration().unwrap().num_days(), 3);
+    /// let ts = Org::parse("<2024-01-02 Tue 10:00>--<2024-01-03 Wed 12:30>").first_node::<Timestamp>().unwrap();
+    /// assert_eq!(ts.range_duration().unwrap().num_minutes(), 26 * 60 + 30);
+    /// let ts = Org::parse("<2024-01-02 Tue 10:00-11:00>").first_node::<Timestamp>().unwrap();
+    /// assert_eq!(ts.range_duration().unwrap().num_hours(), 1);
+    /// let ts = Org::parse("<2024-01-02 Tue 10:00>").first_node::<Timestamp>().unwrap();
+    /// assert!(ts.range_duration().is_none());
+    /// ```
+    #[cfg(feature = "chrono")]
+    pub fn range_duration(&self) -> Option<chrono::TimeDelta> {
+        if !self.is_range() {
+            return None;
+        }
+        let end = chrono::NaiveDateTime::new(
+            self.end_date()?,
+            self.end_time().unwrap_or(chrono::NaiveTime::MIN),
+        );
+        Some(end - self.start_moment()?)
+    }
+
+    /// Returns the date this timestamp repeats to when completed on `after`
+    ///
+    /// The result is always later than `after`:
+    ///
+    /// - `+N` (cumulate) and `++N` (catch up) step from the stored date by as
+    ///   many intervals as needed to get past `after`, or by one interval if
+    ///   the stored date is already later
+    /// - `.+N` (restart) shifts `after` itself by one interval
+    ///
+    /// Unlike `org-auto-repeat-maybe`, an overdue `+N` timestamp isn't left
+    /// in the past after a single shift.
+    ///
+    /// Returns `None` if timestamp has no repeater, a zero interval, or an
+    /// invalid start date.
+    ///
+    /// ```rust
+    /// use orgize::{Org, ast::Timestamp};
+    /// use chrono::NaiveDate;
+    ///
+    /// let today = NaiveDate::from_ymd_opt(2024, 1, 20).unwrap();
+    /// let next = |s: &str| {
+    ///     let ts = Org::parse(s).first_node::<Timestamp>().unwrap();
+    ///     ts.next_occurrence(today).map(|d| d.to_string())
+    /// };
+    ///
+    /// assert_eq!(next("<2024-01-01 Mon +1w>").unwrap(), "2024-01-22");
+    /// assert_eq!(next("<2024-02-01 Thu +1w>").unwrap(), "2024-02-08");
+    /// assert_eq!(next("<2024-01-01 Mon ++1w>").unwrap(), "2024-01-22");
+    /// assert_eq!(next("<2024-01-01 Mon .+1w>").unwrap(), "2024-01-27");
+    /// assert_eq!(next("<2023-10-31 Tue ++1m>").unwrap(), "2024-01-31");
+    /// assert_eq!(next("<2023-01-20 Fri 23:00 ++12h>").unwrap(), "2024-01-21");
+    /// assert_eq!(next("<2020-02-29 Sat ++1y>").unwrap(), "2024-02-29");
+    /// assert_eq!(next("<2024-01-01 Mon>"), None);
+    /// assert_eq!(next("<2024-01-01 Mon ++0d>"), None);
+    /// ```
+    #[cfg(feature = "chrono")]
+    pub fn next_occurrence(&self, after: chrono::NaiveDate) -> Option<chrono::NaiveDate> {
+        let repeater = self.repeater().filter(|r| r.value > 0)?;
+        let start = self.start_moment()?;
+
+        let next = match repeater.ty {
+            RepeaterType::Cumulate | RepeaterType::CatchUp => repeater.catch_up(start, after)?,
+            RepeaterType::Restart => repeater.add_to(after.and_time(start.time()), 1)?,
+        };
+
+        Some(next.date())
+    }
+
+    /// Returns start date and time, date-only timestamp starts at midnight
+    #[cfg(feature = "chrono")]
+    fn start_moment(&self) -> Option<chrono::NaiveDateTime> {
+        Some(chrono::NaiveDateTime::new(
+            self.start_date()?,
+            self.start_time().unwrap_or(chrono::NaiveTime::MIN),
+        ))
+    }
 }
@@ -0,0 +1,105 @@
+use rowan::{ast::AstNode, TextRange};
+
+use crate::ast::{AffiliatedKeyword, Keyword, SourceBlock};
+use crate::{Org, SyntaxKind, SyntaxNode};
+
+/// Returns `true` if given element is a babel result, aka. introduced by `#+RESULTS:`
+pub(crate) fn is_results(node: &SyntaxNode) -> bool {
+    if let Some(keyword) = Keyword::cast(node.clone()) {
+        return keyword.key().eq_ignore_ascii_case("RESULTS");
+    }
+
+    node.children()
+        .take_while(|n| n.kind() == SyntaxKind::AFFILIATED_KEYWORD)
+        .filter_map(AffiliatedKeyword::cast)
+        .any(|k| k.key().eq_ignore_ascii_case("RESULTS"))
+}
+
+impl SourceBlock {
+    /// Returns the results element of this source block, if it's directly followed by one
+    ///
+    /// ```rust
+    /// use orgize::{Org, ast::SourceBlock};
+    ///
+    /// let org = Org::parse("#+begin_src sh\necho 1\n#+end_src\n\n#+RESULTS:\n: 1\n");
+    /// let block = org.first_node::<SourceBlock>().unwrap();
+    /// assert_eq!(block.results_element().unwrap().to_string(), "#+RESULTS:\n: 1\n");
+    ///
+    /// let org = Org::parse("#+begin_src sh\necho 1\n#+end_src\n\n: 1\n");
+    /// let block = org.first_node::<SourceBlock>().unwrap();
+    /// assert!(block.results_element().is_none());
+    /// ```
+    pub fn results_element(&self) -> Option<SyntaxNode> {
+        self.syntax.next_sibling().filter(is_results)
+    }
+}
+
+impl Org {
+    /// Removes all babel results from this document, leaving source blocks untouched
+    ///
+    /// Results are elements introduced by a `#+RESULTS:` affiliated keyword, or a
+    /// standalone `#+RESULTS:` keyword with empty result.
+    ///
+    /// ```rust
+    /// use orgize::Org;
+    ///
+    /// let mut org = Org::parse(r#"#+begin_src python
+    /// print(1)
+    /// #+end_src
+    ///
+    /// #+RESULTS:
+    /// : 1
+    ///
+    /// * headline
+    /// #+RESULTS: named
+    /// #+begin_example
+    /// 2
+    /// #+end_example
+    /// paragraph
+    /// "#);
+    /// org.strip_results();
+    /// assert_eq!(org.to_org(), r#"#+begin_src python
+    /// print(1)
+    /// #+end_src
+    ///
+    /// * headline
+    /// paragraph
+    /// "#);
+    /// ```
+    pub fn strip_results(&mut self) {
+        let edits: Vec<(TextRange, String)> = self
+            .document()
+            .syntax()
+            .descendants()
+            .filter(is_results)
+            // results nested in another results element are removed along with it
+            .filter(|node| !node.ancestors().skip(1).any(|n| is_results(&n)))
+            .map(|node| (node.text_range(), String::new()))
+            .collect();
+
+        self.replace_ranges(edits);
+    }
+}
+
+#[test]
+fn strip_results() {
+    let strip = |input: &str| {
+        let mut org = Org::parse(input);
+        org.strip_results();
+        org.to_org()
+    };
+
+    assert_eq!(strip(""), "");
+    assert_eq!(strip("#+NAME: a\nparagraph\n"), "#+NAME: a\nparagraph\n");
+    assert_eq!(strip("a\n\n#+RESULTS:\n: 1\n: 2\n\nb\n"), "a\n\nb\n");
+    assert_eq!(strip("a\n\n#+RESULTS:\n\nb\n"), "a\n\nb\n");
+    assert_eq!(strip("a\n\n#+NAME: x\n#+RESULTS:\n| 1 |\n"), "a\n\n");
+    assert_eq!(
+        strip("* a\n#+RESULTS:\n#+begin_src\n#+end_src\n** b\n#+RESULTS: x\n: 1"),
+        "* a\n** b\n"
+    );
+    assert_eq!(
+        strip("#+RESULTS:\n#+begin_quote\n#+RESULTS:\n: 1\n#+end_quote\nafter\n"),
+        "after\n"
+    );
+}
@@ -0,0 +1,243 @@
+use crate::{Org, ParseConfig};
+
+/// Builds org document programmatically
+///
+/// Each method appends an element as canonical org text, and [`OrgBuilder::build`]
+/// parses the result. So the built document always round-trips through
+/// [`Org::to_org`].
+///
+/// ```rust
+/// use orgize::{OrgBuilder, ast::{Headline, SourceBlock}};
+///
+/// let org = OrgBuilder::new()
+///     .headline(1, Some("TODO"), "Tasks", &["work"])
+///     .paragraph("Things to do today.")
+///     .list(["write docs", "fix bugs"])
+///     .headline(2, None, "Code", &[])
+///     .src_block("rust", "fn main() {}")
+///     .build();
+///
+/// assert_eq!(
+///     org.to_org(),
+///     r#"* TODO Tasks :work:
+/// Things to do today.
+///
+/// - write docs
+/// - fix bugs
+/// ** Code
+/// #+BEGIN_SRC rust
+/// fn main() {}
+/// #+END_SRC
+/// "#
+/// );
+///
+/// let hdl = org.first_node::<Headline>().unwrap();
+/// assert_eq!(hdl.todo_keyword().unwrap(), "TODO");
+/// assert_eq!(hdl.tags().next().unwrap(), "work");
+/// assert_eq!(hdl.headlines().count(), 1);
+/// assert_eq!(org.first_node::<SourceBlock>().unwrap().value(), "fn main() {}\n");
+/// ```
+#[derive(Debug, Default)]
+pub struct OrgBuilder {
+    output: String,
+    config: ParseConfig,
+    /// Previous element is a paragraph or list, which must be separated from
+    /// the next one by a blank line
+    needs_blank_line: bool,
+}
+
+impl OrgBuilder {
+    pub fn new() -> Self {
+        OrgBuilder::default()
+    }
+
+    /// Sets config used by [`OrgBuilder::build`]
+    ///
+    /// Todo keywords passed to [`OrgBuilder::headline`] should be included
+    /// in the config, otherwise they're parsed as part of the title.
+    pub fn with_config(mut self, config: ParseConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Appends a headline
+    ///
+    /// `level` less than 1 is treated as 1, and newlines in title are replaced
+    /// with spaces.
+    ///
+    /// ```rust
+    /// use orgize::OrgBuilder;
+    ///
+    /// let org = OrgBuilder::new()
+    ///     .headline(1, None, "Hello\nworld", &[])
+    ///     .headline(3, Some("DONE"), "Done", &["a", "b"])
+    ///     .build();
+    /// assert_eq!(org.to_org(), "* Hello world\n*** DONE Done :a:b:\n");
+    /// ```
+    pub fn headline(
+        mut self,
+        level: usize,
+        keyword: Option<&str>,
+        title: &str,
+        tags: &[&str],
+    ) -> Self {
+        self.needs_blank_line = false;
+        self.output.push_str(&"*".repeat(level.max(1)));
+        self.output.push(' ');
+        if let Some(keyword) = keyword {
+            self.output.push_str(keyword);
+            self.output.push(' ');
+        }
+        self.output.push_str(&single_line(title));
+        if !tags.is_empty() {
+            self.output.push_str(" :");
+            for tag in tags {
+                self.output.push_str(tag);
+                self.output.push(':');
+            }
+        }
+        self.output.push('\n');
+        self
+    }
+
+    /// Appends a paragraph, text is inserted as is
+    ///
+    /// ```rust
+    /// use orgize::OrgBuilder;
+    ///
+    /// let org = OrgBuilder::new().paragraph("a\nb").paragraph("c").build();
+    /// assert_eq!(org.to_org(), "a\nb\n\nc\n");
+    /// ```
+    pub fn paragraph(mut self, text: &str) -> Self {
+        self.start_element();
+        self.output.push_str(text.trim_end_matches(['\n', '\r']));
+        self.output.push('\n');
+        self.needs_blank_line = true;
+        self
+    }
+
+    /// Appends an unordered list, one item for each string
+    ///
+    /// Continuation lines of an item are indented to its content.
+    ///
+    /// ```rust
+    /// use orgize::{OrgBuilder, ast::List};
+    ///
+    /// let org = OrgBuilder::new().list(["a", "b\nc"]).build();
+    /// assert_eq!(org.to_org(), "- a\n- b\n  c\n");
+    /// assert_eq!(org.first_node::<List>().unwrap().items().count(), 2);
+    /// ```
+    pub fn list<I, S>(self, items: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        self.list_items(items, |_| "-".into())
+    }
+
+    /// Appends an ordered list, one item for each string
+    ///
+    /// ```rust
+    /// use orgize::{OrgBuilder, ast::List};
+    ///
+    /// let org = OrgBuilder::new().ordered_list(["a", "b"]).build();
+    /// assert_eq!(org.to_org(), "1. a\n2. b\n");
+    /// assert!(org.first_node::<List>().unwrap().is_ordered());
+    /// ```
+    pub fn ordered_list<I, S>(self, items: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        self.list_items(items, |idx| format!("{}.", idx + 1))
+    }
+
+    /// Appends a source block
+    ///
+    /// Lines starting with `*` or `#+` are escaped with a comma.
+    ///
+    /// ```rust
+    /// use orgize::{OrgBuilder, ast::SourceBlock};
+    ///
+    /// let org = OrgBuilder::new().src_block("org", "* a\n#+END_SRC").build();
+    /// assert_eq!(org.to_org(), "#+BEGIN_SRC org\n,* a\n,#+END_SRC\n#+END_SRC\n");
+    /// assert_eq!(org.first_node::<SourceBlock>().unwrap().value(), "* a\n#+END_SRC\n");
+    /// ```
+    pub fn src_block(mut self, language: &str, code: &str) -> Self {
+        self.start_element();
+        self.output.push_str("#+BEGIN_SRC");
+        if !language.is_empty() {
+            self.output.push(' ');
+            self.output.push_str(language);
+        }
+        self.output.push('\n');
+        self.output.push_str(&escape_code(code));
+        self.output.push_str("#+END_SRC\n");
+        self.needs_blank_line = false;
+        self
+    }
+
+    /// Parses the built text into an [`Org`]
+    pub fn build(self) -> Org {
+        self.config.parse(self.output)
+    }
+
+    fn list_items<I, S>(mut self, items: I, bullet: impl Fn(usize) -> String) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        self.start_element();
+        for (idx, item) in items.into_iter().enumerate() {
+            let bullet = bullet(idx);
+            let indent = " ".repeat(bullet.len() + 1);
+            let mut lines = item.as_ref().lines();
+            self.output.push_str(&bullet);
+            if let Some(first) = lines.next().filter(|l| !l.is_empty()) {
+                self.output.push(' ');
+                self.output.push_str(first);
+            }
+            self.output.push('\n');
+            for line in lines {
+                if !line.is_empty() {
+                    self.output.push_str(&indent);
+                }
+                self.output.push_str(line);
+                self.output.push('\n');
+            }
+        }
+        self.needs_blank_line = true;
+        self
+    }
+
+    fn start_element(&mut self) {
+        if self.needs_blank_line {
+            self.output.push('\n');
+        }
+    }
+}
+
+fn single_line(text: &str) -> String {
+    text.split(['\n', '\r'])
+        .filter(|s| !s.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Escapes lines starting with `*` or `#+` with a comma, so code can be put
+/// inside a block, equivalent to `org-escape-code-in-string`
+pub(crate) fn escape_code(code: &str) -> String {
+    let mut escaped = String::with_capacity(code.len());
+    for line in code.lines() {
+        let content = line.trim_start();
+        if ["*", "#+", ",*", ",#+"]
+            .iter()
+            .any(|p| content.starts_with(p))
+        {
+            escaped.push(',');
+        }
+        escaped.push_str(line);
+        escaped.push('\n');
+    }
+    escaped
+}
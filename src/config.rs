@@ -1,3 +1,7 @@
+use std::collections::HashMap;
+use std::str::Utf8Error;
+
+use crate::entities::EntityInfo;
 use crate::syntax::document::document_node;
 use crate::Org;
 
@@ -22,12 +26,156 @@ impl UseSubSuperscript {
     }
 }
 
+/// Boundary characters of text markup
+///
+/// Equivalent to `org-emphasis-regexp-components`
+#[derive(Clone, Debug)]
+pub struct EmphasisRegexpComponents {
+    /// Characters allowed before the opening marker
+    ///
+    /// `None` allows any character, which enables emphasis inside words.
+    pub pre: Option<String>,
+
+    /// Characters allowed after the closing marker
+    ///
+    /// `None` allows any character, which enables emphasis inside words.
+    pub post: Option<String>,
+
+    /// Characters not allowed right after the opening marker and right before the closing marker
+    pub border: String,
+
+    /// Maximum number of newlines allowed inside the emphasis
+    pub newline: usize,
+}
+
+impl EmphasisRegexpComponents {
+    pub(crate) fn is_pre(&self, c: char) -> bool {
+        self.pre.as_ref().is_none_or(|pre| pre.contains(c))
+    }
+
+    pub(crate) fn is_post(&self, c: char) -> bool {
+        self.post.as_ref().is_none_or(|post| post.contains(c))
+    }
+
+    pub(crate) fn is_border(&self, c: char) -> bool {
+        self.border.contains(c)
+    }
+}
+
+impl Default for EmphasisRegexpComponents {
+    fn default() -> Self {
+        EmphasisRegexpComponents {
+            pre: Some(" \t\r\n-({\\\"".into()),
+            post: Some(" \t\r\n-.,;:!?')}[".into()),
+            border: " \t\r\n\x0C".into(),
+            newline: 1,
+        }
+    }
+}
+
+/// Text markup types to be parsed
+///
+/// Markers of disabled types remain as plain text.
+#[derive(Clone, Debug)]
+pub struct TextMarkup {
+    /// `*bold*`
+    pub bold: bool,
+    /// `/italic/`
+    pub italic: bool,
+    /// `_underline_`
+    pub underline: bool,
+    /// `+strike-through+`
+    pub strike: bool,
+    /// `=verbatim=`
+    pub verbatim: bool,
+    /// `~code~`
+    pub code: bool,
+}
+
+impl TextMarkup {
+    pub(crate) fn is_enabled(&self, marker: u8) -> bool {
+        match marker {
+            b'*' => self.bold,
+            b'/' => self.italic,
+            b'_' => self.underline,
+            b'+' => self.strike,
+            b'=' => self.verbatim,
+            b'~' => self.code,
+            _ => false,
+        }
+    }
+}
+
+impl Default for TextMarkup {
+    fn default() -> Self {
+        TextMarkup {
+            bold: true,
+            italic: true,
+            underline: true,
+            strike: true,
+            verbatim: true,
+            code: true,
+        }
+    }
+}
+
 /// Parse configuration
 #[derive(Clone, Debug)]
 pub struct ParseConfig {
     /// Headline's todo keywords
     pub todo_keywords: (Vec<String>, Vec<String>),
 
+    /// Highest priority character, `[#X]` is only treated as a priority cookie
+    /// when `X` lies between the highest and lowest priorities
+    ///
+    /// Equivalent to `org-priority-highest`
+    pub priority_highest: char,
+
+    /// Lowest priority character
+    ///
+    /// Equivalent to `org-priority-lowest`
+    pub priority_lowest: char,
+
+    /// Priority of headlines without priority cookie
+    ///
+    /// Equivalent to `org-priority-default`
+    pub priority_default: char,
+
+    /// Link abbreviations, mapping abbreviation to its replacement text
+    ///
+    /// In replacement text, `%s` is replaced by the link tag and `%h` by the
+    /// url-encoded link tag. Otherwise the tag is appended.
+    ///
+    /// Equivalent to `org-link-abbrev-alist`
+    pub link_abbreviations: HashMap<String, String>,
+
+    /// User-defined entities, taking precedence over builtin ones
+    ///
+    /// Equivalent to `org-entities-user`
+    ///
+    /// ```rust
+    /// use orgize::{ParseConfig, ast::Entity, entities::EntityInfo};
+    ///
+    /// let config = ParseConfig {
+    ///     user_entities: vec![EntityInfo {
+    ///         name: "check".into(),
+    ///         latex: "\\checkmark".into(),
+    ///         latex_math: true,
+    ///         html: "&check;".into(),
+    ///         ascii: "v".into(),
+    ///         latin1: "v".into(),
+    ///         utf8: "✓".into(),
+    ///     }],
+    ///     ..Default::default()
+    /// };
+    /// let org = config.parse("done \\check");
+    /// let entity = org.first_node::<Entity>().unwrap();
+    /// assert_eq!(entity.info(org.config()).unwrap().utf8, "✓");
+    /// assert_eq!(org.to_html(), "<main><section><p>done &check;</p></section></main>");
+    /// assert_eq!(org.to_plain_text(), "done ✓\n");
+    /// ```
+    pub user_entities: Vec<EntityInfo>,
+
     pub dual_keywords: Vec<String>,
 
     pub parsed_keywords: Vec<String>,
@@ -45,12 +193,144 @@ pub struct ParseConfig {
     ///
     /// Equivalent to [`org-element-affiliated-keywords`](https://git.sr.ht/~bzg/org-mode/tree/6f960f3c6a4dfe137fbd33fef9f7dadfd229600c/item/lisp/org-element.el#L331)
     pub affiliated_keywords: Vec<String>,
+
+    /// Control boundaries of text markup
+    ///
+    /// Equivalent to `org-emphasis-regexp-components`
+    pub emphasis_regexp_components: EmphasisRegexpComponents,
+
+    /// Enable or disable parsing of each text markup type
+    ///
+    /// ```rust
+    /// use orgize::{config::TextMarkup, ParseConfig};
+    ///
+    /// let config = ParseConfig {
+    ///     text_markup: TextMarkup {
+    ///         bold: false,
+    ///         underline: false,
+    ///         ..Default::default()
+    ///     },
+    ///     ..Default::default()
+    /// };
+    /// let org = config.parse("*a* _b_ /c/");
+    /// assert_eq!(org.to_html(), "<main><section><p>*a* _b_ <i>c</i></p></section></main>");
+    /// ```
+    pub text_markup: TextMarkup,
+
+    /// Minimum number of stars of an inline task, `None` disables inline tasks
+    ///
+    /// Equivalent to `org-inlinetask-min-level`
+    ///
+    /// ```rust
+    /// use orgize::{Org, ParseConfig, ast::{Headline, InlineTask}};
+    ///
+    /// let org = Org::parse("* a\n*************** TODO task\ntext");
+    /// assert_eq!(org.document().headlines().count(), 1);
+    /// assert!(org.first_node::<InlineTask>().is_some());
+    ///
+    /// let config = ParseConfig {
+    ///     inline_task_min_level: None,
+    ///     ..Default::default()
+    /// };
+    /// let org = config.parse("* a\n*************** TODO task\ntext");
+    /// assert!(org.first_node::<InlineTask>().is_none());
+    /// ```
+    pub inline_task_min_level: Option<usize>,
+
+    /// Maximum level of a headline, `None` means unlimited
+    ///
+    /// Lines with more stars than this don't start a new headline. They're
+    /// parsed as regular text, so a deeper "headline" becomes a paragraph in
+    /// the section of its closest parent headline. Lines reaching
+    /// [`ParseConfig::inline_task_min_level`] still make inline tasks.
+    ///
+    /// ```rust
+    /// use orgize::{Org, ParseConfig, ast::Headline};
+    ///
+    /// let config = ParseConfig {
+    ///     max_headline_level: Some(2),
+    ///     ..Default::default()
+    /// };
+    /// let org = config.parse("* a\n** b\n*** c\n**** d");
+    /// let b = org.document().headlines().next().unwrap().headlines().next().unwrap();
+    /// assert_eq!(b.headlines().count(), 0);
+    /// assert_eq!(b.section().unwrap().raw(), "*** c\n**** d");
+    /// ```
+    pub max_headline_level: Option<usize>,
+
+    /// Width of a tab character when measuring indentation, defaults to `8`
+    ///
+    /// Only used by the list parser, to decide whether a line continues a
+    /// list item or starts a nested list. A tab advances the column to the
+    /// next multiple of `tab_width`, so mixed indentation like `"  \t"`
+    /// counts as `8` columns rather than `3`, same as `tab-width` in Emacs.
+    ///
+    /// ```rust
+    /// use orgize::{Org, ParseConfig, ast::List};
+    ///
+    /// let org = Org::parse("- a\n\t- b\n        - c");
+    /// let list = org.first_node::<List>().unwrap();
+    /// assert_eq!(list.items().count(), 1);
+    ///
+    /// let config = ParseConfig {
+    ///     tab_width: 2,
+    ///     ..Default::default()
+    /// };
+    /// let org = config.parse("- a\n\t- b\n  - c");
+    /// let list = org.first_node::<List>().unwrap();
+    /// assert_eq!(list.items().count(), 1);
+    /// ```
+    pub tab_width: usize,
+
+    /// Names of drawers to be recognized, `None` accepts any name
+    ///
+    /// `PROPERTIES` and `LOGBOOK` drawers are always recognized. Names are
+    /// compared case-insensitively, and drawers with other names are parsed
+    /// as plain paragraphs.
+    ///
+    /// Similar to `org-drawers` in older Org versions
+    ///
+    /// ```rust
+    /// use orgize::{Org, ParseConfig, ast::Drawer};
+    ///
+    /// let input = ":FOO:\nbar\n:END:\n\n:NOTES:\nbaz\n:END:\n\n:LOGBOOK:\n:END:";
+    /// assert_eq!(Org::parse(input).nodes::<Drawer>().count(), 3);
+    ///
+    /// let config = ParseConfig {
+    ///     drawer_names: Some(vec!["notes".into()]),
+    ///     ..Default::default()
+    /// };
+    /// let org = config.parse(input);
+    /// let names: Vec<_> = org.nodes::<Drawer>().map(|drawer| drawer.name()).collect();
+    /// assert_eq!(names, ["NOTES", "LOGBOOK"]);
+    /// ```
+    pub drawer_names: Option<Vec<String>>,
 }
 
 impl ParseConfig {
     /// Parses input with current config
-    pub fn parse(self, input: impl AsRef<str>) -> Org {
-        let input = (input.as_ref(), &self).into();
+    ///
+    /// Todo keywords declared by `#+TODO`, `#+SEQ_TODO` or `#+TYP_TODO` and link
+    /// abbreviations declared by `#+LINK` in the zeroth section of input are merged
+    /// into [`ParseConfig::todo_keywords`] and [`ParseConfig::link_abbreviations`]
+    /// before parsing.
+    ///
+    /// ```rust
+    /// use orgize::Org;
+    ///
+    /// let org = Org::parse("#+TODO: NEXT | CANCELLED\n* NEXT a\n* CANCELLED b");
+    /// let mut headlines = org.document().headlines();
+    /// assert_eq!(headlines.next().unwrap().todo_keyword().unwrap(), "NEXT");
+    /// assert!(headlines.next().unwrap().is_done());
+    /// assert_eq!(org.config().todo_keywords.0, vec!["TODO", "NEXT"]);
+    ///
+    /// let org = Org::parse("#+LINK: gh https://github.com/%s\n[[gh:PoiScript/orgize]]");
+    /// assert_eq!(org.config().link_abbreviations["gh"], "https://github.com/%s");
+    /// ```
+    pub fn parse(mut self, input: impl AsRef<str>) -> Org {
+        let input = input.as_ref();
+        self.merge_document_settings(input);
+        let input = (input, &self).into();
         let node = document_node(input).unwrap().1;
 
         Org {
@@ -58,12 +338,157 @@ impl ParseConfig {
             green: node.into_node().unwrap(),
         }
     }
+
+    /// Parses UTF-8 bytes with current config, see [`ParseConfig::parse`]
+    ///
+    /// Input is validated once and parsed without copying.
+    ///
+    /// ```rust
+    /// use orgize::ParseConfig;
+    ///
+    /// let org = ParseConfig::default().parse_bytes(b"* hello").unwrap();
+    /// assert_eq!(org.to_org(), "* hello");
+    /// assert!(ParseConfig::default().parse_bytes(b"* \xff").is_err());
+    /// ```
+    pub fn parse_bytes(self, input: &[u8]) -> Result<Org, Utf8Error> {
+        Ok(self.parse(std::str::from_utf8(input)?))
+    }
+
+    /// Parses bytes with current config, replacing invalid UTF-8 sequences with
+    /// `U+FFFD REPLACEMENT CHARACTER`
+    ///
+    /// Input is only copied if it contains invalid sequences.
+    ///
+    /// ```rust
+    /// use orgize::ParseConfig;
+    ///
+    /// let org = ParseConfig::default().parse_bytes_lossy(b"* \xffhello");
+    /// assert_eq!(org.to_org(), "* \u{FFFD}hello");
+    /// ```
+    pub fn parse_bytes_lossy(self, input: &[u8]) -> Org {
+        self.parse(String::from_utf8_lossy(input))
+    }
+
+    /// Creates default config, with todo keywords and link abbreviations declared
+    /// in the zeroth section of input
+    ///
+    /// In `#+TODO: TODO NEXT | DONE CANCELLED`, keywords before `|` are todo states
+    /// and keywords after it are done states. Without `|`, the last keyword is the
+    /// only done state. Multiple declarations accumulate.
+    ///
+    /// `#+LINK: gh https://github.com/%s` declares a link abbreviation, see
+    /// [`ParseConfig::link_abbreviations`].
+    ///
+    /// ```rust
+    /// use orgize::ParseConfig;
+    ///
+    /// let config = ParseConfig::from_document(
+    ///     "#+TITLE: doc\n#+TODO: NEXT(n) WAIT(w@/!) | CANCELLED(c)\n#+seq_todo: A B\n* NEXT headline\n#+TODO: IGNORED",
+    /// );
+    /// assert_eq!(config.todo_keywords.0, vec!["TODO", "NEXT", "WAIT", "A"]);
+    /// assert_eq!(config.todo_keywords.1, vec!["DONE", "CANCELLED", "B"]);
+    ///
+    /// let config = ParseConfig::from_document("#+link: wiki https://en.wikipedia.org/wiki/%h");
+    /// assert_eq!(config.link_abbreviations["wiki"], "https://en.wikipedia.org/wiki/%h");
+    /// ```
+    pub fn from_document(input: impl AsRef<str>) -> Self {
+        let mut config = ParseConfig::default();
+        config.merge_document_settings(input.as_ref());
+        config
+    }
+
+    /// Returns `true` if given character lies in the priority range
+    pub(crate) fn is_priority(&self, c: char) -> bool {
+        let (highest, lowest) = (self.priority_highest, self.priority_lowest);
+        (highest.min(lowest)..=highest.max(lowest)).contains(&c)
+    }
+
+    /// Returns the column reached by leading whitespace of given string,
+    /// expanding tabs according to [`ParseConfig::tab_width`]
+    pub(crate) fn indent_width(&self, s: &str) -> usize {
+        let tab_width = self.tab_width.max(1);
+        s.bytes()
+            .take_while(|b| *b == b' ' || *b == b'\t')
+            .fold(0, |col, b| {
+                if b == b'\t' {
+                    (col / tab_width + 1) * tab_width
+                } else {
+                    col + 1
+                }
+            })
+    }
+
+    /// Returns `true` if drawer of given name should be recognized
+    pub(crate) fn is_drawer_name(&self, name: &str) -> bool {
+        name.eq_ignore_ascii_case("PROPERTIES")
+            || name.eq_ignore_ascii_case("LOGBOOK")
+            || self
+                .drawer_names
+                .as_ref()
+                .is_none_or(|names| names.iter().any(|n| n.eq_ignore_ascii_case(name)))
+    }
+
+    /// Appends todo keywords and link abbreviations declared in the zeroth section of input
+    fn merge_document_settings(&mut self, input: &str) {
+        for line in input.lines() {
+            if line.starts_with('*') && line.trim_start_matches('*').starts_with([' ', '\t']) {
+                break;
+            }
+
+            let Some(rest) = line.trim_start().strip_prefix("#+") else {
+                continue;
+            };
+            let Some((key, value)) = rest.split_once(':') else {
+                continue;
+            };
+            if key.eq_ignore_ascii_case("LINK") {
+                if let Some((abbrev, replacement)) = value.trim().split_once([' ', '\t']) {
+                    self.link_abbreviations
+                        .insert(abbrev.to_string(), replacement.trim().to_string());
+                }
+                continue;
+            }
+
+            if !["TODO", "SEQ_TODO", "TYP_TODO"]
+                .iter()
+                .any(|k| k.eq_ignore_ascii_case(key))
+            {
+                continue;
+            }
+
+            let words: Vec<_> = value.split_whitespace().collect();
+            let (todo, done) = match words.iter().position(|w| *w == "|") {
+                Some(idx) => (&words[..idx], &words[idx + 1..]),
+                None if words.is_empty() => continue,
+                // without separator, the last keyword is the done state
+                None => words.split_at(words.len() - 1),
+            };
+
+            for (keywords, words) in [
+                (&mut self.todo_keywords.0, todo),
+                (&mut self.todo_keywords.1, done),
+            ] {
+                for word in words {
+                    // strip fast access key and logging options, e.g. `WAIT(w@/!)`
+                    let keyword = word.split_once('(').map_or(*word, |(k, _)| k);
+                    if !keyword.is_empty() && !keywords.iter().any(|k| k == keyword) {
+                        keywords.push(keyword.to_string());
+                    }
+                }
+            }
+        }
+    }
 }
 
 impl Default for ParseConfig {
     fn default() -> Self {
         ParseConfig {
             todo_keywords: (vec!["TODO".into()], vec!["DONE".into()]),
+            priority_highest: 'A',
+            priority_lowest: 'C',
+            priority_default: 'B',
+            link_abbreviations: HashMap::new(),
+            user_entities: Vec::new(),
             dual_keywords: vec!["CAPTION".into(), "RESULTS".into()],
             parsed_keywords: vec!["CAPTION".into()],
             use_sub_superscript: UseSubSuperscript::True,
@@ -82,6 +507,12 @@ impl Default for ParseConfig {
                 "SRCNAME".into(),
                 "TBLNAME".into(),
             ],
+            emphasis_regexp_components: EmphasisRegexpComponents::default(),
+            text_markup: TextMarkup::default(),
+            inline_task_min_level: Some(15),
+            max_headline_level: None,
+            tab_width: 8,
+            drawer_names: None,
         }
     }
 }
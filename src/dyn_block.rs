@@ -0,0 +1,45 @@
+use rowan::TextRange;
+
+use crate::ast::DynBlock;
+use crate::Org;
+
+impl Org {
+    /// Regenerates content of given dynamic block, equivalent to
+    /// `org-update-dblock`
+    ///
+    /// The callback receives name and parameters (empty if not specified) of
+    /// the block and returns its new content, which replaces everything between
+    /// `#+BEGIN:` and `#+END:` lines. A trailing newline is added if missing.
+    ///
+    /// ```rust
+    /// use orgize::{Org, ast::DynBlock};
+    ///
+    /// let mut org = Org::parse("* a\n#+BEGIN: clocktable :scope file\nold\n#+END:\ntext");
+    /// let block = org.first_node::<DynBlock>().unwrap();
+    /// org.rebuild_dynblock(&block, |name, parameters| {
+    ///     format!("| {name} | {parameters} |")
+    /// });
+    /// assert_eq!(
+    ///     org.to_org(),
+    ///     "* a\n#+BEGIN: clocktable :scope file\n| clocktable | :scope file |\n#+END:\ntext"
+    /// );
+    ///
+    /// let block = org.first_node::<DynBlock>().unwrap();
+    /// org.rebuild_dynblock(&block, |_, _| String::new());
+    /// assert_eq!(org.to_org(), "* a\n#+BEGIN: clocktable :scope file\n#+END:\ntext");
+    /// ```
+    pub fn rebuild_dynblock(&mut self, block: &DynBlock, f: impl Fn(&str, &str) -> String) {
+        let parameters = block.parameters();
+        let mut content = f(
+            &block.block_name(),
+            parameters.as_deref().unwrap_or_default(),
+        );
+        if !content.is_empty() && !content.ends_with(['\n', '\r']) {
+            content.push('\n');
+        }
+        self.replace_range(
+            TextRange::new(block.content_start(), block.content_end()),
+            content,
+        );
+    }
+}
@@ -1,9 +1,76 @@
+//! Entity table, equivalent to `org-entities`
+
+use crate::ast::EntityRepresentation;
+
+/// Representations of an entity, see [`lookup`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EntityInfo {
+    /// Entity name, without leading backslash
+    pub name: String,
+    /// LaTeX representation
+    pub latex: String,
+    /// `true` if LaTeX representation must be used in math mode
+    pub latex_math: bool,
+    /// HTML representation
+    pub html: String,
+    /// ASCII representation
+    pub ascii: String,
+    /// Latin1 representation
+    pub latin1: String,
+    /// UTF-8 representation
+    pub utf8: String,
+}
+
+impl EntityInfo {
+    /// Returns representation for given export target
+    ///
+    /// ```rust
+    /// use orgize::{ast::EntityRepresentation, entities::lookup};
+    ///
+    /// let alpha = lookup("alpha").unwrap();
+    /// assert_eq!(alpha.representation(EntityRepresentation::Latex), "\\alpha");
+    /// assert_eq!(alpha.representation(EntityRepresentation::Utf8), "α");
+    /// ```
+    pub fn representation(&self, repr: EntityRepresentation) -> &str {
+        match repr {
+            EntityRepresentation::Latex => &self.latex,
+            EntityRepresentation::Html => &self.html,
+            EntityRepresentation::Ascii => &self.ascii,
+            EntityRepresentation::Latin1 => &self.latin1,
+            EntityRepresentation::Utf8 => &self.utf8,
+        }
+    }
+}
+
+/// Looks up a builtin entity by its name
+///
+/// ```rust
+/// use orgize::entities::lookup;
+///
+/// let middot = lookup("middot").unwrap();
+/// assert_eq!(middot.utf8, "·");
+/// assert_eq!(middot.latex, "\\textperiodcentered{}");
+/// assert_eq!(middot.html, "&middot;");
+/// assert!(lookup("unknown").is_none());
+/// ```
+pub fn lookup(name: &str) -> Option<EntityInfo> {
+    ENTITIES.iter().find(|e| e.0 == name).map(|e| EntityInfo {
+        name: e.0.to_string(),
+        latex: e.1.to_string(),
+        latex_math: e.2,
+        html: e.3.to_string(),
+        ascii: e.4.to_string(),
+        latin1: e.5.to_string(),
+        utf8: e.6.to_string(),
+    })
+}
+
 // https://git.sr.ht/~bzg/org-mode/tree/bfa4f9d5aa3e5c94974cae7a459cb5e5b4b15f52/item/lisp/org-entities.el#L85
 // nil -> false
 // t -> true
 // \x00A0 -> \\x00A0
 #[rustfmt::skip]
-pub const ENTITIES: &[(&str, &str, bool, &str, &str, &str, &str)] = &[
+pub(crate) const ENTITIES: &[(&str, &str, bool, &str, &str, &str, &str)] = &[
 // ("* Letters"
 // Latin
 ("Agrave", "\\`{A}", false, "&Agrave;", "A", "À", "À"),
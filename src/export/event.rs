@@ -1,4 +1,7 @@
+use rowan::TextRange;
+
 use crate::ast::*;
+use crate::{SyntaxKind, SyntaxNode};
 
 #[non_exhaustive]
 #[derive(Clone, PartialEq, Eq, Debug)]
@@ -7,6 +10,7 @@ pub enum Container {
     Section(Section),
     Paragraph(Paragraph),
     Headline(Headline),
+    InlineTask(InlineTask),
 
     OrgTable(OrgTable),
     OrgTableRow(OrgTableRow),
@@ -69,7 +73,140 @@ pub enum Event {
     LatexFragment(LatexFragment),
     LatexEnvironment(LatexEnvironment),
     Entity(Entity),
+    Citation(Citation),
 
     #[cfg(feature = "syntax-org-fc")]
     Cloze(Cloze),
 }
+
+impl Container {
+    /// Returns the syntax node of this container
+    pub fn syntax(&self) -> &SyntaxNode {
+        match self {
+            Container::Document(n) => &n.syntax,
+            Container::Section(n) => &n.syntax,
+            Container::Paragraph(n) => &n.syntax,
+            Container::Headline(n) => &n.syntax,
+            Container::InlineTask(n) => &n.syntax,
+            Container::OrgTable(n) => &n.syntax,
+            Container::OrgTableRow(n) => &n.syntax,
+            Container::OrgTableCell(n) => &n.syntax,
+            Container::TableEl(n) => &n.syntax,
+            Container::List(n) => &n.syntax,
+            Container::ListItem(n) => &n.syntax,
+            Container::Drawer(n) => &n.syntax,
+            Container::DynBlock(n) => &n.syntax,
+            Container::FnDef(n) => &n.syntax,
+            Container::FnContent(n) => &n.syntax,
+            Container::Comment(n) => &n.syntax,
+            Container::FixedWidth(n) => &n.syntax,
+            Container::SpecialBlock(n) => &n.syntax,
+            Container::QuoteBlock(n) => &n.syntax,
+            Container::CenterBlock(n) => &n.syntax,
+            Container::VerseBlock(n) => &n.syntax,
+            Container::CommentBlock(n) => &n.syntax,
+            Container::ExampleBlock(n) => &n.syntax,
+            Container::ExportBlock(n) => &n.syntax,
+            Container::SourceBlock(n) => &n.syntax,
+            Container::Link(n) => &n.syntax,
+            Container::RadioTarget(n) => &n.syntax,
+            Container::FnRef(n) => &n.syntax,
+            Container::Target(n) => &n.syntax,
+            Container::Bold(n) => &n.syntax,
+            Container::Strike(n) => &n.syntax,
+            Container::Italic(n) => &n.syntax,
+            Container::Underline(n) => &n.syntax,
+            Container::Verbatim(n) => &n.syntax,
+            Container::Code(n) => &n.syntax,
+            Container::Superscript(n) => &n.syntax,
+            Container::Subscript(n) => &n.syntax,
+            Container::BabelCall(n) => &n.syntax,
+            Container::PropertyDrawer(n) => &n.syntax,
+            Container::AffiliatedKeyword(n) => &n.syntax,
+            Container::Keyword(n) => &n.syntax,
+        }
+    }
+}
+
+impl Event {
+    /// Returns the syntax kind of node or token in this event
+    ///
+    /// ```rust
+    /// use orgize::{export::{from_fn, Event}, Org, SyntaxKind};
+    ///
+    /// let mut kinds = vec![];
+    /// Org::parse("*a* <2024-01-01>").traverse(&mut from_fn(|event| {
+    ///     if !matches!(event, Event::Leave(_)) {
+    ///         kinds.push(event.kind());
+    ///     }
+    /// }));
+    /// assert_eq!(
+    ///     kinds,
+    ///     vec![
+    ///         SyntaxKind::DOCUMENT,
+    ///         SyntaxKind::SECTION,
+    ///         SyntaxKind::PARAGRAPH,
+    ///         SyntaxKind::BOLD,
+    ///         SyntaxKind::TEXT,
+    ///         SyntaxKind::TEXT,
+    ///         SyntaxKind::TIMESTAMP_ACTIVE,
+    ///     ]
+    /// );
+    /// ```
+    pub fn kind(&self) -> SyntaxKind {
+        match self {
+            Event::Enter(c) | Event::Leave(c) => c.syntax().kind(),
+            Event::Text(t) | Event::FnLabel(t) => t.syntax().kind(),
+            Event::Macros(n) => n.syntax.kind(),
+            Event::Cookie(n) => n.syntax.kind(),
+            Event::InlineCall(n) => n.syntax.kind(),
+            Event::InlineSrc(n) => n.syntax.kind(),
+            Event::Clock(n) => n.syntax.kind(),
+            Event::LineBreak(n) => n.syntax.kind(),
+            Event::Snippet(n) => n.syntax.kind(),
+            Event::Rule(n) => n.syntax.kind(),
+            Event::Timestamp(n) => n.syntax.kind(),
+            Event::LatexFragment(n) => n.syntax.kind(),
+            Event::LatexEnvironment(n) => n.syntax.kind(),
+            Event::Entity(n) => n.syntax.kind(),
+            Event::Citation(n) => n.syntax.kind(),
+            #[cfg(feature = "syntax-org-fc")]
+            Event::Cloze(n) => n.syntax.kind(),
+        }
+    }
+
+    /// Returns the text range of node or token in this event
+    ///
+    /// ```rust
+    /// use orgize::{export::{from_fn, Event}, Org, TextRange};
+    ///
+    /// let mut ranges = vec![];
+    /// Org::parse("*a* b").traverse(&mut from_fn(|event| {
+    ///     if let Event::Text(_) = event {
+    ///         ranges.push(event.text_range());
+    ///     }
+    /// }));
+    /// assert_eq!(ranges, vec![TextRange::new(1.into(), 2.into()), TextRange::new(3.into(), 5.into())]);
+    /// ```
+    pub fn text_range(&self) -> TextRange {
+        match self {
+            Event::Enter(c) | Event::Leave(c) => c.syntax().text_range(),
+            Event::Text(t) | Event::FnLabel(t) => t.syntax().text_range(),
+            Event::Macros(n) => n.syntax.text_range(),
+            Event::Cookie(n) => n.syntax.text_range(),
+            Event::InlineCall(n) => n.syntax.text_range(),
+            Event::InlineSrc(n) => n.syntax.text_range(),
+            Event::Clock(n) => n.syntax.text_range(),
+            Event::LineBreak(n) => n.syntax.text_range(),
+            Event::Snippet(n) => n.syntax.text_range(),
+            Event::Rule(n) => n.syntax.text_range(),
+            Event::Timestamp(n) => n.syntax.text_range(),
+            Event::LatexFragment(n) => n.syntax.text_range(),
+            Event::LatexEnvironment(n) => n.syntax.text_range(),
+            Event::Entity(n) => n.syntax.text_range(),
+            Event::Citation(n) => n.syntax.text_range(),
+            #[cfg(feature = "syntax-org-fc")]
+            Event::Cloze(n) => n.syntax.text_range(),
+        }
+    }
+}
@@ -1,14 +1,24 @@
 use rowan::ast::AstNode;
-use rowan::NodeOrToken;
+use rowan::{NodeOrToken, TextRange, TextSize};
+use std::borrow::Cow;
 use std::cmp::min;
+use std::collections::{HashMap, HashSet};
 use std::fmt;
 use std::fmt::Write as _;
+use std::ops::Range;
 
 use super::event::{Container, Event};
+use super::ExportTags;
 use super::TraversalContext;
 use super::Traverser;
-use crate::ast::token;
-use crate::{SyntaxElement, SyntaxKind, SyntaxNode};
+use crate::ast::{
+    collect_macros, expand_link_abbreviation, find_radio_target, headline_anchors,
+    headline_numbers, radio_targets, slugify, split_coderef, AffiliatedKeyword, Checkbox,
+    EntityRepresentation, FnDef, Headline, InternalLinkKind, LatexFragment, LineNumbers, Link,
+    LinkTargets, Paragraph, Planning, SourceBlock, TableAlignment, Timestamp, Token,
+};
+use crate::entities::EntityInfo;
+use crate::{ParseConfig, SyntaxElement, SyntaxKind, SyntaxNode, SyntaxToken};
 
 /// A wrapper for escaping sensitive characters in html.
 ///
@@ -49,16 +59,143 @@ impl<S: AsRef<str>> fmt::Display for HtmlEscape<S> {
     }
 }
 
-#[derive(Default)]
-pub struct HtmlExport {
-    output: String,
+/// Html exporter, writes rendered html into a [`fmt::Write`] sink, defaults to [`String`]
+///
+/// Subtrees of commented headlines, see [`Headline::is_commented`], are omitted.
+///
+/// `#+ATTR_HTML` attributes are written onto images, tables and blocks, and
+/// attributes whose names aren't valid html attribute names are dropped. Event
+/// handler attributes like `onclick` are kept, just as html export blocks and
+/// snippets are passed through, so output of untrusted documents still needs
+/// sanitizing.
+///
+/// ```rust
+/// use orgize::Org;
+///
+/// let org = Org::parse("#+ATTR_HTML: :data-x 1 :a\"b 2 :onclick go()\n| a |");
+/// assert_eq!(
+///     org.to_html(),
+///     "<main><section><table data-x=\"1\" onclick=\"go()\"><tbody><tr><td style=\"text-align: left\">a</td></tr></tbody></table></section></main>"
+/// );
+/// ```
+pub struct HtmlExport<W: fmt::Write = String> {
+    output: W,
+
+    /// referenced footnotes, in order of first reference
+    footnotes: Vec<Footnote>,
+
+    /// number of footnotes whose definitions are already written
+    footnotes_written: usize,
 
-    ///TODO: track footnotes and citations within the export struct and
-    /// construct them after the document is fully parsed?
-    //footnotes: HashMap<String, String>,
     in_descriptive_list: Vec<bool>,
 
     table_row: TableRow,
+
+    /// alignments of columns in current table
+    table_alignments: Vec<TableAlignment>,
+
+    /// number of cells written in current table row
+    table_column: usize,
+
+    entity_representation: EntityRepresentation,
+
+    math_delimiters: MathDelimiters,
+
+    /// results already rendered inside a source block figure
+    figure_results: Option<TextRange>,
+
+    heading_self_links: bool,
+
+    /// anchor ids of headlines, computed once per document
+    anchors: HashMap<SyntaxNode, String>,
+
+    /// elements internal links can point to, computed once per document
+    link_targets: HashMap<SyntaxNode, LinkTargets>,
+
+    /// macro definitions of each document, computed once per document
+    macros: HashMap<SyntaxNode, HashMap<String, String>>,
+
+    /// radio targets of each document, computed once per document
+    radio_targets: HashMap<SyntaxNode, Vec<String>>,
+
+    /// prefix prepended to every emitted class name
+    class_prefix: String,
+
+    heading_offset: usize,
+
+    /// number of last line in numbered source blocks, for `+n` switch
+    line_number: usize,
+
+    highlighter: Option<Highlighter>,
+
+    /// link abbreviations used to expand link paths
+    link_abbreviations: HashMap<String, String>,
+
+    /// user-defined entities, looked up before built-in entities
+    user_entities: Vec<EntityInfo>,
+
+    /// config used to parse org text generated during export, e.g. macro expansions,
+    /// captions and org export blocks
+    parse_config: ParseConfig,
+
+    /// rendering overrides run before entering nodes of given kind
+    overrides: HashMap<SyntaxKind, Box<OverrideFn<W>>>,
+
+    /// tags of subtrees to be omitted, `None` exports everything
+    export_tags: Option<ExportTags>,
+
+    /// headlines omitted by export tags, computed once per document
+    excluded: HashMap<SyntaxNode, HashSet<SyntaxNode>>,
+
+    /// renders newlines inside paragraphs as `<br/>`, equivalent to `\n:t`
+    preserve_line_breaks: bool,
+
+    /// replaces quotes, dashes and ellipses with typographic ones, equivalent to `':t`
+    smart_quotes: bool,
+
+    /// renders drawers other than property drawers, equivalent to `d:t`
+    render_drawers: bool,
+
+    /// depth of numbered headlines, zero disables numbering, equivalent to `num:N`
+    section_numbers: usize,
+
+    /// section numbers of headlines in each document, computed once per document
+    numbers: HashMap<SyntaxNode, HashMap<SyntaxNode, String>>,
+
+    /// element wrapping the whole document, `None` emits no wrapper
+    root_element: Option<String>,
+}
+
+/// Delimiters of LaTeX math in html output, see [`HtmlExport::with_math_delimiters`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum MathDelimiters {
+    /// Emits LaTeX fragments and environments as written
+    #[default]
+    Raw,
+    /// Uses `\(...\)` for inline math and `$$...$$` or `\[...\]` for display math
+    MathJax,
+    /// Uses `$...$` for inline math and `$$...$$` for display math, environments
+    /// are wrapped in `$$`
+    KaTeX,
+}
+
+type OverrideFn<W> = dyn FnMut(&SyntaxNode, &mut W) -> bool;
+
+type HighlightFn = dyn Fn(&str, &str) -> String;
+
+/// Source block highlighter, see [`HtmlExport::with_highlighter`]
+struct Highlighter {
+    highlight: Box<HighlightFn>,
+    /// wraps highlighted code in `<pre><code>`
+    wrap: bool,
+}
+
+struct Footnote {
+    id: String,
+    /// label of footnote, empty if it's anonymous
+    label: String,
+    /// `FN_DEF` or `FN_REF` node containing the definition
+    definition: SyntaxNode,
 }
 
 #[derive(Default, PartialEq, Eq)]
@@ -70,201 +207,1328 @@ enum TableRow {
     Body,
 }
 
-impl HtmlExport {
+impl Default for HtmlExport {
+    fn default() -> Self {
+        HtmlExport::new(String::new())
+    }
+}
+
+impl<W: fmt::Write> HtmlExport<W> {
+    /// Creates an exporter writing into given sink
+    ///
+    /// Errors returned by the sink are ignored. Use `HtmlExport::default()` to
+    /// render into an owned `String`.
+    ///
+    /// ```rust
+    /// use orgize::{Org, export::HtmlExport};
+    ///
+    /// let mut output = String::from("<!DOCTYPE html>");
+    /// let mut html = HtmlExport::new(&mut output);
+    /// Org::parse("hello").traverse(&mut html);
+    /// drop(html);
+    /// assert_eq!(output, "<!DOCTYPE html><main><section><p>hello</p></section></main>");
+    /// ```
+    pub fn new(output: W) -> Self {
+        HtmlExport {
+            output,
+            footnotes: Vec::new(),
+            footnotes_written: 0,
+            in_descriptive_list: Vec::new(),
+            table_row: TableRow::default(),
+            table_alignments: Vec::new(),
+            table_column: 0,
+            entity_representation: EntityRepresentation::default(),
+            math_delimiters: MathDelimiters::default(),
+            figure_results: None,
+            heading_self_links: false,
+            anchors: HashMap::new(),
+            link_targets: HashMap::new(),
+            macros: HashMap::new(),
+            radio_targets: HashMap::new(),
+            class_prefix: String::new(),
+            heading_offset: 0,
+            line_number: 0,
+            highlighter: None,
+            link_abbreviations: HashMap::new(),
+            user_entities: Vec::new(),
+            parse_config: ParseConfig::default(),
+            overrides: HashMap::new(),
+            export_tags: None,
+            excluded: HashMap::new(),
+            preserve_line_breaks: false,
+            smart_quotes: false,
+            render_drawers: false,
+            section_numbers: 0,
+            numbers: HashMap::new(),
+            root_element: Some("main".into()),
+        }
+    }
+
+    /// Sets which representation is used for entities, defaults to [`EntityRepresentation::Html`]
+    ///
+    /// ```rust
+    /// use orgize::{Org, ast::EntityRepresentation, export::HtmlExport};
+    ///
+    /// let mut html = HtmlExport::default().with_entity_representation(EntityRepresentation::Utf8);
+    /// Org::parse("\\alpha \\S").traverse(&mut html);
+    /// assert_eq!(html.finish(), "<main><section><p>α §</p></section></main>");
+    ///
+    /// // representations other than html are escaped
+    /// let mut html = HtmlExport::default().with_entity_representation(EntityRepresentation::Ascii);
+    /// Org::parse("\\lt{}script\\gt{}").traverse(&mut html);
+    /// assert_eq!(html.finish(), "<main><section><p>&lt;script&gt;</p></section></main>");
+    /// ```
+    pub fn with_entity_representation(mut self, repr: EntityRepresentation) -> Self {
+        self.entity_representation = repr;
+        self
+    }
+
+    /// Renders a self-link anchor `<a class="anchor" href="#id"></a>` inside each heading
+    ///
+    /// ```rust
+    /// use orgize::{Org, export::HtmlExport};
+    ///
+    /// let mut html = HtmlExport::default().with_heading_self_links(true);
+    /// Org::parse("* Hello World").traverse(&mut html);
+    /// assert_eq!(
+    ///     html.finish(),
+    ///     r##"<main><h1 id="hello-world"><a class="anchor" href="#hello-world"></a>Hello World</h1></main>"##
+    /// );
+    /// ```
+    pub fn with_heading_self_links(mut self, enabled: bool) -> Self {
+        self.heading_self_links = enabled;
+        self
+    }
+
+    /// Sets link abbreviations used to expand link paths, see [`ParseConfig::link_abbreviations`]
+    ///
+    /// [`Org::to_html`](crate::Org::to_html) uses abbreviations from the parse config of given org.
+    ///
+    /// ```rust
+    /// use orgize::{Org, export::HtmlExport};
+    ///
+    /// let org = Org::parse("#+LINK: gh https://github.com/%s\n[[gh:PoiScript/orgize][orgize]]");
+    /// let mut html = HtmlExport::default()
+    ///     .with_link_abbreviations(org.config().link_abbreviations.clone());
+    /// org.traverse(&mut html);
+    /// assert_eq!(
+    ///     html.finish(),
+    ///     "<main><section><p><a href=\"https://github.com/PoiScript/orgize\">orgize</a></p></section></main>"
+    /// );
+    /// ```
+    pub fn with_link_abbreviations(mut self, abbreviations: HashMap<String, String>) -> Self {
+        self.link_abbreviations = abbreviations;
+        self
+    }
+
+    /// Sets user-defined entities, see [`ParseConfig::user_entities`](crate::ParseConfig::user_entities)
+    ///
+    /// [`Org::to_html`](crate::Org::to_html) uses entities from the parse config of given org.
+    pub fn with_user_entities(mut self, entities: Vec<EntityInfo>) -> Self {
+        self.user_entities = entities;
+        self
+    }
+
+    /// Sets config used to parse org text generated during export, like
+    /// macro expansions, captions and org export blocks, defaults to [`ParseConfig::default`]
+    ///
+    /// [`Org::to_html`](crate::Org::to_html) uses the parse config of given org.
+    ///
+    /// ```rust
+    /// use orgize::{Org, ParseConfig, export::HtmlExport};
+    ///
+    /// let config = ParseConfig {
+    ///     todo_keywords: (vec!["WAIT".into()], vec![]),
+    ///     ..Default::default()
+    /// };
+    /// let org = config.parse("#+MACRO: note =$1=\n{{{note(WAIT)}}}");
+    /// let mut html = HtmlExport::default().with_parse_config(org.config().clone());
+    /// org.traverse(&mut html);
+    /// assert_eq!(html.finish(), "<main><section><p><code>WAIT</code></p></section></main>");
+    /// ```
+    pub fn with_parse_config(mut self, config: ParseConfig) -> Self {
+        self.parse_config = config;
+        self
+    }
+
+    /// Sets how delimiters of LaTeX math are emitted, defaults to [`MathDelimiters::Raw`]
+    ///
+    /// ```rust
+    /// use orgize::{Org, export::{HtmlExport, MathDelimiters}};
+    ///
+    /// let org = Org::parse(r"$a$ \(b\) $$c$$ \[d\]");
+    ///
+    /// let mut html = HtmlExport::default().with_math_delimiters(MathDelimiters::MathJax);
+    /// org.traverse(&mut html);
+    /// assert_eq!(html.finish(), r"<main><section><p>\(a\) \(b\) $$c$$ \[d\]</p></section></main>");
+    ///
+    /// let mut html = HtmlExport::default().with_math_delimiters(MathDelimiters::KaTeX);
+    /// org.traverse(&mut html);
+    /// assert_eq!(html.finish(), r"<main><section><p>$a$ $b$ $$c$$ $$d$$</p></section></main>");
+    /// ```
+    pub fn with_math_delimiters(mut self, delimiters: MathDelimiters) -> Self {
+        self.math_delimiters = delimiters;
+        self
+    }
+
+    /// Prepends given prefix to every emitted class name, defaults to an empty prefix
+    ///
+    /// ```rust
+    /// use orgize::{Org, export::HtmlExport};
+    ///
+    /// let mut html = HtmlExport::default().with_class_prefix("org-");
+    /// Org::parse("#+begin_verse\nhello\n#+end_verse").traverse(&mut html);
+    /// assert_eq!(
+    ///     html.finish(),
+    ///     "<main><section><p class=\"org-verse\">hello\n</p></section></main>"
+    /// );
+    /// ```
+    pub fn with_class_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.class_prefix = prefix.into();
+        self
+    }
+
+    /// Adds given offset to headline levels before clamping them to `<h6>`,
+    /// useful for embedding exported fragment into an existing page
+    ///
+    /// Anchor ids don't depend on levels, so they're unaffected by this option.
+    ///
+    /// ```rust
+    /// use orgize::{Org, export::HtmlExport};
+    ///
+    /// let mut html = HtmlExport::default().with_heading_offset(2);
+    /// Org::parse("* a\n***** b").traverse(&mut html);
+    /// assert_eq!(
+    ///     html.finish(),
+    ///     r#"<main><h3 id="a">a</h3><h6 id="b">b</h6></main>"#
+    /// );
+    /// ```
+    pub fn with_heading_offset(mut self, offset: usize) -> Self {
+        self.heading_offset = offset;
+        self
+    }
+
+    /// Omits subtrees according to given select and exclude tags, see [`ExportTags`]
+    ///
+    /// By default, all headlines are exported.
+    ///
+    /// ```rust
+    /// use orgize::{Org, export::{ExportTags, HtmlExport}};
+    ///
+    /// let org = Org::parse("* a\n* b :noexport:\n** c\n* d");
+    /// let mut html = HtmlExport::default().with_export_tags(ExportTags::from_document(&org.document()));
+    /// org.traverse(&mut html);
+    /// assert_eq!(html.finish(), r#"<main><h1 id="a">a</h1><h1 id="d">d</h1></main>"#);
+    ///
+    /// let org = Org::parse("intro\n* a\n** b :export:\n*** c\n** d\n* e");
+    /// let mut html = HtmlExport::default().with_export_tags(ExportTags::default());
+    /// org.traverse(&mut html);
+    /// assert_eq!(
+    ///     html.finish(),
+    ///     "<main><section><p>intro\n</p></section><h1 id=\"a\">a</h1><h2 id=\"b\">b </h2><h3 id=\"c\">c</h3></main>"
+    /// );
+    /// ```
+    pub fn with_export_tags(mut self, tags: ExportTags) -> Self {
+        self.export_tags = Some(tags);
+        self
+    }
+
+    /// Renders every newline inside a paragraph as `<br/>`, equivalent to
+    /// `\n:t` in `#+OPTIONS`, defaults to `false`
+    ///
+    /// Explicit line breaks (`\\`) are rendered as usual, and verse blocks
+    /// always preserve line breaks.
+    /// [`Org::to_html`](crate::Org::to_html) enables this option according to `#+OPTIONS`.
+    ///
+    /// ```rust
+    /// use orgize::{Org, export::HtmlExport};
+    ///
+    /// let org = Org::parse("a\nb\\\\\n*c\nd*\n\n#+begin_verse\ne\nf\n#+end_verse");
+    /// let mut html = HtmlExport::default().with_preserve_line_breaks(true);
+    /// org.traverse(&mut html);
+    /// assert_eq!(
+    ///     html.finish(),
+    ///     "<main><section><p>a<br/>\nb<br/><b>c<br/>\nd</b>\n</p><p class=\"verse\">e<br/>\nf\n</p></section></main>"
+    /// );
+    ///
+    /// let org = Org::parse("#+OPTIONS: \\n:t\n\na\nb");
+    /// assert_eq!(org.to_html(), "<main><section><p>a<br/>\nb</p></section></main>");
+    /// ```
+    pub fn with_preserve_line_breaks(mut self, preserve: bool) -> Self {
+        self.preserve_line_breaks = preserve;
+        self
+    }
+
+    /// Replaces straight quotes with curly ones, `---` and `--` with em and en
+    /// dashes, and `...` with an ellipsis, equivalent to `':t` in `#+OPTIONS`,
+    /// defaults to `false`
+    ///
+    /// Quotes are opened at the start of text or after whitespace or an opening
+    /// bracket, and closed otherwise. Verbatim, code and the content of example,
+    /// source and comment blocks are left untouched.
+    ///
+    /// ```rust
+    /// use orgize::{Org, export::HtmlExport};
+    ///
+    /// let org = Org::parse("\"Don't\" -- she said --- 'maybe...' =\"as is\"=");
+    /// let mut html = HtmlExport::default().with_smart_quotes(true);
+    /// org.traverse(&mut html);
+    /// assert_eq!(
+    ///     html.finish(),
+    ///     "<main><section><p>\u{201c}Don\u{2019}t\u{201d} \u{2013} she said \u{2014} \u{2018}maybe\u{2026}\u{2019} <code>&quot;as is&quot;</code></p></section></main>"
+    /// );
+    ///
+    /// let org = Org::parse("#+OPTIONS: ':t\n\n*Rust*'s");
+    /// assert_eq!(org.to_html(), "<main><section><p><b>Rust</b>\u{2019}s</p></section></main>");
+    /// ```
+    pub fn with_smart_quotes(mut self, smart_quotes: bool) -> Self {
+        self.smart_quotes = smart_quotes;
+        self
+    }
+
+    /// Renders content of drawers as `<div class="drawer">`, defaults to `false`
+    ///
+    /// Drawers are omitted by default, like `d:nil` in `#+OPTIONS`. Property
+    /// drawers are never rendered. [`Org::to_html`](crate::Org::to_html) enables this option if
+    /// `#+OPTIONS` contains `d:t`.
+    ///
+    /// ```rust
+    /// use orgize::{Org, export::HtmlExport};
+    ///
+    /// let org = Org::parse("* a\n:PROPERTIES:\n:ID: 1\n:END:\n:LOGBOOK:\nnote\n:END:\ntext");
+    /// let mut html = HtmlExport::default();
+    /// org.traverse(&mut html);
+    /// assert_eq!(html.finish(), r#"<main><h1 id="a">a</h1><section><p>text</p></section></main>"#);
+    ///
+    /// let mut html = HtmlExport::default().with_render_drawers(true);
+    /// org.traverse(&mut html);
+    /// assert_eq!(
+    ///     html.finish(),
+    ///     "<main><h1 id=\"a\">a</h1><section><div class=\"drawer\"><p>note\n</p></div><p>text</p></section></main>"
+    /// );
+    /// ```
+    pub fn with_render_drawers(mut self, render: bool) -> Self {
+        self.render_drawers = render;
+        self
+    }
+
+    /// Prepends section numbers like `1.2` to headlines up to given depth,
+    /// zero disables numbering, defaults to zero
+    ///
+    /// Commented headlines, headlines omitted by
+    /// [`with_export_tags`](HtmlExport::with_export_tags) and headlines with
+    /// `UNNUMBERED` property are not numbered, nor are their subtrees. Given
+    /// the same export tags, numbers are the same as in [`Org::section_numbers`](crate::Org::section_numbers).
+    ///
+    /// ```rust
+    /// use orgize::{Org, export::{ExportTags, HtmlExport}};
+    ///
+    /// let mut html = HtmlExport::default().with_section_numbers(2);
+    /// Org::parse("* a\n** b\n*** c\n* d").traverse(&mut html);
+    /// assert_eq!(
+    ///     html.finish(),
+    ///     "<main><h1 id=\"a\"><span class=\"section-number\">1</span> a</h1>\
+    ///     <h2 id=\"b\"><span class=\"section-number\">1.1</span> b</h2>\
+    ///     <h3 id=\"c\">c</h3>\
+    ///     <h1 id=\"d\"><span class=\"section-number\">2</span> d</h1></main>"
+    /// );
+    ///
+    /// let org = Org::parse("#+EXPORT_EXCLUDE_TAGS: draft\n* a :draft:\n* b");
+    /// let mut html = HtmlExport::default()
+    ///     .with_section_numbers(2)
+    ///     .with_export_tags(ExportTags::from_document(&org.document()));
+    /// org.traverse(&mut html);
+    /// assert_eq!(
+    ///     html.finish(),
+    ///     "<main><section></section><h1 id=\"b\"><span class=\"section-number\">1</span> b</h1></main>"
+    /// );
+    /// ```
+    pub fn with_section_numbers(mut self, max_depth: usize) -> Self {
+        self.section_numbers = max_depth;
+        self
+    }
+
+    /// Sets element wrapping the whole document, defaults to `main`
+    ///
+    /// `None` emits no wrapper, useful for embedding exported fragment into
+    /// an existing page.
+    ///
+    /// ```rust
+    /// use orgize::{Org, export::HtmlExport};
+    ///
+    /// let org = Org::parse("hello");
+    ///
+    /// let mut html = HtmlExport::default().with_root_element(Some("article"));
+    /// org.traverse(&mut html);
+    /// assert_eq!(html.finish(), "<article><section><p>hello</p></section></article>");
+    ///
+    /// let mut html = HtmlExport::default().with_root_element(None);
+    /// org.traverse(&mut html);
+    /// assert_eq!(html.finish(), "<section><p>hello</p></section>");
+    /// ```
+    pub fn with_root_element(mut self, element: Option<&str>) -> Self {
+        self.root_element = element.map(String::from);
+        self
+    }
+
+    /// Highlights source block code with given callback
+    ///
+    /// The callback receives language (empty if not specified) and code, and
+    /// returns html which is inserted verbatim, so it must be escaped by the
+    /// callback itself. Set `wrap` to `false` if the returned html comes with its
+    /// own `<pre>` wrapper. Line numbers from `-n` and `+n` switches are not
+    /// applied to highlighted code, while `-r` still removes coderef labels.
+    ///
+    /// ```rust
+    /// use orgize::{Org, export::{HtmlEscape, HtmlExport}};
+    ///
+    /// let org = Org::parse("#+begin_src rust\nlet a = 1;\n#+end_src");
+    ///
+    /// let mut html = HtmlExport::default().with_highlighter(
+    ///     |lang, code| format!("<b>{}</b>:{}", lang, HtmlEscape(code)),
+    ///     true,
+    /// );
+    /// org.traverse(&mut html);
+    /// assert_eq!(
+    ///     html.finish(),
+    ///     "<main><section><pre><code class=\"language-rust\"><b>rust</b>:let a = 1;\n</code></pre></section></main>"
+    /// );
+    ///
+    /// let mut html = HtmlExport::default().with_highlighter(
+    ///     |_, code| format!("<pre class=\"hl\">{}</pre>", HtmlEscape(code)),
+    ///     false,
+    /// );
+    /// org.traverse(&mut html);
+    /// assert_eq!(
+    ///     html.finish(),
+    ///     "<main><section><pre class=\"hl\">let a = 1;\n</pre></section></main>"
+    /// );
+    /// ```
+    pub fn with_highlighter(
+        mut self,
+        highlight: impl Fn(&str, &str) -> String + 'static,
+        wrap: bool,
+    ) -> Self {
+        self.highlighter = Some(Highlighter {
+            highlight: Box::new(highlight),
+            wrap,
+        });
+        self
+    }
+
+    /// Registers a closure which runs before entering nodes of given kind
+    ///
+    /// The closure receives the node and output sink. Returning `true` means
+    /// the node was handled, so default rendering of it and its descendants is
+    /// skipped. Returning `false` falls back to default rendering. Registering
+    /// another closure for the same kind replaces the previous one.
+    ///
+    /// ```rust
+    /// use orgize::{Org, SyntaxKind, ast::Link, export::{HtmlEscape, HtmlExport}, rowan::ast::AstNode};
+    /// use std::fmt::Write;
+    ///
+    /// let mut html = HtmlExport::default().on_enter(SyntaxKind::LINK, |node, out| {
+    ///     let link = Link::cast(node.clone()).unwrap();
+    ///     match link.path().strip_prefix("id:") {
+    ///         Some(id) => {
+    ///             let _ = write!(out, r#"<a href="/notes/{}.html">note</a>"#, HtmlEscape(id));
+    ///             true
+    ///         }
+    ///         None => false,
+    ///     }
+    /// });
+    /// Org::parse("[[id:abc]] [[https://example.com][site]]").traverse(&mut html);
+    /// assert_eq!(
+    ///     html.finish(),
+    ///     r#"<main><section><p><a href="/notes/abc.html">note</a> <a href="https://example.com">site</a></p></section></main>"#
+    /// );
+    /// ```
+    pub fn on_enter(
+        mut self,
+        kind: SyntaxKind,
+        f: impl FnMut(&SyntaxNode, &mut W) -> bool + 'static,
+    ) -> Self {
+        self.overrides.insert(kind, Box::new(f));
+        self
+    }
+
     pub fn push_str(&mut self, s: impl AsRef<str>) {
-        self.output += s.as_ref();
+        let _ = self.output.write_str(s.as_ref());
     }
 
-    pub fn finish(self) -> String {
+    /// Consumes this exporter, returning the underlying sink
+    pub fn finish(self) -> W {
         self.output
     }
 
     /// Render syntax node to html string
     ///
+    /// Definitions of footnotes referenced inside the node, and not written by
+    /// a previous call, are written right after it, since there's no end of
+    /// document to collect them at. Footnote numbers continue across calls.
+    ///
     /// ```rust
-    /// use orgize::{Org, ast::Bold, export::HtmlExport, rowan::ast::AstNode};
+    /// use orgize::{Org, ast::{Bold, Paragraph}, export::HtmlExport, rowan::ast::AstNode};
     ///
     /// let org = Org::parse("* /hello/ *world*");
     /// let bold = org.first_node::<Bold>().unwrap();
     /// let mut html = HtmlExport::default();
     /// html.render(bold.syntax());
     /// assert_eq!(html.finish(), "<b>world</b>");
+    ///
+    /// let org = Org::parse("a[fn:1]\n\n[fn:1] note");
+    /// let paragraph = org.first_node::<Paragraph>().unwrap();
+    /// let mut html = HtmlExport::default();
+    /// html.render(paragraph.syntax());
+    /// assert_eq!(
+    ///     html.finish(),
+    ///     "<p>a<a id=\"footnote-ref_1\" href=\"#footnote_1\" class=\"footnote-reference\">[1]</a>\n</p>\
+    ///     <div class=\"footnotes\"><div class=\"footnote-definition\" id=\"footnote_1\">\
+    ///     <a href=\"#footnote-ref_1\" class=\"footnote-reference\">[1]</a>\
+    ///     <span class=\"footnote-content\"> note</span></div></div>"
+    /// );
     /// ```
     pub fn render(&mut self, node: &SyntaxNode) {
         let mut ctx = TraversalContext::default();
         self.element(SyntaxElement::Node(node.clone()), &mut ctx);
+        // documents already write definitions when leaving
+        if node.kind() != SyntaxKind::DOCUMENT {
+            self.footnote_definitions(&mut ctx);
+        }
+    }
+
+    /// Writes opening tag of a table cell in given column
+    fn table_cell_start(&mut self, column: usize) {
+        let alignment = match self.table_alignments.get(column) {
+            Some(TableAlignment::Center) => "center",
+            Some(TableAlignment::Right) => "right",
+            _ => "left",
+        };
+        let _ = write!(&mut self.output, "<td style=\"text-align: {alignment}\">");
+    }
+
+    /// Writes empty cells up to given column, since blank cells have no node
+    fn pad_table_cells(&mut self, column: usize) {
+        while self.table_column < column {
+            self.table_cell_start(self.table_column);
+            self.push_str("</td>");
+            self.table_column += 1;
+        }
+    }
+
+    /// Writes definitions of footnotes not written yet, if any
+    fn footnote_definitions(&mut self, ctx: &mut TraversalContext) {
+        if self.footnotes_written == self.footnotes.len() {
+            return;
+        }
+        let _ = write!(
+            &mut self.output,
+            "<div class=\"{}footnotes\">",
+            self.class_prefix
+        );
+        // definitions may reference new footnotes while rendering
+        while let Some(footnote) = self.footnotes.get(self.footnotes_written) {
+            let _ = write!(
+                &mut self.output,
+                "<div class=\"{prefix}footnote-definition\" id=\"footnote_{id}\">\
+                <a href=\"#footnote-ref_{id}\" class=\"{prefix}footnote-reference\">[{}]</a>",
+                self.footnotes_written + 1,
+                id = HtmlEscape(&footnote.id),
+                prefix = self.class_prefix,
+            );
+            self.footnotes_written += 1;
+            for child in footnote.definition.clone().children() {
+                self.element(SyntaxElement::Node(child), ctx);
+            }
+            self.push_str("</div>");
+        }
+        self.push_str("</div>");
+    }
+
+    /// Render syntax node to html string like [`HtmlExport::render`], except
+    /// that a paragraph is rendered without its `<p>` wrapper
+    ///
+    /// Useful for placing paragraph contents inside inline contexts, e.g.
+    /// table cells or captions.
+    ///
+    /// ```rust
+    /// use orgize::{Org, ast::Paragraph, export::HtmlExport, rowan::ast::AstNode};
+    ///
+    /// let org = Org::parse("/hello/ *world*");
+    /// let paragraph = org.first_node::<Paragraph>().unwrap();
+    ///
+    /// let mut html = HtmlExport::default();
+    /// html.render_inline(paragraph.syntax());
+    /// assert_eq!(html.finish(), "<i>hello</i> <b>world</b>");
+    ///
+    /// let mut html = HtmlExport::default();
+    /// html.render(paragraph.syntax());
+    /// assert_eq!(html.finish(), "<p><i>hello</i> <b>world</b></p>");
+    /// ```
+    pub fn render_inline(&mut self, node: &SyntaxNode) {
+        if node.kind() != SyntaxKind::PARAGRAPH {
+            return self.render(node);
+        }
+        let mut ctx = TraversalContext::default();
+        for elem in node.children_with_tokens() {
+            self.element(elem, &mut ctx);
+        }
+    }
+
+    /// Returns anchor id of element which internal link points to
+    fn internal_link_href(&mut self, link: &Link, node: SyntaxNode) -> String {
+        match node.kind() {
+            SyntaxKind::HEADLINE => self.headline_anchor(&Headline { syntax: node }),
+            SyntaxKind::TARGET => target_anchor(&node),
+            _ if link.internal_kind() == Some(InternalLinkKind::CodeRef) => {
+                let path = link.path();
+                format!("coderef-{}", &path[1..path.len() - 1])
+            }
+            _ => link.path().trim().to_string(),
+        }
+    }
+
+    /// Renders `<pre><code>`, with language class if `language` isn't empty
+    fn code_begin(&mut self, language: &str, block: &SourceBlock) {
+        self.push_str("<pre");
+        self.attributes(element_attrs(block.syntax()), None);
+        self.push_str(">");
+        if language.is_empty() {
+            self.push_str("<code>");
+        } else {
+            let _ = write!(
+                &mut self.output,
+                r#"<code class="{}language-{}">"#,
+                self.class_prefix,
+                HtmlEscape(language)
+            );
+        }
+    }
+
+    /// Renders source block code wrapped in `<pre><code>`, honoring `-n`, `+n` and `-r` switches
+    fn source_block_code(&mut self, block: &SourceBlock) {
+        if let Some(highlighter) = &self.highlighter {
+            let language = block.language();
+            let language = language.as_deref().unwrap_or_default();
+            let mut code = block.value();
+            if block.removes_labels() {
+                code = code
+                    .split_inclusive('\n')
+                    .map(|line| match (split_coderef(line), line.ends_with('\n')) {
+                        (Some((code, _)), true) => format!("{code}\n"),
+                        (Some((code, _)), false) => code.to_string(),
+                        (None, _) => line.to_string(),
+                    })
+                    .collect();
+            }
+            let highlighted = (highlighter.highlight)(language, &code);
+            if !highlighter.wrap {
+                return self.push_str(highlighted);
+            }
+            self.code_begin(language, block);
+            self.push_str(highlighted);
+            return self.push_str("</code></pre>");
+        }
+
+        self.code_begin(block.language().as_deref().unwrap_or_default(), block);
+
+        let value = block.value();
+        let removes_labels = block.removes_labels();
+        let mut number = match block.line_numbers() {
+            Some(LineNumbers::New(n)) => Some(n),
+            Some(LineNumbers::Continued(n)) => Some(self.line_number + n),
+            None => None,
+        };
+
+        if number.is_none() && !removes_labels && !value.contains("(ref:") {
+            let _ = write!(&mut self.output, "{}", HtmlEscape(&value));
+        } else {
+            for line in value.split_inclusive('\n') {
+                let (line, newline) = line.split_at(line.trim_end_matches(['\n', '\r']).len());
+                let (code, label) = match split_coderef(line) {
+                    Some((code, label)) if removes_labels => (code, Some(label)),
+                    Some((_, label)) => (line, Some(label)),
+                    None => (line, None),
+                };
+                let Some(n) = number else {
+                    // labelled lines are still link targets without line numbers
+                    match label {
+                        Some(label) => {
+                            let _ = write!(
+                                &mut self.output,
+                                r#"<span id="coderef-{}">{}</span>{newline}"#,
+                                HtmlEscape(label),
+                                HtmlEscape(code)
+                            );
+                        }
+                        None => {
+                            let _ = write!(&mut self.output, "{}{newline}", HtmlEscape(code));
+                        }
+                    }
+                    continue;
+                };
+                let _ = write!(
+                    &mut self.output,
+                    r#"<span class="{}line" data-line="{n}""#,
+                    self.class_prefix
+                );
+                if let Some(label) = label {
+                    let _ = write!(&mut self.output, r#" id="coderef-{}""#, HtmlEscape(label));
+                }
+                let _ = write!(&mut self.output, ">{}</span>{newline}", HtmlEscape(code));
+                self.line_number = n;
+                number = Some(n + 1);
+            }
+        }
+
+        self.push_str("</code></pre>");
+    }
+
+    /// Parses given text with [`HtmlExport::with_parse_config`] and renders
+    /// its first paragraph as inline markup
+    fn render_org_inline(&mut self, text: &str) {
+        let org = self.parse_config.clone().parse(text);
+        match org.first_node::<Paragraph>() {
+            Some(paragraph) => self.render_inline(paragraph.syntax()),
+            None => {
+                let _ = write!(&mut self.output, "{}", HtmlEscape(text));
+            }
+        }
+    }
+
+    /// Writes `id` attribute from `#+NAME` keyword of element, if any
+    fn name_id(&mut self, element: &SyntaxNode) {
+        if let Some(name) = element_name(element) {
+            let _ = write!(&mut self.output, r#" id="{}""#, HtmlEscape(&name));
+        }
+    }
+
+    /// Writes attributes from `#+ATTR_HTML` keywords, `class` is merged into
+    /// the default class if any
+    fn attributes(&mut self, attrs: Vec<(String, String)>, class: Option<String>) {
+        let mut classes: Vec<String> = class.into_iter().collect();
+        let mut others = vec![];
+        for (key, value) in attrs {
+            if key == "class" {
+                classes.push(value);
+            } else if is_attribute_name(&key) {
+                others.push((key, value));
+            }
+        }
+        if !classes.is_empty() {
+            let _ = write!(
+                &mut self.output,
+                r#" class="{}""#,
+                HtmlEscape(classes.join(" "))
+            );
+        }
+        for (key, value) in others {
+            let _ = write!(
+                &mut self.output,
+                r#" {}="{}""#,
+                HtmlEscape(key),
+                HtmlEscape(value)
+            );
+        }
+    }
+
+    /// Renders the planning line under a headline
+    fn planning(&mut self, planning: &Planning) {
+        let _ = write!(
+            &mut self.output,
+            r#"<p class="{}planning">"#,
+            self.class_prefix
+        );
+        let mut first = true;
+        for node in planning.syntax().children() {
+            let (class, keyword) = match node.kind() {
+                SyntaxKind::PLANNING_DEADLINE => ("deadline", "DEADLINE"),
+                SyntaxKind::PLANNING_SCHEDULED => ("scheduled", "SCHEDULED"),
+                SyntaxKind::PLANNING_CLOSED => ("closed", "CLOSED"),
+                _ => continue,
+            };
+            let Some(timestamp) = node.children().find_map(Timestamp::cast) else {
+                continue;
+            };
+            if !first {
+                self.push_str(" ");
+            }
+            first = false;
+            let _ = write!(
+                &mut self.output,
+                "<span class=\"{}{class}\">{keyword}: <time",
+                self.class_prefix
+            );
+            if let Some(datetime) = timestamp_datetime(&timestamp) {
+                let _ = write!(&mut self.output, " datetime=\"{}\"", HtmlEscape(datetime));
+            }
+            let _ = write!(
+                &mut self.output,
+                ">{}</time></span>",
+                HtmlEscape(timestamp.raw())
+            );
+        }
+        self.push_str("</p>");
+    }
+
+    /// Renders text, linking occurrences of radio targets
+    ///
+    /// In verse blocks, leading whitespace of each line becomes `&nbsp;` and
+    /// newlines become `<br/>`. The latter also applies to paragraphs if
+    /// line breaks are preserved.
+    fn text(&mut self, text: &Token) {
+        let verse = verse_content(text);
+        let end = match verse {
+            Some(content) => Some(content.end()),
+            None if self.preserve_line_breaks => paragraph_content_end(text),
+            None => None,
+        };
+        let Some(end) = end else {
+            return self.text_segment(text, text);
+        };
+
+        let mut offset = text.start();
+        let mut line_start = verse.is_some_and(|content| {
+            offset == content.start() || char_before(text.syntax()) == Some('\n')
+        });
+        for line in text.split_inclusive('\n') {
+            offset += TextSize::of(line);
+            let mut line = line;
+            if line_start {
+                let trimmed = line.trim_start_matches([' ', '\t']);
+                self.push_str("&nbsp;".repeat(line.len() - trimmed.len()));
+                line = trimmed;
+            }
+            match line.strip_suffix('\n') {
+                // newline ending the paragraph or verse is kept as is
+                Some(line) if offset < end => {
+                    let line = line.strip_suffix('\r').unwrap_or(line);
+                    self.text_segment(text, line);
+                    self.push_str("<br/>\n");
+                }
+                _ => self.text_segment(text, line),
+            }
+            line_start = verse.is_some();
+        }
+    }
+
+    /// Renders part of given text token
+    fn text_segment(&mut self, text: &Token, segment: &str) {
+        let smart = self.smart_quotes && !is_verbatim(text);
+        // segment is always a slice of the text token
+        let offset = segment.as_ptr() as usize - text.as_ptr() as usize;
+        // character preceding given range of segment decides whether a quote
+        // opens or closes
+        let typographic = |range: Range<usize>| {
+            if !smart {
+                return Cow::Borrowed(&segment[range]);
+            }
+            // only quotes depend on the preceding character
+            let before = if segment[range.clone()].contains(['"', '\'']) {
+                text[..offset + range.start]
+                    .chars()
+                    .next_back()
+                    .or_else(|| char_before(text.syntax()))
+            } else {
+                None
+            };
+            smart_quotes(&segment[range], before)
+        };
+
+        let no_links = text.syntax().parent_ancestors().any(|n| {
+            matches!(
+                n.kind(),
+                SyntaxKind::VERBATIM
+                    | SyntaxKind::CODE
+                    | SyntaxKind::LINK
+                    | SyntaxKind::RADIO_TARGET
+                    | SyntaxKind::TARGET
+            )
+        });
+        let root = text.syntax().parent_ancestors().last();
+        let targets = match root {
+            Some(root) if !no_links => self
+                .radio_targets
+                .entry(root.clone())
+                .or_insert_with(|| radio_targets(&root).into_iter().map(|(t, _)| t).collect()),
+            _ => {
+                let _ = write!(
+                    &mut self.output,
+                    "{}",
+                    HtmlEscape(typographic(0..segment.len()))
+                );
+                return;
+            }
+        };
+
+        let mut start = 0;
+        while let Some((range, idx)) = find_radio_target(&segment[start..], targets) {
+            let _ = write!(
+                &mut self.output,
+                r##"{}<a href="#radio-{}">{}</a>"##,
+                HtmlEscape(typographic(start..start + range.start)),
+                HtmlEscape(slugify(&targets[idx])),
+                HtmlEscape(typographic(start + range.start..start + range.end))
+            );
+            start += range.end;
+        }
+        let _ = write!(
+            &mut self.output,
+            "{}",
+            HtmlEscape(typographic(start..segment.len()))
+        );
+    }
+
+    /// Returns `true` if subtree of given headline is omitted from export,
+    /// either because it's commented or because of export tags
+    fn is_excluded(&mut self, headline: &Headline) -> bool {
+        if headline.is_commented() {
+            return true;
+        }
+        let Some(tags) = &self.export_tags else {
+            return false;
+        };
+        let root = headline
+            .syntax()
+            .ancestors()
+            .last()
+            .unwrap_or_else(|| headline.syntax().clone());
+        self.excluded
+            .entry(root)
+            .or_insert_with_key(|root| tags.excluded_headlines(root))
+            .contains(headline.syntax())
+    }
+
+    fn headline_number(&mut self, headline: &Headline) -> Option<String> {
+        if self.section_numbers == 0 {
+            return None;
+        }
+        let root = headline.syntax().ancestors().last();
+        let root = root.as_ref().unwrap_or(headline.syntax());
+        let max_depth = self.section_numbers;
+        let tags = self.export_tags.as_ref();
+        self.numbers
+            .entry(root.clone())
+            .or_insert_with(|| {
+                headline_numbers(root, max_depth, tags)
+                    .into_iter()
+                    .map(|(hdl, number)| (hdl.syntax, number))
+                    .collect()
+            })
+            .get(headline.syntax())
+            .cloned()
+    }
+
+    fn resolve_link(&mut self, link: &Link) -> Option<SyntaxNode> {
+        let root = link.syntax().ancestors().last()?;
+        self.link_targets
+            .entry(root)
+            .or_insert_with_key(LinkTargets::new)
+            .resolve(link)
+    }
+
+    fn headline_anchor(&mut self, headline: &Headline) -> String {
+        if !self.anchors.contains_key(headline.syntax()) {
+            let root = headline.syntax().ancestors().last();
+            let root = root.as_ref().unwrap_or(headline.syntax());
+            self.anchors.extend(
+                headline_anchors(root)
+                    .into_iter()
+                    .map(|(hdl, anchor)| (hdl.syntax, anchor)),
+            );
+        }
+        self.anchors
+            .get(headline.syntax())
+            .cloned()
+            .unwrap_or_default()
     }
 }
 
-impl Traverser for HtmlExport {
+impl<W: fmt::Write> Traverser for HtmlExport<W> {
     fn event(&mut self, event: Event, ctx: &mut TraversalContext) {
+        if let Event::Enter(container) = &event {
+            let node = container.syntax();
+            if let Some(f) = self.overrides.get_mut(&node.kind()) {
+                if f(node, &mut self.output) {
+                    return ctx.skip();
+                }
+            }
+        }
+
         match event {
-            Event::Enter(Container::Document(_)) => self.output += "<main>",
-            Event::Leave(Container::Document(_)) => self.output += "</main>",
+            Event::Enter(container)
+                if self.figure_results == Some(container.syntax().text_range()) =>
+            {
+                ctx.skip()
+            }
 
+            Event::Enter(Container::Document(_)) => {
+                if let Some(element) = &self.root_element {
+                    let _ = write!(&mut self.output, "<{element}>");
+                }
+            }
+            Event::Leave(Container::Document(_)) => {
+                self.footnote_definitions(ctx);
+                if let Some(element) = &self.root_element {
+                    let _ = write!(&mut self.output, "</{element}>");
+                }
+            }
+
+            Event::Enter(Container::Headline(headline)) if self.is_excluded(&headline) => {
+                ctx.skip()
+            }
             Event::Enter(Container::Headline(headline)) => {
-                let level = min(headline.level(), 6);
-                let _ = write!(&mut self.output, "<h{level}>");
+                let level = min(headline.level() + self.heading_offset, 6);
+                let id = self.headline_anchor(&headline);
+                let _ = write!(&mut self.output, "<h{level} id=\"{}\">", HtmlEscape(&id));
+                if self.heading_self_links {
+                    let _ = write!(
+                        &mut self.output,
+                        "<a class=\"{}anchor\" href=\"#{}\"></a>",
+                        self.class_prefix,
+                        HtmlEscape(&id)
+                    );
+                }
+                if let Some(number) = self.headline_number(&headline) {
+                    let _ = write!(
+                        &mut self.output,
+                        "<span class=\"{}section-number\">{number}</span> ",
+                        self.class_prefix
+                    );
+                }
                 for elem in headline.title() {
                     self.element(elem, ctx);
                 }
                 let _ = write!(&mut self.output, "</h{level}>");
+                if let Some(planning) = headline.planning() {
+                    self.planning(&planning);
+                }
             }
             Event::Leave(Container::Headline(_)) => {}
 
-            Event::Enter(Container::FnRef(t)) => {
-                if let Some(label) = t.label() {
+            Event::Enter(Container::InlineTask(task)) => {
+                let _ = write!(
+                    &mut self.output,
+                    r#"<div class="{}inlinetask"><b>"#,
+                    self.class_prefix
+                );
+                if let Some(keyword) = task.todo_keyword() {
                     let _ = write!(
                         &mut self.output,
-                        "<a href=\"#footnote_{}\" class=\"footnote-reference\">[{}]",
-                        label.syntax().text(),
-                        label.syntax().text()
+                        r#"<span class="{}todo">{}</span> "#,
+                        self.class_prefix,
+                        HtmlEscape(&keyword)
                     );
                 }
-                self.output += "</a>";
+                for elem in task.title() {
+                    self.element(elem, ctx);
+                }
+                self.push_str("</b><br/>");
             }
-            Event::Leave(Container::FnRef(_)) => {}
+            Event::Leave(Container::InlineTask(_)) => self.push_str("</div>"),
+
+            Event::Enter(Container::FnRef(fn_ref)) => {
+                let label = fn_ref.label().map(|l| l.to_string()).unwrap_or_default();
+                let inline = fn_ref
+                    .syntax()
+                    .children()
+                    .any(|n| n.kind() == SyntaxKind::FN_CONTENT);
 
-            Event::Enter(Container::FnDef(t)) => {
-                self.output += "<aside ";
-                self.output += r#"class="footnote-definition" "#;
-                self.output += ">";
+                let existing = self
+                    .footnotes
+                    .iter()
+                    .position(|f| !label.is_empty() && f.label == label);
 
-                if let Some(label) = t.label() {
-                    self.output += "<a ";
+                if let Some(idx) = existing {
+                    let _ = write!(
+                        &mut self.output,
+                        "<a href=\"#footnote_{}\" class=\"{}footnote-reference\">[{}]</a>",
+                        HtmlEscape(&self.footnotes[idx].id),
+                        self.class_prefix,
+                        idx + 1
+                    );
+                } else if let Some(definition) = inline
+                    .then(|| fn_ref.syntax().clone())
+                    .or_else(|| find_fn_def(fn_ref.syntax(), &label))
+                {
+                    // footnotes are numbered in order of first reference
+                    let n = self.footnotes.len() + 1;
+                    let id = if label.is_empty() {
+                        format!("anonymous-{n}")
+                    } else {
+                        label.clone()
+                    };
                     let _ = write!(
                         &mut self.output,
-                        "href=\"#footnote_{}\" ",
-                        label.syntax().text()
+                        "<a id=\"footnote-ref_{id}\" href=\"#footnote_{id}\" class=\"{}footnote-reference\">[{n}]</a>",
+                        self.class_prefix,
+                        id = HtmlEscape(&id),
                     );
-                    self.output += "class=\"footnote-reference\" ";
-                    self.output += ">";
-                    let _ = write!(&mut self.output, "[{}]", label.syntax().text());
-                    self.output += "</a>";
+                    self.footnotes.push(Footnote {
+                        id,
+                        label,
+                        definition,
+                    });
+                } else {
+                    // missing definition
+                    let _ = write!(&mut self.output, "[{}]", HtmlEscape(&label));
                 }
-            }
-            Event::Leave(Container::FnDef(_)) => {
-                self.output += "</aside>";
+
+                ctx.skip();
             }
 
-            Event::Enter(Container::FnContent(c)) => {
-                self.output += "<span class=\"footnote-content\" ";
-                if let Some(parent) = c.syntax().parent() {
-                    if parent.kind() == SyntaxKind::FN_REF || parent.kind() == SyntaxKind::FN_DEF {
-                        let label = token(&parent, SyntaxKind::FN_LABEL).unwrap();
-                        let _ = write!(&mut self.output, "id=\"footnote_{}\" ", label);
-                    }
-                }
-                self.output += ">";
+            // footnote definitions are rendered at the end of document
+            Event::Enter(Container::FnDef(_)) => ctx.skip(),
+
+            Event::Enter(Container::FnContent(_)) => {
+                let _ = write!(
+                    &mut self.output,
+                    "<span class=\"{}footnote-content\">",
+                    self.class_prefix
+                );
             }
             Event::Leave(Container::FnContent(_)) => {
-                self.output += "</span>";
+                self.push_str("</span>");
             }
 
-            Event::Enter(Container::Paragraph(_)) => self.output += "<p>",
-            Event::Leave(Container::Paragraph(_)) => self.output += "</p>",
+            Event::Enter(Container::Paragraph(paragraph)) => {
+                // a captioned image standing alone is wrapped in a figure
+                if let Some((link, caption)) = standalone_image(paragraph.syntax())
+                    .and_then(|link| Some((link.clone(), link.caption()?.value()?)))
+                {
+                    self.push_str("<figure");
+                    self.name_id(paragraph.syntax());
+                    self.push_str(">");
+                    self.element(SyntaxElement::Node(link.syntax().clone()), ctx);
+                    self.push_str("<figcaption>");
+                    self.render_org_inline(caption.trim());
+                    self.push_str("</figcaption></figure>");
+                    return ctx.skip();
+                }
+
+                self.push_str("<p");
+                self.name_id(paragraph.syntax());
+                self.push_str(">");
+            }
+            Event::Leave(Container::Paragraph(_)) => self.push_str("</p>"),
 
-            Event::Enter(Container::Section(_)) => self.output += "<section>",
-            Event::Leave(Container::Section(_)) => self.output += "</section>",
+            // body of an inline task is already wrapped in its div
+            Event::Enter(Container::Section(section))
+            | Event::Leave(Container::Section(section))
+                if section
+                    .syntax()
+                    .parent()
+                    .is_some_and(|n| n.kind() == SyntaxKind::INLINE_TASK) => {}
+            Event::Enter(Container::Section(_)) => self.push_str("<section>"),
+            Event::Leave(Container::Section(_)) => self.push_str("</section>"),
 
-            Event::Enter(Container::Italic(_)) => self.output += "<i>",
-            Event::Leave(Container::Italic(_)) => self.output += "</i>",
+            Event::Enter(Container::Italic(_)) => self.push_str("<i>"),
+            Event::Leave(Container::Italic(_)) => self.push_str("</i>"),
 
-            Event::Enter(Container::Bold(_)) => self.output += "<b>",
-            Event::Leave(Container::Bold(_)) => self.output += "</b>",
+            Event::Enter(Container::Bold(_)) => self.push_str("<b>"),
+            Event::Leave(Container::Bold(_)) => self.push_str("</b>"),
 
-            Event::Enter(Container::Strike(_)) => self.output += "<s>",
-            Event::Leave(Container::Strike(_)) => self.output += "</s>",
+            Event::Enter(Container::Strike(_)) => self.push_str("<s>"),
+            Event::Leave(Container::Strike(_)) => self.push_str("</s>"),
 
-            Event::Enter(Container::Underline(_)) => self.output += "<u>",
-            Event::Leave(Container::Underline(_)) => self.output += "</u>",
+            Event::Enter(Container::Underline(_)) => self.push_str("<u>"),
+            Event::Leave(Container::Underline(_)) => self.push_str("</u>"),
 
-            Event::Enter(Container::Verbatim(_)) => self.output += "<code>",
-            Event::Leave(Container::Verbatim(_)) => self.output += "</code>",
+            Event::Enter(Container::Verbatim(_)) => self.push_str("<code>"),
+            Event::Leave(Container::Verbatim(_)) => self.push_str("</code>"),
 
-            Event::Enter(Container::Code(_)) => self.output += "<code>",
-            Event::Leave(Container::Code(_)) => self.output += "</code>",
+            Event::Enter(Container::Code(_)) => self.push_str("<code>"),
+            Event::Leave(Container::Code(_)) => self.push_str("</code>"),
 
             Event::Enter(Container::SourceBlock(block)) => {
-                if let Some(language) = block.language() {
+                let caption = block.caption().and_then(|c| c.value());
+                let exports_both = block
+                    .header_argument("exports")
+                    .is_some_and(|e| e.eq_ignore_ascii_case("both"));
+
+                // captioned code and its results are rendered together in one figure
+                if let (Some(caption), true) = (caption, exports_both) {
+                    self.push_str("<figure>");
+                    self.source_block_code(&block);
+                    if let Some(results) = block.results_element() {
+                        let range = results.text_range();
+                        self.element(SyntaxElement::Node(results), ctx);
+                        self.figure_results = Some(range);
+                    }
                     let _ = write!(
                         &mut self.output,
-                        r#"<pre><code class="language-{}">"#,
-                        HtmlEscape(&language)
+                        "<figcaption>{}</figcaption></figure>",
+                        HtmlEscape(caption.trim())
                     );
-                } else {
-                    self.output += r#"<pre><code>"#
+                    return ctx.skip();
                 }
+
+                self.source_block_code(&block);
+                ctx.skip();
+            }
+
+            Event::Enter(Container::QuoteBlock(block)) => {
+                self.push_str("<blockquote");
+                self.attributes(element_attrs(block.syntax()), None);
+                self.push_str(">");
+            }
+            Event::Leave(Container::QuoteBlock(_)) => self.push_str("</blockquote>"),
+
+            Event::Enter(Container::VerseBlock(block)) => {
+                self.push_str("<p");
+                let class = format!("{}verse", self.class_prefix);
+                self.attributes(element_attrs(block.syntax()), Some(class));
+                self.push_str(">");
+            }
+            Event::Leave(Container::VerseBlock(_)) => self.push_str("</p>"),
+
+            Event::Enter(Container::ExampleBlock(block)) => {
+                self.push_str("<pre");
+                let class = format!("{}example", self.class_prefix);
+                self.attributes(element_attrs(block.syntax()), Some(class));
+                self.push_str(">");
             }
-            Event::Leave(Container::SourceBlock(_)) => self.output += "</code></pre>",
+            Event::Leave(Container::ExampleBlock(_)) => self.push_str("</pre>"),
 
-            Event::Enter(Container::QuoteBlock(_)) => self.output += "<blockquote>",
-            Event::Leave(Container::QuoteBlock(_)) => self.output += "</blockquote>",
+            // property drawers are metadata, never prose
+            Event::Enter(Container::PropertyDrawer(_)) => ctx.skip(),
 
-            Event::Enter(Container::VerseBlock(_)) => self.output += "<p class=\"verse\">",
-            Event::Leave(Container::VerseBlock(_)) => self.output += "</p>",
+            Event::Enter(Container::Drawer(_)) if self.render_drawers => {
+                let _ = write!(
+                    &mut self.output,
+                    r#"<div class="{}drawer">"#,
+                    self.class_prefix
+                );
+            }
+            Event::Enter(Container::Drawer(_)) => ctx.skip(),
+            Event::Leave(Container::Drawer(_)) => self.push_str("</div>"),
 
-            Event::Enter(Container::ExampleBlock(_)) => self.output += "<pre class=\"example\">",
-            Event::Leave(Container::ExampleBlock(_)) => self.output += "</pre>",
+            Event::Enter(Container::CenterBlock(block)) => {
+                self.push_str("<div");
+                let class = format!("{}center", self.class_prefix);
+                self.attributes(element_attrs(block.syntax()), Some(class));
+                self.push_str(">");
+            }
+            Event::Leave(Container::CenterBlock(_)) => self.push_str("</div>"),
 
-            Event::Enter(Container::CenterBlock(_)) => self.output += "<div class=\"center\">",
-            Event::Leave(Container::CenterBlock(_)) => self.output += "</div>",
+            Event::Enter(Container::ExportBlock(block)) => {
+                match block.ty() {
+                    // html is passed through as-is
+                    Some(ty) if ty.eq_ignore_ascii_case("html") => self.push_str(block.value()),
+                    // org is the native backend, so its content is parsed and exported again
+                    Some(ty) if ty.eq_ignore_ascii_case("org") => {
+                        let org = self.parse_config.clone().parse(block.value());
+                        let document = org.document();
+                        // avoid nesting the zeroth section inside current section
+                        if let Some(section) = document.section() {
+                            for child in section.syntax().children_with_tokens() {
+                                self.element(child, ctx);
+                            }
+                        }
+                        for headline in document.headlines() {
+                            self.element(SyntaxElement::Node(headline.syntax().clone()), ctx);
+                        }
+                    }
+                    _ => {}
+                }
+                ctx.skip();
+            }
+            Event::Leave(Container::ExportBlock(_)) => {}
 
-            Event::Enter(Container::CommentBlock(_)) => self.output += "<!--",
-            Event::Leave(Container::CommentBlock(_)) => self.output += "-->",
+            Event::Enter(Container::CommentBlock(_)) => self.push_str("<!--"),
+            Event::Leave(Container::CommentBlock(_)) => self.push_str("-->"),
 
-            Event::Enter(Container::Comment(_)) => self.output += "<!--",
-            Event::Leave(Container::Comment(_)) => self.output += "-->",
+            Event::Enter(Container::Comment(_)) => self.push_str("<!--"),
+            Event::Leave(Container::Comment(_)) => self.push_str("-->"),
 
-            Event::Enter(Container::Subscript(_)) => self.output += "<sub>",
-            Event::Leave(Container::Subscript(_)) => self.output += "</sub>",
+            Event::Enter(Container::Subscript(_)) => self.push_str("<sub>"),
+            Event::Leave(Container::Subscript(_)) => self.push_str("</sub>"),
 
-            Event::Enter(Container::Superscript(_)) => self.output += "<sup>",
-            Event::Leave(Container::Superscript(_)) => self.output += "</sup>",
+            Event::Enter(Container::Superscript(_)) => self.push_str("<sup>"),
+            Event::Leave(Container::Superscript(_)) => self.push_str("</sup>"),
 
             Event::Enter(Container::List(list)) => {
-                self.output += if list.is_ordered() {
+                let tag = if list.is_ordered() {
                     self.in_descriptive_list.push(false);
-                    "<ol>"
+                    "<ol"
                 } else if list.is_descriptive() {
                     self.in_descriptive_list.push(true);
-                    "<dl>"
+                    "<dl"
                 } else {
                     self.in_descriptive_list.push(false);
-                    "<ul>"
+                    "<ul"
                 };
+                self.push_str(tag);
+                self.name_id(list.syntax());
+                self.push_str(">");
             }
             Event::Leave(Container::List(list)) => {
-                self.output += if list.is_ordered() {
+                let tag = if list.is_ordered() {
                     "</ol>"
                 } else if let Some(true) = self.in_descriptive_list.last() {
                     "</dl>"
                 } else {
                     "</ul>"
                 };
+                self.push_str(tag);
                 self.in_descriptive_list.pop();
             }
             Event::Enter(Container::ListItem(list_item)) => {
-                if let Some(&true) = self.in_descriptive_list.last() {
-                    self.output += "<dt>";
+                let descriptive = matches!(self.in_descriptive_list.last(), Some(&true));
+                let tag = if descriptive { "dt" } else { "li" };
+
+                if let Some(checkbox) = list_item.checkbox_state() {
+                    let prefix = &self.class_prefix;
+                    let (indeterminate, checked) = match checkbox {
+                        Checkbox::Unchecked => (String::new(), ""),
+                        Checkbox::Checked => (String::new(), " checked"),
+                        Checkbox::Indeterminate => (format!(" {prefix}indeterminate"), ""),
+                    };
+                    let _ = write!(
+                        &mut self.output,
+                        r#"<{tag} class="{prefix}checkbox{indeterminate}"><input type="checkbox"{checked} disabled> "#
+                    );
+                } else {
+                    let _ = write!(&mut self.output, "<{tag}>");
+                }
+
+                if descriptive {
                     for elem in list_item.tag() {
                         self.element(elem, ctx);
                     }
-                    self.output += "</dt><dd>";
-                } else {
-                    self.output += "<li>";
+                    self.push_str("</dt><dd>");
                 }
             }
             Event::Leave(Container::ListItem(_)) => {
                 if let Some(&true) = self.in_descriptive_list.last() {
-                    self.output += "</dd>";
+                    self.push_str("</dd>");
                 } else {
-                    self.output += "</li>";
+                    self.push_str("</li>");
                 }
             }
 
             Event::Enter(Container::OrgTable(table)) => {
-                self.output += "<table>";
+                self.push_str("<table");
+                self.attributes(element_attrs(table.syntax()), None);
+                self.push_str(">");
+                if let Some(caption) = table.caption().and_then(|c| c.value()) {
+                    self.push_str("<caption>");
+                    self.render_org_inline(caption.trim());
+                    self.push_str("</caption>");
+                }
+                // like org, columns without cookie are right-aligned if mostly numeric
+                self.table_alignments = table
+                    .column_cookies()
+                    .into_iter()
+                    .enumerate()
+                    .map(|(idx, cookie)| {
+                        cookie.unwrap_or(if table.column_is_numeric(idx) {
+                            TableAlignment::Right
+                        } else {
+                            TableAlignment::Left
+                        })
+                    })
+                    .collect();
                 self.table_row = if table.has_header() {
                     TableRow::HeaderRule
                 } else {
@@ -273,21 +1537,23 @@ impl Traverser for HtmlExport {
             }
             Event::Leave(Container::OrgTable(_)) => {
                 match self.table_row {
-                    TableRow::Body => self.output += "</tbody>",
-                    TableRow::Header => self.output += "</thead>",
+                    TableRow::Body => self.push_str("</tbody>"),
+                    TableRow::Header => self.push_str("</thead>"),
                     _ => {}
                 }
-                self.output += "</table>";
+                self.push_str("</table>");
             }
+            // alignment cookies are not exported
+            Event::Enter(Container::OrgTableRow(row)) if row.is_alignment() => ctx.skip(),
             Event::Enter(Container::OrgTableRow(row)) => {
                 if row.is_rule() {
                     match self.table_row {
                         TableRow::Body => {
-                            self.output += "</tbody>";
+                            self.push_str("</tbody>");
                             self.table_row = TableRow::BodyRule;
                         }
                         TableRow::Header => {
-                            self.output += "</thead>";
+                            self.push_str("</thead>");
                             self.table_row = TableRow::BodyRule;
                         }
                         _ => {}
@@ -297,90 +1563,157 @@ impl Traverser for HtmlExport {
                     match self.table_row {
                         TableRow::HeaderRule => {
                             self.table_row = TableRow::Header;
-                            self.output += "<thead>";
+                            self.push_str("<thead>");
                         }
                         TableRow::BodyRule => {
                             self.table_row = TableRow::Body;
-                            self.output += "<tbody>";
+                            self.push_str("<tbody>");
                         }
                         _ => {}
                     }
-                    self.output += "<tr>";
+                    self.push_str("<tr>");
+                    self.table_column = 0;
                 }
             }
             Event::Leave(Container::OrgTableRow(row)) => {
                 if row.is_rule() {
                     match self.table_row {
                         TableRow::Body => {
-                            self.output += "</tbody>";
+                            self.push_str("</tbody>");
                             self.table_row = TableRow::BodyRule;
                         }
                         TableRow::Header => {
-                            self.output += "</thead>";
+                            self.push_str("</thead>");
                             self.table_row = TableRow::BodyRule;
                         }
                         _ => {}
                     }
                     ctx.skip();
                 } else {
-                    self.output += "</tr>";
+                    self.pad_table_cells(self.table_alignments.len());
+                    self.push_str("</tr>");
                 }
             }
-            Event::Enter(Container::OrgTableCell(_)) => self.output += "<td>",
-            Event::Leave(Container::OrgTableCell(_)) => self.output += "</td>",
+            Event::Enter(Container::OrgTableCell(cell)) => {
+                let column = cell.column();
+                self.pad_table_cells(column);
+                self.table_cell_start(column);
+                self.table_column = column + 1;
+            }
+            Event::Leave(Container::OrgTableCell(_)) => self.push_str("</td>"),
 
             Event::Enter(Container::Link(link)) => {
-                let path = link.path();
-                let path = path.trim_start_matches("file:");
+                let expanded = expand_link_abbreviation(&link.path(), &self.link_abbreviations);
+                let path = expanded.clone().unwrap_or_else(|| link.destination());
 
                 if link.is_image() {
-                    let _ = write!(&mut self.output, r#"<img src="{}">"#, HtmlEscape(&path));
+                    let _ = write!(&mut self.output, r#"<img src="{}""#, HtmlEscape(&path));
+                    // attributes of an image come from its paragraph
+                    let attrs = link
+                        .syntax()
+                        .parent()
+                        .filter(|n| n.kind() == SyntaxKind::PARAGRAPH)
+                        .map(|paragraph| html_attrs(&paragraph))
+                        .unwrap_or_default();
+                    if let Some(alt) = link
+                        .image_alt()
+                        .filter(|_| attrs.iter().all(|(k, _)| k != "alt"))
+                    {
+                        let _ = write!(&mut self.output, r#" alt="{}""#, HtmlEscape(&alt));
+                    }
+                    self.attributes(attrs, None);
+                    self.push_str(">");
                     return ctx.skip();
                 }
 
-                let _ = write!(&mut self.output, r#"<a href="{}">"#, HtmlEscape(&path));
+                let internal = expanded.is_none()
+                    && (link.internal_kind().is_some() || link.protocol().as_deref() == Some("id"));
+
+                if !internal {
+                    let _ = write!(&mut self.output, r#"<a href="{}">"#, HtmlEscape(&path));
+                } else if let Some(node) = self.resolve_link(&link) {
+                    let href = self.internal_link_href(&link, node);
+                    let _ = write!(&mut self.output, r##"<a href="#{}">"##, HtmlEscape(&href));
+                } else {
+                    let _ = write!(
+                        &mut self.output,
+                        r#"<a class="{}broken">"#,
+                        self.class_prefix
+                    );
+                }
 
                 if !link.has_description() {
                     let _ = write!(&mut self.output, "{}</a>", HtmlEscape(&path));
                     ctx.skip();
                 }
             }
-            Event::Leave(Container::Link(_)) => self.output += "</a>",
+            Event::Leave(Container::Link(_)) => self.push_str("</a>"),
 
-            Event::Text(text) => {
-                let _ = write!(&mut self.output, "{}", HtmlEscape(text));
+            // targets are invisible anchors
+            Event::Enter(Container::Target(target)) => {
+                let id = target_anchor(target.syntax());
+                let _ = write!(&mut self.output, r#"<a id="{}"></a>"#, HtmlEscape(&id));
+                ctx.skip();
             }
 
+            Event::Enter(Container::RadioTarget(target)) => {
+                let _ = write!(
+                    &mut self.output,
+                    r#"<a id="radio-{}"></a>"#,
+                    HtmlEscape(slugify(&target.text_raw()))
+                );
+            }
+
+            Event::Text(text) => self.text(&text),
+
             Event::FnLabel(_) => {}
 
-            Event::LineBreak(_) => self.output += "<br/>",
+            Event::LineBreak(_) => self.push_str("<br/>"),
 
-            Event::Snippet(snippet) => {
-                if snippet.backend().eq_ignore_ascii_case("html") {
-                    self.output += &snippet.value();
-                }
+            Event::Snippet(snippet) if snippet.backend().eq_ignore_ascii_case("html") => {
+                self.push_str(snippet.value());
             }
 
-            Event::Rule(_) => self.output += "<hr/>",
+            Event::Rule(_) => self.push_str("<hr/>"),
 
             Event::Timestamp(timestamp) => {
-                self.output += r#"<span class="timestamp-wrapper"><span class="timestamp">"#;
+                let _ = write!(
+                    &mut self.output,
+                    r#"<span class="{prefix}timestamp-wrapper"><span class="{prefix}timestamp">"#,
+                    prefix = self.class_prefix
+                );
                 for e in timestamp.syntax.children_with_tokens() {
                     match e {
                         NodeOrToken::Token(t) if t.kind() == SyntaxKind::MINUS2 => {
-                            self.output += "&#x2013;";
+                            self.push_str("&#x2013;");
                         }
                         NodeOrToken::Token(t) => {
-                            self.output += t.text();
+                            self.push_str(t.text());
                         }
                         _ => {}
                     }
                 }
-                self.output += r#"</span></span>"#;
+                self.push_str(r#"</span></span>"#);
             }
 
-            Event::LatexFragment(latex) => {
-                let _ = write!(&mut self.output, "{}", &latex.syntax);
+            Event::LatexFragment(latex) => match (self.math_delimiters, math_fragment(&latex)) {
+                (MathDelimiters::MathJax, Some((false, math))) => {
+                    let _ = write!(&mut self.output, r"\({math}\)");
+                }
+                (MathDelimiters::KaTeX, Some((false, math))) => {
+                    let _ = write!(&mut self.output, "${math}$");
+                }
+                (MathDelimiters::KaTeX, Some((true, math))) => {
+                    let _ = write!(&mut self.output, "$${math}$$");
+                }
+                _ => {
+                    let _ = write!(&mut self.output, "{}", &latex.syntax);
+                }
+            },
+            Event::LatexEnvironment(latex) if self.math_delimiters == MathDelimiters::KaTeX => {
+                let raw = latex.syntax.to_string();
+                let math = raw.trim_end();
+                let _ = write!(&mut self.output, "$${math}$${}", &raw[math.len()..]);
             }
             Event::LatexEnvironment(latex) => {
                 let _ = write!(&mut self.output, "{}", &latex.syntax);
@@ -389,9 +1722,247 @@ impl Traverser for HtmlExport {
             // ignores keyword
             Event::Enter(Container::Keyword(_)) => ctx.skip(),
 
-            Event::Entity(entity) => self.output += entity.html(),
+            Event::Macros(macros) => {
+                let root = macros.syntax.ancestors().last();
+                let root = root.as_ref().unwrap_or(&macros.syntax);
+                let defs = self
+                    .macros
+                    .entry(root.clone())
+                    .or_insert_with(|| collect_macros(root));
+                // expansion is org text, so markup and export snippets in it are rendered
+                if let Some(expanded) = macros.expand(defs) {
+                    self.render_org_inline(&expanded);
+                }
+            }
+
+            Event::Cookie(cookie) => {
+                let _ = write!(&mut self.output, "{}", HtmlEscape(cookie.raw()));
+            }
+
+            Event::Citation(citation) => {
+                let _ = write!(
+                    &mut self.output,
+                    r#"<span class="{}citation">{}</span>"#,
+                    self.class_prefix,
+                    HtmlEscape(citation.body())
+                );
+            }
+
+            Event::Entity(entity) => match self.entity_representation {
+                EntityRepresentation::Html => {
+                    let html =
+                        entity.representation_with(EntityRepresentation::Html, &self.user_entities);
+                    let _ = self.output.write_str(html);
+                }
+                // other representations are plain text, so they need to be escaped
+                repr => {
+                    let text = entity.representation_with(repr, &self.user_entities);
+                    let _ = write!(&mut self.output, "{}", HtmlEscape(text));
+                }
+            },
 
             _ => {}
         }
     }
 }
+
+/// Returns anchor id of given `<<target>>`
+fn target_anchor(target: &SyntaxNode) -> String {
+    let text = target
+        .children_with_tokens()
+        .find(|e| e.kind() == SyntaxKind::TEXT)
+        .map(|e| e.to_string())
+        .unwrap_or_default();
+    format!("target-{}", slugify(&text))
+}
+
+/// Returns `(is_display, content)` of math fragment delimited by `$`, `$$`, `\(` or `\[`
+fn math_fragment(latex: &LatexFragment) -> Option<(bool, String)> {
+    let raw = latex.syntax.to_string();
+    let display = match latex.syntax.first_token()?.kind() {
+        SyntaxKind::DOLLAR => false,
+        SyntaxKind::DOLLAR2 => true,
+        SyntaxKind::BACKSLASH if raw.starts_with(r"\(") => false,
+        SyntaxKind::BACKSLASH if raw.starts_with(r"\[") => true,
+        _ => return None,
+    };
+    let delimiter = if raw.starts_with('$') && !display {
+        1
+    } else {
+        2
+    };
+    let content = raw.get(delimiter..raw.len().checked_sub(delimiter)?)?;
+    Some((display, content.to_string()))
+}
+
+/// Returns value of `datetime` attribute for given timestamp start, e.g. `2019-04-09T10:00`
+fn timestamp_datetime(timestamp: &Timestamp) -> Option<String> {
+    let mut datetime = format!(
+        "{}-{}-{}",
+        timestamp.year_start()?,
+        timestamp.month_start()?,
+        timestamp.day_start()?
+    );
+    if let (Some(hour), Some(minute)) = (timestamp.hour_start(), timestamp.minute_start()) {
+        let _ = write!(&mut datetime, "T{hour}:{minute}");
+    }
+    Some(datetime)
+}
+
+/// Returns attributes from `#+ATTR_HTML` keywords of given element,
+/// keywords of other backends are ignored
+fn html_attrs(element: &SyntaxNode) -> Vec<(String, String)> {
+    element
+        .children()
+        .take_while(|n| n.kind() == SyntaxKind::AFFILIATED_KEYWORD)
+        .filter_map(AffiliatedKeyword::cast)
+        .filter(|keyword| keyword.backend().as_deref() == Some("html"))
+        .flat_map(|keyword| keyword.attrs())
+        .collect()
+}
+
+/// Returns `true` if given text is a valid html attribute name
+///
+/// Names containing whitespace, quotes, `/`, `<`, `>`, `=` or control
+/// characters would break out of the tag, see
+/// <https://html.spec.whatwg.org/multipage/syntax.html#attributes-2>.
+fn is_attribute_name(name: &str) -> bool {
+    !name.is_empty()
+        && !name.chars().any(|c| {
+            c.is_whitespace() || c.is_control() || matches!(c, '"' | '\'' | '/' | '<' | '>' | '=')
+        })
+}
+
+/// Returns `#+ATTR_HTML` attributes of element, plus an `id` from its
+/// `#+NAME` keyword unless one is given explicitly
+fn element_attrs(element: &SyntaxNode) -> Vec<(String, String)> {
+    let mut attrs = html_attrs(element);
+    if attrs.iter().all(|(key, _)| key != "id") {
+        if let Some(name) = element_name(element) {
+            attrs.insert(0, ("id".to_string(), name));
+        }
+    }
+    attrs
+}
+
+/// Returns trimmed value of `#+NAME` keyword of element, if any
+fn element_name(element: &SyntaxNode) -> Option<String> {
+    element
+        .children()
+        .take_while(|n| n.kind() == SyntaxKind::AFFILIATED_KEYWORD)
+        .filter_map(AffiliatedKeyword::cast)
+        .find(|keyword| keyword.key().eq_ignore_ascii_case("NAME"))
+        .and_then(|keyword| keyword.value())
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+}
+
+/// Returns `true` if given text is rendered literally, e.g. inside code or
+/// example blocks
+fn is_verbatim(text: &Token) -> bool {
+    text.syntax().parent_ancestors().any(|n| {
+        matches!(
+            n.kind(),
+            SyntaxKind::VERBATIM
+                | SyntaxKind::CODE
+                | SyntaxKind::INLINE_SRC
+                | SyntaxKind::EXAMPLE_BLOCK
+                | SyntaxKind::SOURCE_BLOCK
+                | SyntaxKind::EXPORT_BLOCK
+                | SyntaxKind::COMMENT_BLOCK
+                | SyntaxKind::COMMENT
+                | SyntaxKind::FIXED_WIDTH
+        )
+    })
+}
+
+/// Replaces quotes, dashes and ellipses with their typographic counterparts
+///
+/// `before` is the character preceding given text, if any.
+fn smart_quotes(text: &str, before: Option<char>) -> Cow<'_, str> {
+    if !text.contains(['"', '\'', '-', '.']) {
+        return Cow::Borrowed(text);
+    }
+
+    let mut output = String::with_capacity(text.len());
+    let mut prev = before;
+    let mut rest = text;
+    while let Some(ch) = rest.chars().next() {
+        let (replacement, len) = if rest.starts_with("---") {
+            ('\u{2014}', 3)
+        } else if rest.starts_with("--") {
+            ('\u{2013}', 2)
+        } else if rest.starts_with("...") {
+            ('\u{2026}', 3)
+        } else {
+            let opening = prev.is_none_or(|c| {
+                c.is_whitespace() || matches!(c, '(' | '[' | '{' | '\u{201c}' | '\u{2018}')
+            });
+            match ch {
+                '"' if opening => ('\u{201c}', 1),
+                '"' => ('\u{201d}', 1),
+                '\'' if opening => ('\u{2018}', 1),
+                '\'' => ('\u{2019}', 1),
+                _ => (ch, ch.len_utf8()),
+            }
+        };
+        output.push(replacement);
+        prev = Some(replacement);
+        rest = &rest[len..];
+    }
+    Cow::Owned(output)
+}
+
+/// Returns the character right before given token in the document
+fn char_before(token: &SyntaxToken) -> Option<char> {
+    std::iter::successors(token.prev_token(), |t| t.prev_token())
+        .find_map(|t| t.text().chars().next_back())
+}
+
+/// Returns range of the content of the verse block containing given text
+fn verse_content(text: &Token) -> Option<TextRange> {
+    let content = text.syntax().parent()?;
+    (content.kind() == SyntaxKind::BLOCK_CONTENT
+        && content.parent()?.kind() == SyntaxKind::VERSE_BLOCK)
+        .then(|| content.text_range())
+}
+
+/// Returns end of the content of the paragraph containing given text, blank
+/// lines excluded, `None` if text isn't in a paragraph or is in a verse block
+fn paragraph_content_end(text: &Token) -> Option<TextSize> {
+    let mut ancestors = text.syntax().parent_ancestors();
+    let paragraph = ancestors.find(|n| n.kind() == SyntaxKind::PARAGRAPH)?;
+    if ancestors.any(|n| n.kind() == SyntaxKind::VERSE_BLOCK) {
+        return None;
+    }
+    paragraph
+        .children_with_tokens()
+        .filter(|e| e.kind() != SyntaxKind::BLANK_LINE)
+        .last()
+        .map(|e| e.text_range().end())
+}
+
+/// Returns the image link if it's the only object in given paragraph
+fn standalone_image(paragraph: &SyntaxNode) -> Option<Link> {
+    let mut objects = paragraph.children_with_tokens().filter(|e| match e {
+        NodeOrToken::Node(n) => n.kind() != SyntaxKind::AFFILIATED_KEYWORD,
+        NodeOrToken::Token(t) => !t.text().trim().is_empty(),
+    });
+
+    let link = objects.next()?.into_node().and_then(Link::cast)?;
+
+    (objects.next().is_none() && link.is_image()).then_some(link)
+}
+
+/// Finds footnote definition with given label in the same document
+fn find_fn_def(node: &SyntaxNode, label: &str) -> Option<SyntaxNode> {
+    if label.is_empty() {
+        return None;
+    }
+    node.ancestors()
+        .last()?
+        .descendants()
+        .filter_map(FnDef::cast)
+        .find(|def| def.label().is_some_and(|l| l == label))
+        .map(|def| def.syntax)
+}
@@ -0,0 +1,364 @@
+use rowan::ast::AstNode;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::ast::{filter_token, Headline, TimeUnit, Timestamp, TodoType};
+use crate::date::{civil_from_days, days_from_civil};
+use crate::SyntaxKind;
+
+use super::event::{Container, Event};
+use super::PlainTextExport;
+use super::TraversalContext;
+use super::Traverser;
+
+/// iCalendar export, similar to `org-icalendar-export-to-ics`
+///
+/// Each headline with a `SCHEDULED`, `DEADLINE` or an active timestamp in its
+/// section becomes one component:
+///
+/// - headlines with a todo keyword become `VTODO`, with `DTSTART` from the
+///   scheduled (or first active) timestamp and `DUE` from the deadline, or
+///   from the end of a range start timestamp
+/// - other headlines become `VEVENT`, using the scheduled, first active or
+///   deadline timestamp, in that order
+///
+/// `DUE` always has the same value type as `DTSTART`, so a date-only deadline
+/// of a timed todo becomes the midnight ending that day, while a date-only
+/// `DUE` is the last day itself, whether it comes from a deadline or from the
+/// end of a range. Every component is stamped with `DTSTAMP`, which is the
+/// current time unless set by [`IcalExport::with_dtstamp`].
+///
+/// Title is written as `SUMMARY`, tags as `CATEGORIES`, and repeater of the
+/// start timestamp as `RRULE`. Date-only timestamps are written with
+/// `VALUE=DATE`, timed ones as floating local time. Subtrees of commented
+/// headlines and diary timestamps are ignored.
+///
+/// ```rust
+/// use orgize::{Org, export::IcalExport};
+///
+/// let mut ical = IcalExport::default().with_dtstamp("20240101T000000Z");
+/// Org::parse("* TODO Report :work:\nDEADLINE: <2024-01-05 Fri>\n* Standup\n<2024-01-02 Tue 10:00-10:15 +1d>")
+///     .traverse(&mut ical);
+/// assert_eq!(
+///     ical.finish().replace("\r\n", "\n"),
+///     r#"BEGIN:VCALENDAR
+/// VERSION:2.0
+/// PRODID:-//orgize//orgize//EN
+/// CALSCALE:GREGORIAN
+/// BEGIN:VTODO
+/// UID:orgize-0
+/// DTSTAMP:20240101T000000Z
+/// SUMMARY:Report
+/// CATEGORIES:work
+/// DUE;VALUE=DATE:20240105
+/// STATUS:NEEDS-ACTION
+/// END:VTODO
+/// BEGIN:VEVENT
+/// UID:orgize-48
+/// DTSTAMP:20240101T000000Z
+/// SUMMARY:Standup
+/// DTSTART:20240102T100000
+/// DTEND:20240102T101500
+/// RRULE:FREQ=DAILY;INTERVAL=1
+/// END:VEVENT
+/// END:VCALENDAR
+/// "#
+/// );
+/// ```
+pub struct IcalExport {
+    output: String,
+    /// UTC date-time written as `DTSTAMP`, e.g. `20240101T000000Z`
+    dtstamp: String,
+}
+
+impl Default for IcalExport {
+    fn default() -> Self {
+        let seconds = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_secs())
+            .unwrap_or_default();
+        let (year, month, day) = civil_from_days((seconds / 86400) as i64);
+        let seconds = seconds % 86400;
+        IcalExport {
+            output: String::new(),
+            dtstamp: format!(
+                "{year:04}{month:02}{day:02}T{:02}{:02}{:02}Z",
+                seconds / 3600,
+                seconds / 60 % 60,
+                seconds % 60
+            ),
+        }
+    }
+}
+
+impl IcalExport {
+    /// Sets UTC date-time written as `DTSTAMP` of every component, defaults
+    /// to the current time
+    ///
+    /// Useful for reproducible output, or on targets without a system clock.
+    ///
+    /// ```rust
+    /// use orgize::{Org, export::IcalExport};
+    ///
+    /// let mut ical = IcalExport::default().with_dtstamp("20240101T120000Z");
+    /// Org::parse("* Meeting\n<2024-01-02 Tue>").traverse(&mut ical);
+    /// assert!(ical.finish().contains("\r\nDTSTAMP:20240101T120000Z\r\n"));
+    /// ```
+    pub fn with_dtstamp(mut self, dtstamp: impl Into<String>) -> Self {
+        self.dtstamp = dtstamp.into();
+        self
+    }
+
+    pub fn finish(self) -> String {
+        self.output
+    }
+
+    /// Writes a content line, folding it if longer than 75 octets
+    fn line(&mut self, line: &str) {
+        let mut width = 0;
+        for ch in line.chars() {
+            if width + ch.len_utf8() > 75 {
+                self.output += "\r\n ";
+                width = 1;
+            }
+            self.output.push(ch);
+            width += ch.len_utf8();
+        }
+        self.output += "\r\n";
+    }
+
+    fn headline(&mut self, headline: &Headline) {
+        let scheduled = headline.scheduled();
+        let deadline = headline.deadline();
+        let active = headline.section().and_then(|section| {
+            section
+                .syntax()
+                .descendants()
+                .filter_map(Timestamp::cast)
+                .find(|ts| ts.is_active() && !ts.is_diary())
+        });
+
+        let is_todo = headline.todo_keyword().is_some();
+        let (start, due) = if is_todo {
+            (scheduled.or(active), deadline)
+        } else {
+            (scheduled.or(active).or(deadline), None)
+        };
+        let start = start.and_then(|ts| Some((Moment::start(&ts)?, ts)));
+        let end = start
+            .as_ref()
+            .and_then(|(start, ts)| Moment::end(ts, start));
+        let due = match (due.as_ref().and_then(Moment::start), &start) {
+            // todos have no end, so end of range becomes due instead, inclusive
+            // like a deadline
+            (None, Some((start, ts))) if is_todo => {
+                Moment::last(ts).map(|last| last.with_value_type_of(start))
+            }
+            (Some(due), Some((start, _))) => Some(due.with_value_type_of(start)),
+            (due, _) => due,
+        };
+        if start.is_none() && due.is_none() {
+            return;
+        }
+
+        let component = if is_todo { "VTODO" } else { "VEVENT" };
+        self.line(&format!("BEGIN:{component}"));
+
+        let uid = headline
+            .property("ID")
+            .map(|id| id.to_string())
+            .unwrap_or_else(|| format!("orgize-{}", u32::from(headline.start())));
+        self.line(&format!("UID:{}", escape(&uid)));
+        self.line(&format!("DTSTAMP:{}", self.dtstamp));
+
+        let mut plain = PlainTextExport::default();
+        let mut ctx = TraversalContext::default();
+        for elem in headline.title() {
+            plain.element(elem, &mut ctx);
+        }
+        self.line(&format!("SUMMARY:{}", escape(plain.finish().trim())));
+
+        let tags: Vec<_> = headline.tags().map(|tag| escape(&tag)).collect();
+        if !tags.is_empty() {
+            self.line(&format!("CATEGORIES:{}", tags.join(",")));
+        }
+
+        if let Some((start, ts)) = &start {
+            self.line(&format!("DTSTART{}", start.value()));
+            if let Some(end) = end.filter(|_| !is_todo) {
+                self.line(&format!("DTEND{}", end.value()));
+            }
+            if let Some(repeater) = ts.repeater().filter(|r| r.value > 0) {
+                let freq = match repeater.unit {
+                    TimeUnit::Hour => "HOURLY",
+                    TimeUnit::Day => "DAILY",
+                    TimeUnit::Week => "WEEKLY",
+                    TimeUnit::Month => "MONTHLY",
+                    TimeUnit::Year => "YEARLY",
+                };
+                self.line(&format!("RRULE:FREQ={freq};INTERVAL={}", repeater.value));
+            }
+        }
+
+        if let Some(due) = &due {
+            self.line(&format!("DUE{}", due.value()));
+        }
+
+        if is_todo {
+            let status = if headline.todo_type() == Some(TodoType::Done) {
+                "COMPLETED"
+            } else {
+                "NEEDS-ACTION"
+            };
+            self.line(&format!("STATUS:{status}"));
+        }
+
+        self.line(&format!("END:{component}"));
+    }
+}
+
+impl Traverser for IcalExport {
+    fn event(&mut self, event: Event, ctx: &mut TraversalContext) {
+        match event {
+            Event::Enter(Container::Document(_)) => {
+                self.line("BEGIN:VCALENDAR");
+                self.line("VERSION:2.0");
+                self.line("PRODID:-//orgize//orgize//EN");
+                self.line("CALSCALE:GREGORIAN");
+            }
+            Event::Leave(Container::Document(_)) => self.line("END:VCALENDAR"),
+
+            Event::Enter(Container::Headline(headline)) if headline.is_commented() => ctx.skip(),
+            Event::Enter(Container::Headline(headline)) => self.headline(&headline),
+
+            // only headlines are exported, so there's no need to walk into sections
+            Event::Enter(Container::Section(_)) => ctx.skip(),
+
+            _ => {}
+        }
+    }
+}
+
+/// Date and optional time of a timestamp
+#[derive(Clone, Copy)]
+struct Moment {
+    date: (u32, u32, u32),
+    time: Option<(u32, u32)>,
+}
+
+impl Moment {
+    fn start(ts: &Timestamp) -> Option<Moment> {
+        let second_date = ts.second_date_offset();
+        let time = times(ts)
+            .next()
+            .filter(|(offset, _)| second_date.is_none_or(|date| *offset < date))
+            .map(|(_, time)| time);
+        Some(Moment {
+            date: (
+                ts.year_start()?.parse().ok()?,
+                ts.month_start()?.parse().ok()?,
+                ts.day_start()?.parse().ok()?,
+            ),
+            time,
+        })
+    }
+
+    /// Returns inclusive end of a range timestamp, as written
+    fn last(ts: &Timestamp) -> Option<Moment> {
+        if !ts.is_range() {
+            return None;
+        }
+        let second_date = ts.second_date_offset();
+        let time = times(ts)
+            .last()
+            .filter(|(offset, _)| second_date.is_none_or(|date| *offset > date))
+            .map(|(_, time)| time);
+        Some(Moment {
+            date: (
+                ts.year_end()?.parse().ok()?,
+                ts.month_end()?.parse().ok()?,
+                ts.day_end()?.parse().ok()?,
+            ),
+            time,
+        })
+    }
+
+    /// Returns exclusive end of a range timestamp, in the same form as its start
+    fn end(ts: &Timestamp, start: &Moment) -> Option<Moment> {
+        let Moment { date, time } = Moment::last(ts)?;
+        Some(match (start.time, time) {
+            (Some(_), Some(_)) => Moment { date, time },
+            // end date of an all-day event is exclusive
+            (Some(_), None) => Moment {
+                date: next_day(date),
+                time: Some((0, 0)),
+            },
+            (None, _) => Moment {
+                date: next_day(date),
+                time: None,
+            },
+        })
+    }
+
+    /// Converts to a date-time if `other` has time, or to a date otherwise
+    ///
+    /// A date becomes the midnight ending that day, as dates are inclusive.
+    fn with_value_type_of(self, other: &Moment) -> Moment {
+        match (self.time, other.time) {
+            (None, Some(_)) => Moment {
+                date: next_day(self.date),
+                time: Some((0, 0)),
+            },
+            (Some(_), None) => Moment {
+                date: self.date,
+                time: None,
+            },
+            _ => self,
+        }
+    }
+
+    /// Formats as property parameters and value, e.g. `;VALUE=DATE:20240102`
+    fn value(&self) -> String {
+        let (year, month, day) = self.date;
+        match self.time {
+            Some((hour, minute)) => format!(":{year:04}{month:02}{day:02}T{hour:02}{minute:02}00"),
+            None => format!(";VALUE=DATE:{year:04}{month:02}{day:02}"),
+        }
+    }
+}
+
+/// Returns an iterator of offsets and hour-minute pairs
+fn times(ts: &Timestamp) -> impl Iterator<Item = (rowan::TextSize, (u32, u32))> {
+    let hours = ts
+        .syntax()
+        .children_with_tokens()
+        .filter_map(filter_token(SyntaxKind::TIMESTAMP_HOUR));
+    let minutes = ts
+        .syntax()
+        .children_with_tokens()
+        .filter_map(filter_token(SyntaxKind::TIMESTAMP_MINUTE));
+    hours.zip(minutes).filter_map(|(hour, minute)| {
+        Some((hour.start(), (hour.parse().ok()?, minute.parse().ok()?)))
+    })
+}
+
+fn next_day((year, month, day): (u32, u32, u32)) -> (u32, u32, u32) {
+    let (year, month, day) = civil_from_days(days_from_civil(year.into(), month, day) + 1);
+    (year as u32, month, day)
+}
+
+/// Escapes text value, see RFC 5545 section 3.3.11
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for ch in text.chars() {
+        match ch {
+            '\\' | ';' | ',' => {
+                escaped.push('\\');
+                escaped.push(ch);
+            }
+            '\n' => escaped.push_str("\\n"),
+            '\r' => {}
+            _ => escaped.push(ch),
+        }
+    }
+    escaped
+}
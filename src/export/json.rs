@@ -0,0 +1,103 @@
+use serde::Serialize;
+
+use super::event::Event;
+use super::jsonl::Record;
+use super::TraversalContext;
+use super::Traverser;
+use crate::Org;
+
+/// A node in serialized tree
+#[derive(Serialize)]
+struct Node {
+    #[serde(flatten)]
+    record: Record,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    children: Vec<Node>,
+}
+
+/// JSON serialization of syntax tree
+///
+/// Each element is serialized as an object containing its `type`, syntax `kind`,
+/// text `range` in byte offsets, typed attributes (e.g. headline level and tags,
+/// link path and description, timestamp dates) and `children`.
+///
+/// ```rust
+/// use orgize::{Org, export::JsonExport};
+///
+/// let mut json = JsonExport::default();
+/// Org::parse("* hello :a:\n[[https://example.com][site]]").traverse(&mut json);
+/// assert_eq!(
+///     json.finish(),
+///     concat!(
+///         r#"{"type":"node","kind":"DOCUMENT","range":[0,41],"children":["#,
+///         r#"{"type":"node","kind":"HEADLINE","range":[0,41],"level":1,"title":"hello","tags":["a"],"children":["#,
+///         r#"{"type":"node","kind":"SECTION","range":[12,41],"children":["#,
+///         r#"{"type":"node","kind":"PARAGRAPH","range":[12,41],"children":["#,
+///         r#"{"type":"node","kind":"LINK","range":[12,41],"path":"https://example.com","description":"site","children":["#,
+///         r#"{"type":"text","kind":"TEXT","range":[35,39],"value":"site"}"#,
+///         r#"]}]}]}]}]}"#
+///     )
+/// );
+/// ```
+#[derive(Default)]
+pub struct JsonExport {
+    /// nodes being entered, from root to current one
+    stack: Vec<Node>,
+    root: Option<Node>,
+}
+
+impl JsonExport {
+    /// Returns serialized tree, or `null` if nothing was traversed
+    pub fn finish(self) -> String {
+        serde_json::to_string(&self.root).unwrap_or_default()
+    }
+}
+
+impl Traverser for JsonExport {
+    fn event(&mut self, event: Event, _: &mut TraversalContext) {
+        match event {
+            Event::Enter(container) => self.stack.push(Node {
+                record: Record::container("node", &container),
+                children: vec![],
+            }),
+            Event::Leave(_) => {
+                let Some(node) = self.stack.pop() else {
+                    return;
+                };
+                match self.stack.last_mut() {
+                    Some(parent) => parent.children.push(node),
+                    None => self.root = Some(node),
+                }
+            }
+            event => {
+                let Some(record) = Record::leaf(event) else {
+                    return;
+                };
+                let node = Node {
+                    record,
+                    children: vec![],
+                };
+                match self.stack.last_mut() {
+                    Some(parent) => parent.children.push(node),
+                    None => self.root = Some(node),
+                }
+            }
+        }
+    }
+}
+
+impl Org {
+    /// Serializes syntax tree to JSON, see [JsonExport]
+    ///
+    /// ```rust
+    /// use orgize::Org;
+    ///
+    /// let json = Org::parse("<2019-04-08 Mon 10:00>--<2019-04-09 Tue>").to_json();
+    /// assert!(json.contains(r#""active":true,"start":"2019-04-08 10:00","end":"2019-04-09""#));
+    /// ```
+    pub fn to_json(&self) -> String {
+        let mut handler = JsonExport::default();
+        self.traverse(&mut handler);
+        handler.finish()
+    }
+}
@@ -0,0 +1,267 @@
+use rowan::TextRange;
+use serde::Serialize;
+
+use super::event::{Container, Event};
+use super::TraversalContext;
+use super::Traverser;
+use crate::ast::Timestamp;
+use crate::{Org, SyntaxKind};
+
+/// A single line in event stream
+#[derive(Serialize, Default)]
+pub(super) struct Record {
+    #[serde(rename = "type")]
+    ty: &'static str,
+    kind: String,
+    range: [u32; 2],
+    #[serde(skip_serializing_if = "Option::is_none")]
+    level: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    title: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    todo: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    path: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    language: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    backend: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    key: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    value: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    raw: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    tags: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    active: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    start: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    end: Option<String>,
+}
+
+impl Record {
+    fn new(ty: &'static str, kind: SyntaxKind, range: TextRange) -> Self {
+        Record {
+            ty,
+            kind: format!("{kind:?}"),
+            range: [range.start().into(), range.end().into()],
+            ..Default::default()
+        }
+    }
+}
+
+/// JSON Lines serialization of traversal events
+///
+/// Each `Enter`, `Leave` and leaf event is written as one JSON object per line,
+/// containing its `type`, syntax `kind`, text `range` and some event-specific payload.
+///
+/// ```rust
+/// use orgize::{Org, export::JsonlExport};
+///
+/// let mut jsonl = JsonlExport::default();
+/// Org::parse("* hello\nworld").traverse(&mut jsonl);
+/// assert_eq!(jsonl.finish(), r#"{"type":"enter","kind":"DOCUMENT","range":[0,13]}
+/// {"type":"enter","kind":"HEADLINE","range":[0,13],"level":1,"title":"hello"}
+/// {"type":"enter","kind":"SECTION","range":[8,13]}
+/// {"type":"enter","kind":"PARAGRAPH","range":[8,13]}
+/// {"type":"text","kind":"TEXT","range":[8,13],"value":"world"}
+/// {"type":"leave","kind":"PARAGRAPH","range":[8,13]}
+/// {"type":"leave","kind":"SECTION","range":[8,13]}
+/// {"type":"leave","kind":"HEADLINE","range":[0,13]}
+/// {"type":"leave","kind":"DOCUMENT","range":[0,13]}
+/// "#);
+/// ```
+#[derive(Default)]
+pub struct JsonlExport {
+    output: String,
+}
+
+impl JsonlExport {
+    pub fn finish(self) -> String {
+        self.output
+    }
+
+    fn write(&mut self, record: Record) {
+        if let Ok(line) = serde_json::to_string(&record) {
+            self.output += &line;
+            self.output += "\n";
+        }
+    }
+}
+
+impl Traverser for JsonlExport {
+    fn event(&mut self, event: Event, _: &mut TraversalContext) {
+        let record = match event {
+            Event::Enter(container) => Record::container("enter", &container),
+            Event::Leave(container) => {
+                let syntax = container.syntax();
+                Record::new("leave", syntax.kind(), syntax.text_range())
+            }
+            event => match Record::leaf(event) {
+                Some(record) => record,
+                None => return,
+            },
+        };
+
+        self.write(record);
+    }
+}
+
+impl Record {
+    /// Creates record of given container, including its typed attributes
+    pub(super) fn container(ty: &'static str, container: &Container) -> Self {
+        let syntax = container.syntax();
+        let mut record = Record::new(ty, syntax.kind(), syntax.text_range());
+        match container {
+            Container::Headline(headline) => {
+                record.level = Some(headline.level());
+                record.title = Some(headline.title_raw().trim().to_string());
+                record.todo = headline.todo_keyword().map(|t| t.to_string());
+                record.tags = headline.tags().map(|t| t.to_string()).collect();
+            }
+            Container::InlineTask(task) => {
+                record.level = Some(task.level());
+                record.title = Some(task.title_raw().trim().to_string());
+                record.todo = task.todo_keyword().map(|t| t.to_string());
+                record.tags = task.tags().map(|t| t.to_string()).collect();
+            }
+            Container::Link(link) => {
+                record.path = Some(link.path().to_string());
+                if link.has_description() {
+                    record.description = Some(link.description_raw());
+                }
+            }
+            Container::SourceBlock(block) => {
+                record.language = block.language().map(|t| t.to_string());
+            }
+            Container::ExportBlock(block) => {
+                record.backend = block.ty().map(|t| t.to_string());
+            }
+            Container::Keyword(keyword) => {
+                record.key = Some(keyword.key().to_string());
+                record.value = Some(keyword.value_trimmed());
+            }
+            Container::Drawer(drawer) => record.name = Some(drawer.name().to_string()),
+            _ => {}
+        }
+        record
+    }
+
+    /// Creates record of given leaf or text event, returns `None` for `Enter` and `Leave`
+    pub(super) fn leaf(event: Event) -> Option<Self> {
+        let record = match event {
+            Event::Enter(_) | Event::Leave(_) => return None,
+            Event::Text(text) | Event::FnLabel(text) => {
+                let mut record = Record::new("text", text.syntax().kind(), text.text_range());
+                record.value = Some(text.to_string());
+                record
+            }
+            Event::Snippet(snippet) => {
+                let mut record = Record::new("leaf", SyntaxKind::SNIPPET, snippet.text_range());
+                record.backend = Some(snippet.backend().to_string());
+                record.value = Some(snippet.value().to_string());
+                record
+            }
+            Event::Entity(entity) => {
+                let mut record = Record::new("leaf", SyntaxKind::ENTITY, entity.text_range());
+                record.name = Some(entity.name().to_string());
+                record
+            }
+            Event::Macros(macros) => {
+                let mut record = Record::new("leaf", SyntaxKind::MACROS, macros.text_range());
+                record.key = Some(macros.key().to_string());
+                record.value = macros.args().map(|t| t.to_string());
+                record
+            }
+            Event::InlineSrc(src) => {
+                let mut record = Record::new("leaf", SyntaxKind::INLINE_SRC, src.text_range());
+                record.language = Some(src.language().to_string());
+                record.value = Some(src.value().to_string());
+                record
+            }
+            Event::Timestamp(timestamp) => {
+                let mut record =
+                    Record::new("leaf", timestamp.syntax.kind(), timestamp.text_range());
+                record.raw = Some(timestamp.raw());
+                if !timestamp.is_diary() {
+                    record.active = Some(timestamp.is_active());
+                    record.start = timestamp_start(&timestamp);
+                    record.end = timestamp_end(&timestamp);
+                }
+                record
+            }
+            Event::Cookie(x) => leaf(&x.syntax),
+            Event::InlineCall(x) => leaf(&x.syntax),
+            Event::Clock(x) => leaf(&x.syntax),
+            Event::LineBreak(x) => leaf(&x.syntax),
+            Event::Rule(x) => leaf(&x.syntax),
+            Event::LatexFragment(x) => leaf(&x.syntax),
+            Event::LatexEnvironment(x) => leaf(&x.syntax),
+            Event::Citation(x) => leaf(&x.syntax),
+            #[cfg(feature = "syntax-org-fc")]
+            Event::Cloze(x) => leaf(&x.syntax),
+        };
+        Some(record)
+    }
+}
+
+/// Formats timestamp start as `YYYY-MM-DD` or `YYYY-MM-DD HH:MM`
+fn timestamp_start(timestamp: &Timestamp) -> Option<String> {
+    let mut start = format!(
+        "{}-{}-{}",
+        timestamp.year_start()?,
+        timestamp.month_start()?,
+        timestamp.day_start()?
+    );
+    let second_date = timestamp.second_date_offset();
+    if let (Some(hour), Some(minute)) = (timestamp.hour_start(), timestamp.minute_start()) {
+        // the first time may belong to the second date
+        if second_date.is_none_or(|offset| hour.start() < offset) {
+            start += &format!(" {hour}:{minute}");
+        }
+    }
+    Some(start)
+}
+
+/// Formats timestamp end, only if it's a range
+fn timestamp_end(timestamp: &Timestamp) -> Option<String> {
+    if !timestamp.is_range() {
+        return None;
+    }
+    let mut end = format!(
+        "{}-{}-{}",
+        timestamp.year_end()?,
+        timestamp.month_end()?,
+        timestamp.day_end()?
+    );
+    let second_date = timestamp.second_date_offset();
+    if let (Some(hour), Some(minute)) = (timestamp.hour_end(), timestamp.minute_end()) {
+        // the last time may belong to the first date
+        if second_date.is_none_or(|offset| hour.start() > offset) {
+            end += &format!(" {hour}:{minute}");
+        }
+    }
+    Some(end)
+}
+
+fn leaf(syntax: &crate::SyntaxNode) -> Record {
+    let mut record = Record::new("leaf", syntax.kind(), syntax.text_range());
+    record.raw = Some(syntax.to_string());
+    record
+}
+
+impl Org {
+    /// Serializes traversal events to JSON Lines, see [JsonlExport]
+    pub fn to_event_jsonl(&self) -> String {
+        let mut handler = JsonlExport::default();
+        self.traverse(&mut handler);
+        handler.finish()
+    }
+}
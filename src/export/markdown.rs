@@ -1,20 +1,117 @@
+use rowan::ast::AstNode;
 use std::cmp::min;
 use std::fmt::Write as _;
 
+use crate::ast::{
+    Checkbox, CounterDelimiter, EntityRepresentation, ListBullet, OrgTable, OrgTableCell,
+    OrgTableRow, TableAlignment,
+};
+use crate::entities::EntityInfo;
 use crate::{SyntaxElement, SyntaxNode};
 
 use super::event::{Container, Event};
 use super::TraversalContext;
 use super::Traverser;
 
-#[derive(Default)]
 pub struct MarkdownExport {
     output: String,
 
     inside_blockquote: bool,
+
+    entity_representation: EntityRepresentation,
+
+    /// user-defined entities, looked up before built-in entities
+    user_entities: Vec<EntityInfo>,
+
+    list_bullet: Option<ListBullet>,
+
+    counter_delimiter: Option<CounterDelimiter>,
+}
+
+impl Default for MarkdownExport {
+    fn default() -> Self {
+        MarkdownExport {
+            output: String::new(),
+            inside_blockquote: false,
+            entity_representation: EntityRepresentation::Utf8,
+            user_entities: Vec::new(),
+            list_bullet: None,
+            counter_delimiter: None,
+        }
+    }
 }
 
 impl MarkdownExport {
+    /// Sets which representation is used for entities, defaults to [`EntityRepresentation::Utf8`]
+    ///
+    /// ```rust
+    /// use orgize::{Org, ast::EntityRepresentation, export::MarkdownExport};
+    ///
+    /// let mut markdown = MarkdownExport::default().with_entity_representation(EntityRepresentation::Ascii);
+    /// Org::parse("a \\rarr b").traverse(&mut markdown);
+    /// assert_eq!(markdown.finish(), "a -> b\n");
+    /// ```
+    pub fn with_entity_representation(mut self, repr: EntityRepresentation) -> Self {
+        self.entity_representation = repr;
+        self
+    }
+
+    /// Sets user-defined entities, see [`ParseConfig::user_entities`](crate::ParseConfig::user_entities)
+    ///
+    /// ```rust
+    /// use orgize::{entities::EntityInfo, export::MarkdownExport, ParseConfig};
+    ///
+    /// let check = EntityInfo {
+    ///     name: "check".into(),
+    ///     latex: "\\checkmark".into(),
+    ///     latex_math: false,
+    ///     html: "&check;".into(),
+    ///     ascii: "v".into(),
+    ///     latin1: "v".into(),
+    ///     utf8: "✓".into(),
+    /// };
+    /// let config = ParseConfig {
+    ///     user_entities: vec![check.clone()],
+    ///     ..Default::default()
+    /// };
+    /// let mut markdown = MarkdownExport::default().with_user_entities(vec![check]);
+    /// config.parse("done \\check").traverse(&mut markdown);
+    /// assert_eq!(markdown.finish(), "done ✓\n");
+    /// ```
+    pub fn with_user_entities(mut self, entities: Vec<EntityInfo>) -> Self {
+        self.user_entities = entities;
+        self
+    }
+
+    /// Sets bullet of unordered list items, defaults to the bullet used in org
+    ///
+    /// ```rust
+    /// use orgize::{Org, ast::ListBullet, export::MarkdownExport};
+    ///
+    /// let mut markdown = MarkdownExport::default().with_list_bullet(ListBullet::Star);
+    /// Org::parse("- a\n  + b").traverse(&mut markdown);
+    /// assert_eq!(markdown.finish(), "* a\n\n  * b\n");
+    /// ```
+    pub fn with_list_bullet(mut self, bullet: ListBullet) -> Self {
+        self.list_bullet = Some(bullet);
+        self
+    }
+
+    /// Sets delimiter after counters of ordered list items, defaults to the
+    /// delimiter used in org
+    ///
+    /// ```rust
+    /// use orgize::{Org, ast::CounterDelimiter, export::MarkdownExport};
+    ///
+    /// let mut markdown = MarkdownExport::default().with_counter_delimiter(CounterDelimiter::Parenthesis);
+    /// Org::parse("1. a\n2. b").traverse(&mut markdown);
+    /// assert_eq!(markdown.finish(), "1) a\n\n2) b\n");
+    /// ```
+    pub fn with_counter_delimiter(mut self, delimiter: CounterDelimiter) -> Self {
+        self.counter_delimiter = Some(delimiter);
+        self
+    }
+
     pub fn push_str(&mut self, s: impl AsRef<str>) {
         self.output += s.as_ref();
     }
@@ -39,6 +136,81 @@ impl MarkdownExport {
         self.output
     }
 
+    /// Renders table as a GitHub-Flavored Markdown pipe table
+    ///
+    /// Rows before the first rule become the header. GFM only supports one
+    /// header row, so rest of them are rendered as body rows. Tables without
+    /// header get an empty one.
+    fn table(&mut self, table: &OrgTable, ctx: &mut TraversalContext) {
+        let alignments = table.column_alignments();
+
+        // columns with an explicit alignment cookie
+        let mut explicit = vec![false; alignments.len()];
+        let mut rows = vec![];
+        for row in table.syntax().children().filter_map(OrgTableRow::cast) {
+            if row.is_rule() {
+                continue;
+            }
+            if !row.is_alignment() {
+                rows.push(self.table_row(&row, alignments.len(), ctx));
+                continue;
+            }
+            for cell in row.syntax().children().filter_map(OrgTableCell::cast) {
+                let cookie = cell.syntax().to_string();
+                if matches!(cookie.trim().as_bytes().get(1), Some(b'l' | b'c' | b'r')) {
+                    if let Some(e) = explicit.get_mut(cell.column()) {
+                        *e = true;
+                    }
+                }
+            }
+        }
+
+        let header = if table.has_header() && !rows.is_empty() {
+            rows.remove(0)
+        } else {
+            vec![String::new(); alignments.len()]
+        };
+
+        self.follows_newline();
+        self.output += &format_table_row(&header);
+        self.output += "|";
+        for (alignment, explicit) in alignments.iter().zip(&explicit) {
+            self.output += match (alignment, explicit) {
+                (TableAlignment::Left, true) => " :--- |",
+                (TableAlignment::Left, false) => " --- |",
+                (TableAlignment::Center, _) => " :---: |",
+                (TableAlignment::Right, _) => " ---: |",
+            };
+        }
+        self.output += "\n";
+        for row in rows {
+            self.output += &format_table_row(&row);
+        }
+        // blank line ends the table
+        self.output += "\n";
+    }
+
+    /// Renders cells of given row, padded to `columns` cells
+    fn table_row(
+        &mut self,
+        row: &OrgTableRow,
+        columns: usize,
+        ctx: &mut TraversalContext,
+    ) -> Vec<String> {
+        let mut cells = vec![String::new(); columns];
+        for cell in row.syntax().children().filter_map(OrgTableCell::cast) {
+            let saved = std::mem::take(&mut self.output);
+            for child in cell.syntax().children_with_tokens() {
+                self.element(child, ctx);
+            }
+            let text = std::mem::replace(&mut self.output, saved);
+            if let Some(c) = cells.get_mut(cell.column()) {
+                *c = text.trim().replace('|', "\\|");
+            }
+        }
+        cells
+    }
+
     fn follows_newline(&mut self) {
         if !self.output.is_empty() && !self.output.ends_with(['\n', '\r']) {
             self.output += "\n";
@@ -52,6 +224,7 @@ impl Traverser for MarkdownExport {
             Event::Enter(Container::Document(_)) => {}
             Event::Leave(Container::Document(_)) => {}
 
+            Event::Enter(Container::Headline(headline)) if headline.is_commented() => ctx.skip(),
             Event::Enter(Container::Headline(headline)) => {
                 self.follows_newline();
                 let level = min(headline.level(), 6);
@@ -62,6 +235,20 @@ impl Traverser for MarkdownExport {
             }
             Event::Leave(Container::Headline(_)) => {}
 
+            Event::Enter(Container::InlineTask(task)) => {
+                // markdown has no inline tasks, so the title becomes its own paragraph
+                self.follows_newline();
+                if let Some(keyword) = task.todo_keyword() {
+                    let _ = write!(&mut self.output, "{keyword} ");
+                }
+                for elem in task.title() {
+                    self.element(elem, ctx);
+                }
+                self.output.truncate(self.output.trim_end().len());
+                self.output += "\n\n";
+            }
+            Event::Leave(Container::InlineTask(_)) => {}
+
             Event::Enter(Container::Paragraph(_)) => {}
             Event::Leave(Container::Paragraph(_)) => self.output += "\n",
 
@@ -101,6 +288,8 @@ impl Traverser for MarkdownExport {
             }
             Event::Leave(Container::QuoteBlock(_)) => self.inside_blockquote = false,
 
+            Event::Enter(Container::Drawer(_)) => ctx.skip(),
+
             Event::Enter(Container::CommentBlock(_)) => self.output += "<!--",
             Event::Leave(Container::CommentBlock(_)) => self.output += "-->",
 
@@ -118,24 +307,29 @@ impl Traverser for MarkdownExport {
 
             Event::Enter(Container::ListItem(list_item)) => {
                 self.follows_newline();
-                self.output += &" ".repeat(list_item.indent());
-                self.output += &list_item.bullet();
+                self.output += &list_item.indent_token();
+                self.output += &list_item.restyled_bullet(self.list_bullet, self.counter_delimiter);
+                match list_item.checkbox_state() {
+                    Some(Checkbox::Checked) => self.output += "[x] ",
+                    Some(Checkbox::Unchecked) => self.output += "[ ] ",
+                    // gfm has no partial task
+                    Some(Checkbox::Indeterminate) => self.output += "[ ] <!-- [-] --> ",
+                    None => {}
+                }
             }
             Event::Leave(Container::ListItem(_)) => {}
 
-            Event::Enter(Container::OrgTable(_table)) => {}
-            Event::Leave(Container::OrgTable(_)) => {}
-            Event::Enter(Container::OrgTableRow(_row)) => {}
-            Event::Leave(Container::OrgTableRow(_row)) => {}
-            Event::Enter(Container::OrgTableCell(_)) => {}
-            Event::Leave(Container::OrgTableCell(_)) => {}
+            Event::Enter(Container::OrgTable(table)) => {
+                self.table(&table, ctx);
+                ctx.skip();
+            }
 
             Event::Enter(Container::Link(link)) => {
-                let path = link.path();
-                let path = path.trim_start_matches("file:");
+                let path = link.destination();
 
                 if link.is_image() {
-                    let _ = write!(&mut self.output, "![]({path})");
+                    let alt = link.image_alt().unwrap_or_default();
+                    let _ = write!(&mut self.output, "![{alt}]({path})");
                     return ctx.skip();
                 }
 
@@ -147,7 +341,7 @@ impl Traverser for MarkdownExport {
                 self.output += "[";
             }
             Event::Leave(Container::Link(link)) => {
-                let _ = write!(&mut self.output, r#"]({})"#, &*link.path());
+                let _ = write!(&mut self.output, r#"]({})"#, link.destination());
             }
 
             Event::Text(text) => {
@@ -178,9 +372,130 @@ impl Traverser for MarkdownExport {
                 let _ = write!(&mut self.output, "{}", &latex.syntax);
             }
 
-            Event::Entity(entity) => self.output += entity.utf8(),
+            Event::Entity(entity) => {
+                self.output +=
+                    entity.representation_with(self.entity_representation, &self.user_entities)
+            }
+
+            // pandoc citation syntax shares the body with org-cite
+            Event::Citation(citation) => {
+                let _ = write!(&mut self.output, "[{}]", citation.body());
+            }
 
             _ => {}
         }
     }
 }
+
+/// Formats cells as a pipe table row, e.g. `| a | b |`
+fn format_table_row(cells: &[String]) -> String {
+    let mut row = String::from("|");
+    for cell in cells {
+        let _ = write!(&mut row, " {cell} |");
+    }
+    row + "\n"
+}
+
+#[test]
+fn checkbox() {
+    use crate::Org;
+
+    let mut export = MarkdownExport::default();
+    Org::parse("- [X] a\n- [-] b\n  1. [ ] c\n  2. d\n").traverse(&mut export);
+    insta::assert_snapshot!(export.finish(), @"
+    - [x] a
+
+    - [ ] <!-- [-] --> b
+
+      1. [ ] c
+
+      2. d
+    ");
+}
+
+#[test]
+fn table() {
+    use crate::Org;
+
+    let to_markdown = |input: &str| {
+        let mut export = MarkdownExport::default();
+        Org::parse(input).traverse(&mut export);
+        export.finish()
+    };
+
+    insta::assert_snapshot!(
+        to_markdown("| <l> | <c> | <r> | |\n| a | b | c | d |\n|---|\n| 1 | 2 | 3 | a\\vert{}b |\n| /4/ | | 6 |"),
+        @r"
+    | a | b | c | d |
+    | :--- | :---: | ---: | --- |
+    | 1 | 2 | 3 | a\|b |
+    | *4* |  | 6 |  |
+    "
+    );
+
+    insta::assert_snapshot!(
+        to_markdown("| a | b |\n| c | d |\n\n|---|\n| e |\n|---|\n| f |"),
+        @"
+    |  |  |
+    | --- | --- |
+    | a | b |
+    | c | d |
+
+    | e |
+    | --- |
+    | f |
+    "
+    );
+}
+
+#[test]
+fn list_tab_indent() {
+    use crate::Org;
+
+    let mut export = MarkdownExport::default();
+    Org::parse("- a\n\t- b\n\t\t- c\n").traverse(&mut export);
+    insta::assert_snapshot!(export.finish(), @"
+    - a
+
+    	- b
+
+    		- c
+    ");
+}
+
+#[test]
+fn list_style() {
+    use crate::Org;
+
+    let mut export = MarkdownExport::default()
+        .with_list_bullet(ListBullet::Plus)
+        .with_counter_delimiter(CounterDelimiter::Parenthesis);
+    Org::parse("- a\n  * b\n    10. c\n1) d\n").traverse(&mut export);
+    insta::assert_snapshot!(export.finish(), @"
+    + a
+
+      + b
+
+        10) c
+
+    1) d
+    ");
+}
+
+#[test]
+fn inline_task() {
+    use crate::Org;
+
+    let mut export = MarkdownExport::default();
+    Org::parse("* h\ntext\n*************** TODO inline *task* :tag:\nbody\n*************** END\n*************** single\n").traverse(&mut export);
+    insta::assert_snapshot!(export.finish(), @"
+    # h
+    text
+
+    TODO inline **task**
+
+    body
+
+    single
+    ");
+}
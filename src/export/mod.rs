@@ -2,10 +2,28 @@
 
 mod event;
 mod html;
+mod ical;
+#[cfg(feature = "serde")]
+mod json;
+#[cfg(feature = "serde")]
+mod jsonl;
 mod markdown;
+mod plain;
+mod sexp;
+mod tags;
 mod traverse;
 
 pub use event::{Container, Event};
-pub use html::{HtmlEscape, HtmlExport};
+pub use html::{HtmlEscape, HtmlExport, MathDelimiters};
+pub use ical::IcalExport;
+#[cfg(feature = "serde")]
+pub use json::JsonExport;
+#[cfg(feature = "serde")]
+pub use jsonl::JsonlExport;
 pub use markdown::MarkdownExport;
-pub use traverse::{from_fn, from_fn_with_ctx, FromFn, FromFnWithCtx, TraversalContext, Traverser};
+pub use plain::PlainTextExport;
+pub use sexp::SexpExport;
+pub use tags::ExportTags;
+pub use traverse::{
+    from_fn, from_fn_with_ctx, FromFn, FromFnWithCtx, HeadlineInfo, TraversalContext, Traverser,
+};
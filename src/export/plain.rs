@@ -0,0 +1,273 @@
+use crate::ast::EntityRepresentation;
+use crate::entities::EntityInfo;
+use crate::{SyntaxElement, SyntaxKind, SyntaxNode};
+
+use super::event::{Container, Event};
+use super::TraversalContext;
+use super::Traverser;
+
+/// Plain text export, strips all markup and keeps readable text only
+///
+/// Headline titles are put on their own lines, list items are prefixed with `- `,
+/// table cells are separated by tabs, links are reduced to their description (or
+/// path if there's none), and source or example blocks are included verbatim.
+/// Subtrees of commented headlines are omitted.
+///
+/// ```rust
+/// use orgize::{Org, export::PlainTextExport};
+///
+/// let mut plain = PlainTextExport::default();
+/// Org::parse("* /hello/ *world*\n- [[https://example.com][site]]").traverse(&mut plain);
+/// assert_eq!(plain.finish(), "hello world\n- site\n");
+/// ```
+#[derive(Default)]
+pub struct PlainTextExport {
+    output: String,
+
+    /// column of last rendered cell in current table row
+    table_column: usize,
+
+    /// `true` right after a list item bullet, so its first paragraph stays on the same line
+    item_start: bool,
+
+    /// user-defined entities, looked up before built-in entities
+    user_entities: Vec<EntityInfo>,
+}
+
+impl PlainTextExport {
+    /// Sets user-defined entities, see [`ParseConfig::user_entities`](crate::ParseConfig::user_entities)
+    ///
+    /// [`Org::to_plain_text`](crate::Org::to_plain_text) uses entities from the parse config of given org.
+    pub fn with_user_entities(mut self, entities: Vec<EntityInfo>) -> Self {
+        self.user_entities = entities;
+        self
+    }
+
+    pub fn push_str(&mut self, s: impl AsRef<str>) {
+        self.output += s.as_ref();
+    }
+
+    /// Render syntax node to plain text string
+    ///
+    /// ```rust
+    /// use orgize::{Org, ast::Paragraph, export::PlainTextExport, rowan::ast::AstNode};
+    ///
+    /// let org = Org::parse("* title\n/a/ [fn:1] ~b~");
+    /// let paragraph = org.first_node::<Paragraph>().unwrap();
+    /// let mut plain = PlainTextExport::default();
+    /// plain.render(paragraph.syntax());
+    /// assert_eq!(plain.finish(), "a b\n");
+    /// ```
+    pub fn render(&mut self, node: &SyntaxNode) {
+        let mut ctx = TraversalContext::default();
+        self.element(SyntaxElement::Node(node.clone()), &mut ctx);
+    }
+
+    pub fn finish(self) -> String {
+        self.output
+    }
+
+    /// Pushes inline text, collapsing whitespace left by removed objects
+    fn push_text(&mut self, text: &str) {
+        self.item_start = false;
+        if self.output.is_empty() || self.output.ends_with([' ', '\t', '\n']) {
+            self.output += text.trim_start_matches(' ');
+        } else {
+            self.output += text;
+        }
+    }
+
+    fn follows_newline(&mut self) {
+        if !self.output.is_empty() && !self.output.ends_with('\n') {
+            self.output += "\n";
+        }
+    }
+}
+
+impl Traverser for PlainTextExport {
+    fn event(&mut self, event: Event, ctx: &mut TraversalContext) {
+        match event {
+            Event::Enter(Container::Headline(headline)) if headline.is_commented() => ctx.skip(),
+            Event::Enter(Container::Headline(headline)) => {
+                self.follows_newline();
+                for elem in headline.title() {
+                    self.element(elem, ctx);
+                }
+                self.output.truncate(self.output.trim_end().len());
+                self.output += "\n";
+            }
+
+            Event::Enter(Container::InlineTask(task)) => {
+                self.follows_newline();
+                if let Some(keyword) = task.todo_keyword() {
+                    self.output += &keyword;
+                    self.output += " ";
+                }
+                for elem in task.title() {
+                    self.element(elem, ctx);
+                }
+                self.output.truncate(self.output.trim_end().len());
+                self.output += "\n";
+            }
+
+            Event::Enter(Container::Paragraph(_)) if !self.item_start => self.follows_newline(),
+            Event::Leave(Container::Paragraph(_)) => {
+                self.output
+                    .truncate(self.output.trim_end_matches(' ').len());
+                self.follows_newline();
+            }
+
+            Event::Enter(Container::ListItem(list_item)) => {
+                self.follows_newline();
+                self.output += &list_item.indent_token();
+                self.output += "- ";
+                self.item_start = true;
+            }
+
+            Event::Enter(Container::OrgTableRow(row)) => {
+                if row.is_rule() || row.is_alignment() {
+                    return ctx.skip();
+                }
+                self.follows_newline();
+                self.table_column = 0;
+            }
+            Event::Leave(Container::OrgTableRow(_)) => self.output += "\n",
+            Event::Enter(Container::OrgTableCell(cell)) => {
+                let column = cell.column();
+                self.output += &"\t".repeat(column.saturating_sub(self.table_column));
+                self.table_column = column;
+            }
+
+            Event::Enter(Container::Link(link)) if !link.has_description() => {
+                self.push_text(&link.path());
+                ctx.skip();
+            }
+
+            Event::Enter(Container::SourceBlock(block)) => {
+                self.follows_newline();
+                self.output += &block.value();
+                ctx.skip();
+            }
+            Event::Enter(Container::ExampleBlock(block)) => {
+                self.follows_newline();
+                for content in block
+                    .syntax
+                    .children()
+                    .filter(|n| n.kind() == SyntaxKind::BLOCK_CONTENT)
+                {
+                    self.output += &content.to_string();
+                }
+                ctx.skip();
+            }
+            Event::Enter(Container::VerseBlock(_))
+            | Event::Enter(Container::QuoteBlock(_))
+            | Event::Enter(Container::CenterBlock(_))
+            | Event::Enter(Container::SpecialBlock(_))
+            | Event::Enter(Container::List(_))
+            | Event::Enter(Container::OrgTable(_)) => self.follows_newline(),
+
+            Event::Enter(Container::FixedWidth(fixed_width)) => {
+                self.follows_newline();
+                self.output += &fixed_width.value();
+                ctx.skip();
+            }
+
+            // elements without readable text
+            Event::Enter(Container::FnRef(_))
+            | Event::Enter(Container::FnDef(_))
+            | Event::Enter(Container::Comment(_))
+            | Event::Enter(Container::CommentBlock(_))
+            | Event::Enter(Container::ExportBlock(_))
+            | Event::Enter(Container::Keyword(_))
+            | Event::Enter(Container::BabelCall(_))
+            | Event::Enter(Container::PropertyDrawer(_))
+            | Event::Enter(Container::Drawer(_)) => ctx.skip(),
+
+            Event::Text(text) => self.push_text(&text),
+
+            Event::LineBreak(_) => self.output += "\n",
+
+            Event::Timestamp(timestamp) => self.push_text(&timestamp.raw()),
+
+            Event::Entity(entity) => {
+                let text = entity
+                    .representation_with(EntityRepresentation::Utf8, &self.user_entities)
+                    .to_string();
+                self.push_text(&text);
+            }
+
+            Event::LatexFragment(latex) => self.push_text(&latex.raw()),
+
+            Event::Citation(citation) => self.push_text(&citation.raw()),
+
+            _ => {}
+        }
+    }
+}
+
+#[test]
+fn plain_text() {
+    use crate::Org;
+
+    insta::assert_snapshot!(
+        Org::parse(r#"#+TITLE: doc
+* TODO Heading *one* :tag:
+:PROPERTIES:
+:ID: 1
+:END:
+Some /emphasized/ text[fn:1] with a [[https://example.com][link]]
+and [[file:a.org]], \alpha.
+# comment
+
+- item *a*
+  1. nested
+| <r> |     |
+| a   | b   |
+|-----+-----|
+| c   | d e |
+
+#+begin_src rust
+fn main() {
+    println!("*not bold*");
+}
+#+end_src
+
+#+begin_example
+  verbatim /text/
+#+end_example
+
+[fn:1] footnote
+"#)
+        .to_plain_text(),
+        @r#"
+    Heading one
+    Some emphasized text with a link
+    and file:a.org, α.
+    - item a
+      - nested
+    a	b
+    c	d e
+    fn main() {
+        println!("*not bold*");
+    }
+      verbatim /text/
+    "#
+    );
+}
+
+#[test]
+fn inline_task() {
+    use crate::Org;
+
+    insta::assert_snapshot!(
+        Org::parse("* h\ntext\n*************** TODO inline *task* :tag:\nbody\n*************** END\n*************** single\n")
+            .to_plain_text(),
+        @"
+    h
+    text
+    TODO inline task
+    body
+    single
+    "
+    );
+}
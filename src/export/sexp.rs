@@ -0,0 +1,820 @@
+use std::cell::RefCell;
+use std::fmt::Write as _;
+
+use rowan::{TextRange, TextSize};
+
+use crate::ast::{Checkbox, DelayType, Link, RepeaterType, TimeUnit, Timestamp, TodoType};
+use crate::{Org, SyntaxKind, SyntaxNode};
+
+use super::event::{Container, Event};
+use super::TraversalContext;
+use super::Traverser;
+
+/// S-expression export, compatible with output of `org-element-parse-buffer`
+///
+/// Each element or object is written as `(type (:key value ...) children...)`, where
+/// the type and property names follow org-element's naming, e.g. `src-block`,
+/// `:raw-value` or `:todo-keyword`. Positions in `:begin` and `:end` are 1-based
+/// character positions, like Emacs buffer positions. Plain text is written as a string.
+///
+/// ```rust
+/// use orgize::{Org, export::SexpExport};
+///
+/// let mut sexp = SexpExport::default();
+/// Org::parse("* TODO hello :a:\nworld").traverse(&mut sexp);
+/// assert_eq!(
+///     sexp.finish(),
+///     r#"(org-data (:begin 1 :end 23)
+///   (headline (:raw-value "hello" :begin 1 :end 23 :level 1 :priority nil :tags ("a") :todo-keyword "TODO" :todo-type todo :archivedp nil :commentedp nil :title "hello")
+///     (section (:begin 18 :end 23)
+///       (paragraph (:begin 18 :end 23)
+///         "world"))))"#
+/// );
+///
+/// let mut sexp = SexpExport::default();
+/// Org::parse("* é\nwörld").traverse(&mut sexp);
+/// assert!(sexp.finish().contains("(section (:begin 5 :end 10)"));
+/// ```
+#[derive(Default)]
+pub struct SexpExport {
+    output: String,
+    depth: usize,
+    positions: Positions,
+}
+
+impl SexpExport {
+    pub fn finish(self) -> String {
+        self.output
+    }
+
+    /// Starts a new form on its own line, it must be closed by [`SexpExport::close`]
+    fn open(&mut self, ty: &str, plist: Plist) {
+        self.newline();
+        let _ = write!(&mut self.output, "({ty} ({plist})");
+        self.depth += 1;
+    }
+
+    fn close(&mut self) {
+        self.depth -= 1;
+        self.output += ")";
+    }
+
+    /// Writes a form without children
+    fn leaf(&mut self, ty: &str, plist: Plist) {
+        self.open(ty, plist);
+        self.close();
+    }
+
+    fn newline(&mut self) {
+        if !self.output.is_empty() {
+            self.output += "\n";
+            self.output += &"  ".repeat(self.depth);
+        }
+    }
+}
+
+impl Traverser for SexpExport {
+    fn event(&mut self, event: Event, ctx: &mut TraversalContext) {
+        match event {
+            Event::Enter(container) => {
+                let syntax = container.syntax();
+                let plist = Plist::default();
+                let (ty, plist) = match &container {
+                    Container::Document(_) => ("org-data", plist.range(syntax, &self.positions)),
+                    Container::Section(_) => ("section", plist.range(syntax, &self.positions)),
+                    Container::Paragraph(_) => ("paragraph", plist.range(syntax, &self.positions)),
+                    Container::Headline(headline) => {
+                        let title = headline.title_raw().trim().to_string();
+                        let plist = plist
+                            .str(":raw-value", Some(&title))
+                            .range(syntax, &self.positions)
+                            .int(":level", Some(headline.level() as u32))
+                            .int(
+                                ":priority",
+                                headline
+                                    .priority()
+                                    .and_then(|p| p.chars().next())
+                                    .map(|c| c as u32),
+                            )
+                            .strings(":tags", headline.tags())
+                            .str(":todo-keyword", headline.todo_keyword())
+                            .symbol(
+                                ":todo-type",
+                                headline.todo_type().map(|ty| match ty {
+                                    TodoType::Todo => "todo",
+                                    TodoType::Done => "done",
+                                }),
+                            )
+                            .bool(":archivedp", headline.is_archived())
+                            .bool(":commentedp", headline.is_commented())
+                            .str(":title", Some(&title));
+                        self.open("headline", plist);
+                        if let Some(planning) = headline.planning() {
+                            let timestamp = |ts: Option<Timestamp>| {
+                                ts.map(|ts| {
+                                    format!(
+                                        "(timestamp ({}))",
+                                        timestamp_plist(&ts, &self.positions)
+                                    )
+                                })
+                            };
+                            let plist = Plist::default()
+                                .range(&planning.syntax, &self.positions)
+                                .form(":closed", timestamp(planning.closed()))
+                                .form(":deadline", timestamp(planning.deadline()))
+                                .form(":scheduled", timestamp(planning.scheduled()));
+                            self.leaf("planning", plist);
+                        }
+                        return;
+                    }
+                    Container::InlineTask(task) => {
+                        let title = task.title_raw().trim().to_string();
+                        let plist = plist
+                            .str(":raw-value", Some(&title))
+                            .range(syntax, &self.positions)
+                            .int(":level", Some(task.level() as u32))
+                            .int(
+                                ":priority",
+                                task.priority()
+                                    .and_then(|p| p.chars().next())
+                                    .map(|c| c as u32),
+                            )
+                            .strings(":tags", task.tags())
+                            .str(":todo-keyword", task.todo_keyword())
+                            .symbol(
+                                ":todo-type",
+                                task.todo_type().map(|ty| match ty {
+                                    TodoType::Todo => "todo",
+                                    TodoType::Done => "done",
+                                }),
+                            )
+                            .str(":title", Some(&title));
+                        ("inlinetask", plist)
+                    }
+
+                    Container::OrgTable(_) => (
+                        "table",
+                        plist
+                            .range(syntax, &self.positions)
+                            .symbol(":type", Some("org")),
+                    ),
+                    Container::TableEl(_) => (
+                        "table",
+                        plist
+                            .range(syntax, &self.positions)
+                            .symbol(":type", Some("table.el")),
+                    ),
+                    Container::OrgTableRow(row) if row.is_rule() => {
+                        let plist = plist
+                            .range(syntax, &self.positions)
+                            .symbol(":type", Some("rule"));
+                        self.leaf("table-row", plist);
+                        return ctx.skip();
+                    }
+                    Container::OrgTableRow(_) => (
+                        "table-row",
+                        plist
+                            .range(syntax, &self.positions)
+                            .symbol(":type", Some("standard")),
+                    ),
+                    Container::OrgTableCell(_) => {
+                        ("table-cell", plist.range(syntax, &self.positions))
+                    }
+
+                    Container::List(list) => {
+                        let ty = if list.is_descriptive() {
+                            "descriptive"
+                        } else if list.is_ordered() {
+                            "ordered"
+                        } else {
+                            "unordered"
+                        };
+                        (
+                            "plain-list",
+                            plist
+                                .range(syntax, &self.positions)
+                                .symbol(":type", Some(ty)),
+                        )
+                    }
+                    Container::ListItem(item) => {
+                        let plist = plist
+                            .range(syntax, &self.positions)
+                            .str(":bullet", Some(item.bullet()))
+                            .symbol(
+                                ":checkbox",
+                                item.checkbox_state().map(|checkbox| match checkbox {
+                                    Checkbox::Checked => "on",
+                                    Checkbox::Unchecked => "off",
+                                    Checkbox::Indeterminate => "trans",
+                                }),
+                            )
+                            .int(":counter", item.counter().and_then(|c| c.parse().ok()));
+                        ("item", plist)
+                    }
+
+                    Container::Drawer(drawer) => (
+                        "drawer",
+                        plist
+                            .range(syntax, &self.positions)
+                            .str(":drawer-name", Some(drawer.name())),
+                    ),
+                    Container::PropertyDrawer(drawer) => {
+                        self.open("property-drawer", plist.range(syntax, &self.positions));
+                        for (key, value) in drawer.iter() {
+                            let plist = Plist::default()
+                                .range(
+                                    &key.syntax().parent().unwrap_or_else(|| syntax.clone()),
+                                    &self.positions,
+                                )
+                                .str(":key", Some(&key))
+                                .str(":value", Some(value.trim()));
+                            self.leaf("node-property", plist);
+                        }
+                        self.close();
+                        return ctx.skip();
+                    }
+                    Container::DynBlock(_) => {
+                        ("dynamic-block", plist.range(syntax, &self.positions))
+                    }
+
+                    Container::FnDef(fn_def) => (
+                        "footnote-definition",
+                        plist
+                            .range(syntax, &self.positions)
+                            .str(":label", fn_def.label()),
+                    ),
+                    // footnote content has no counterpart in org-element
+                    Container::FnContent(_) => return,
+                    Container::FnRef(fn_ref) => (
+                        "footnote-reference",
+                        plist
+                            .range(syntax, &self.positions)
+                            .str(":label", fn_ref.label()),
+                    ),
+
+                    Container::SpecialBlock(_) => (
+                        "special-block",
+                        plist
+                            .range(syntax, &self.positions)
+                            .str(":type", block_name(syntax)),
+                    ),
+                    Container::QuoteBlock(_) => {
+                        ("quote-block", plist.range(syntax, &self.positions))
+                    }
+                    Container::CenterBlock(_) => {
+                        ("center-block", plist.range(syntax, &self.positions))
+                    }
+                    Container::VerseBlock(_) => {
+                        ("verse-block", plist.range(syntax, &self.positions))
+                    }
+
+                    Container::SourceBlock(block) => {
+                        let plist = plist
+                            .range(syntax, &self.positions)
+                            .str(":language", block.language())
+                            .str(":switches", block.switches())
+                            .str(":parameters", block.parameters())
+                            .str(":value", Some(block.value()));
+                        self.leaf("src-block", plist);
+                        return ctx.skip();
+                    }
+                    Container::ExportBlock(block) => {
+                        let plist = plist
+                            .range(syntax, &self.positions)
+                            .str(":type", block.ty().map(|ty| ty.to_ascii_uppercase()))
+                            .str(":value", Some(block.value()));
+                        self.leaf("export-block", plist);
+                        return ctx.skip();
+                    }
+                    Container::ExampleBlock(_) | Container::CommentBlock(_) => {
+                        let ty = if syntax.kind() == SyntaxKind::EXAMPLE_BLOCK {
+                            "example-block"
+                        } else {
+                            "comment-block"
+                        };
+                        let value: String = syntax
+                            .children()
+                            .filter(|n| n.kind() == SyntaxKind::BLOCK_CONTENT)
+                            .map(|n| n.to_string())
+                            .collect();
+                        self.leaf(
+                            ty,
+                            plist
+                                .range(syntax, &self.positions)
+                                .str(":value", Some(value)),
+                        );
+                        return ctx.skip();
+                    }
+                    Container::Comment(comment) => {
+                        self.leaf(
+                            "comment",
+                            plist
+                                .range(syntax, &self.positions)
+                                .str(":value", Some(comment.value())),
+                        );
+                        return ctx.skip();
+                    }
+                    Container::FixedWidth(fixed_width) => {
+                        self.leaf(
+                            "fixed-width",
+                            plist
+                                .range(syntax, &self.positions)
+                                .str(":value", Some(fixed_width.value())),
+                        );
+                        return ctx.skip();
+                    }
+                    Container::Keyword(keyword) => {
+                        let plist = plist
+                            .range(syntax, &self.positions)
+                            .str(":key", Some(keyword.key().to_ascii_uppercase()))
+                            .str(":value", Some(keyword.value_trimmed()));
+                        self.leaf("keyword", plist);
+                        return ctx.skip();
+                    }
+                    Container::AffiliatedKeyword(keyword) => {
+                        let plist = plist
+                            .range(syntax, &self.positions)
+                            .str(":key", Some(keyword.key().to_ascii_uppercase()))
+                            .str(":value", keyword.value().as_deref().map(str::trim));
+                        self.leaf("keyword", plist);
+                        return ctx.skip();
+                    }
+                    Container::BabelCall(_) => {
+                        self.leaf("babel-call", plist.range(syntax, &self.positions));
+                        return ctx.skip();
+                    }
+
+                    Container::Link(link) => (
+                        "link",
+                        link_plist(link, plist.range(syntax, &self.positions)),
+                    ),
+                    Container::RadioTarget(_) => {
+                        ("radio-target", plist.range(syntax, &self.positions))
+                    }
+                    Container::Target(_) => {
+                        self.leaf(
+                            "target",
+                            plist
+                                .range(syntax, &self.positions)
+                                .str(":value", Some(text_of(syntax))),
+                        );
+                        return ctx.skip();
+                    }
+                    Container::Bold(_) => ("bold", plist.range(syntax, &self.positions)),
+                    Container::Strike(_) => {
+                        ("strike-through", plist.range(syntax, &self.positions))
+                    }
+                    Container::Italic(_) => ("italic", plist.range(syntax, &self.positions)),
+                    Container::Underline(_) => ("underline", plist.range(syntax, &self.positions)),
+                    Container::Verbatim(_) | Container::Code(_) => {
+                        let ty = if syntax.kind() == SyntaxKind::VERBATIM {
+                            "verbatim"
+                        } else {
+                            "code"
+                        };
+                        self.leaf(
+                            ty,
+                            plist
+                                .range(syntax, &self.positions)
+                                .str(":value", Some(text_of(syntax))),
+                        );
+                        return ctx.skip();
+                    }
+                    Container::Superscript(_) => {
+                        ("superscript", plist.range(syntax, &self.positions))
+                    }
+                    Container::Subscript(_) => ("subscript", plist.range(syntax, &self.positions)),
+                };
+                self.open(ty, plist);
+            }
+
+            Event::Leave(Container::FnContent(_)) => {}
+            Event::Leave(_) => self.close(),
+
+            Event::Text(text) => {
+                self.newline();
+                self.output += &quote(&text);
+            }
+            // labels are included in `:label` of footnote definitions and references
+            Event::FnLabel(_) => {}
+
+            Event::Macros(macros) => {
+                let plist = Plist::default()
+                    .range(&macros.syntax, &self.positions)
+                    .str(":key", Some(macros.key()))
+                    .strings(
+                        ":args",
+                        macros.args().iter().flat_map(|args| args.split(',')),
+                    );
+                self.leaf("macro", plist);
+            }
+            Event::Cookie(cookie) => {
+                let plist = Plist::default()
+                    .range(&cookie.syntax, &self.positions)
+                    .str(":value", Some(cookie.raw()));
+                self.leaf("statistics-cookie", plist);
+            }
+            Event::InlineCall(call) => {
+                let plist = Plist::default()
+                    .range(&call.syntax, &self.positions)
+                    .str(":call", Some(call.call()))
+                    .str(":inside-header", call.inside_header())
+                    .str(":arguments", Some(call.arguments()))
+                    .str(":end-header", call.end_header());
+                self.leaf("inline-babel-call", plist);
+            }
+            Event::InlineSrc(src) => {
+                let plist = Plist::default()
+                    .range(&src.syntax, &self.positions)
+                    .str(":language", Some(src.language()))
+                    .str(":parameters", src.parameters())
+                    .str(":value", Some(src.value()));
+                self.leaf("inline-src-block", plist);
+            }
+            Event::Clock(clock) => {
+                let status = if clock.is_running() {
+                    "running"
+                } else {
+                    "closed"
+                };
+                let plist = Plist::default()
+                    .range(&clock.syntax, &self.positions)
+                    .str(":value", clock.value().map(|ts| ts.raw()))
+                    .str(":duration", clock.duration_raw())
+                    .symbol(":status", Some(status));
+                self.leaf("clock", plist);
+            }
+            Event::LineBreak(line_break) => self.leaf(
+                "line-break",
+                Plist::default().range(&line_break.syntax, &self.positions),
+            ),
+            Event::Snippet(snippet) => {
+                let plist = Plist::default()
+                    .range(&snippet.syntax, &self.positions)
+                    .str(":back-end", Some(snippet.backend()))
+                    .str(":value", Some(snippet.value()));
+                self.leaf("export-snippet", plist);
+            }
+            Event::Rule(rule) => self.leaf(
+                "horizontal-rule",
+                Plist::default().range(&rule.syntax, &self.positions),
+            ),
+            Event::Timestamp(timestamp) => {
+                self.leaf("timestamp", timestamp_plist(&timestamp, &self.positions));
+            }
+            Event::LatexFragment(latex) => {
+                let plist = Plist::default()
+                    .range(&latex.syntax, &self.positions)
+                    .str(":value", Some(latex.raw()));
+                self.leaf("latex-fragment", plist);
+            }
+            Event::LatexEnvironment(latex) => {
+                let plist = Plist::default()
+                    .range(&latex.syntax, &self.positions)
+                    .str(":value", Some(latex.raw()));
+                self.leaf("latex-environment", plist);
+            }
+            Event::Entity(entity) => {
+                let plist = Plist::default()
+                    .range(&entity.syntax, &self.positions)
+                    .str(":name", Some(entity.name()))
+                    .bool(":use-brackets-p", entity.is_use_brackets());
+                self.leaf("entity", plist);
+            }
+            Event::Citation(citation) => {
+                let plist = Plist::default()
+                    .range(&citation.syntax, &self.positions)
+                    .str(":style", citation.style())
+                    .str(":prefix", citation.prefix())
+                    .str(":suffix", citation.suffix())
+                    .strings(":keys", citation.keys().into_iter());
+                self.leaf("citation", plist);
+            }
+
+            #[cfg(feature = "syntax-org-fc")]
+            Event::Cloze(cloze) => {
+                let plist = Plist::default()
+                    .range(&cloze.syntax, &self.positions)
+                    .str(":value", Some(cloze.text_raw()))
+                    .str(":hint", cloze.hint())
+                    .str(":id", cloze.id());
+                self.leaf("cloze", plist);
+            }
+        }
+    }
+}
+
+/// Character positions of the document being exported
+///
+/// Emacs counts positions in characters, so byte offsets are converted
+/// through a prefix table built once per document.
+#[derive(Default)]
+struct Positions(RefCell<Option<(SyntaxNode, Vec<u32>)>>);
+
+impl Positions {
+    /// Returns 1-based character position of given byte offset in document of `syntax`
+    fn get(&self, syntax: &SyntaxNode, offset: TextSize) -> u32 {
+        let root = syntax.ancestors().last().unwrap_or_else(|| syntax.clone());
+        let mut table = self.0.borrow_mut();
+        let chars = match &mut *table {
+            Some((cached, chars)) if *cached == root => chars,
+            table => {
+                // number of characters before each byte offset
+                let mut chars = Vec::with_capacity(usize::from(root.text_range().len()) + 1);
+                for (count, c) in root.to_string().chars().enumerate() {
+                    chars.extend(std::iter::repeat_n(count as u32, c.len_utf8()));
+                }
+                chars.push(chars.last().map_or(0, |n| n + 1));
+                &mut table.insert((root, chars)).1
+            }
+        };
+        chars
+            .get(usize::from(offset))
+            .copied()
+            .unwrap_or(u32::from(offset))
+            + 1
+    }
+}
+
+/// Property list of a single form, e.g. `:begin 1 :end 10`
+#[derive(Default)]
+struct Plist(String);
+
+impl Plist {
+    /// Appends `:begin` and `:end`, converted to 1-based character positions
+    fn range(self, syntax: &SyntaxNode, positions: &Positions) -> Self {
+        let range: TextRange = syntax.text_range();
+        self.int(":begin", Some(positions.get(syntax, range.start())))
+            .int(":end", Some(positions.get(syntax, range.end())))
+    }
+
+    fn push(mut self, key: &str, value: impl std::fmt::Display) -> Self {
+        let _ = write!(&mut self.0, " {key} {value}");
+        self
+    }
+
+    fn str(self, key: &str, value: Option<impl AsRef<str>>) -> Self {
+        match value {
+            Some(value) => {
+                let value = quote(value.as_ref());
+                self.push(key, value)
+            }
+            None => self.push(key, "nil"),
+        }
+    }
+
+    fn int(self, key: &str, value: Option<u32>) -> Self {
+        match value {
+            Some(value) => self.push(key, value),
+            None => self.push(key, "nil"),
+        }
+    }
+
+    fn symbol(self, key: &str, value: Option<&str>) -> Self {
+        self.push(key, value.unwrap_or("nil"))
+    }
+
+    /// Appends a nested form, e.g. `(timestamp (...))`
+    fn form(self, key: &str, value: Option<String>) -> Self {
+        self.push(key, value.as_deref().unwrap_or("nil"))
+    }
+
+    fn bool(self, key: &str, value: bool) -> Self {
+        self.push(key, if value { "t" } else { "nil" })
+    }
+
+    /// Appends a list of strings, or `nil` if it's empty
+    fn strings<S: AsRef<str>>(self, key: &str, values: impl Iterator<Item = S>) -> Self {
+        let values: Vec<_> = values.map(|s| quote(s.as_ref())).collect();
+        if values.is_empty() {
+            self.push(key, "nil")
+        } else {
+            self.push(key, format_args!("({})", values.join(" ")))
+        }
+    }
+}
+
+impl std::fmt::Display for Plist {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.0.trim_start())
+    }
+}
+
+/// Quotes and escapes string as an elisp string literal
+fn quote(s: &str) -> String {
+    let mut quoted = String::with_capacity(s.len() + 2);
+    quoted.push('"');
+    for c in s.chars() {
+        if c == '"' || c == '\\' {
+            quoted.push('\\');
+        }
+        quoted.push(c);
+    }
+    quoted.push('"');
+    quoted
+}
+
+/// Concatenates `TEXT` tokens directly inside given node
+fn text_of(syntax: &SyntaxNode) -> String {
+    syntax
+        .children_with_tokens()
+        .filter_map(|e| e.into_token())
+        .filter(|t| t.kind() == SyntaxKind::TEXT)
+        .map(|t| t.text().to_string())
+        .collect()
+}
+
+/// Returns name of special block, e.g. `note` in `#+begin_note`
+fn block_name(syntax: &SyntaxNode) -> Option<String> {
+    let begin = syntax
+        .children()
+        .find(|n| n.kind() == SyntaxKind::BLOCK_BEGIN)?;
+    let name = begin
+        .children_with_tokens()
+        .filter_map(|e| e.into_token())
+        .filter(|t| t.kind() == SyntaxKind::TEXT)
+        .nth(1)?;
+    Some(name.text().to_string())
+}
+
+fn link_plist(link: &Link, plist: Plist) -> Plist {
+    let raw_link = link.path();
+    let (ty, path) = if let Some(id) = raw_link.strip_prefix('#') {
+        ("custom-id".to_string(), id.to_string())
+    } else if raw_link.starts_with(['/', '.', '~']) {
+        ("file".to_string(), raw_link.to_string())
+    } else if let Some(protocol) = link.protocol() {
+        let path = &raw_link[protocol.len() + 1..];
+        (protocol, path.to_string())
+    } else {
+        ("fuzzy".to_string(), raw_link.to_string())
+    };
+    plist
+        .str(":type", Some(ty))
+        .str(":path", Some(path))
+        .str(":raw-link", Some(&raw_link))
+}
+
+fn timestamp_plist(timestamp: &Timestamp, positions: &Positions) -> Plist {
+    let ty = match (
+        timestamp.is_diary(),
+        timestamp.is_active(),
+        timestamp.is_range(),
+    ) {
+        (true, _, _) => "diary",
+        (_, true, false) => "active",
+        (_, true, true) => "active-range",
+        (_, false, false) => "inactive",
+        (_, false, true) => "inactive-range",
+    };
+
+    let number = |token: Option<crate::ast::Token>| token.and_then(|t| t.parse::<u32>().ok());
+
+    // in `<...>--<...>` ranges, a single time may belong to either date
+    let second_date = timestamp.second_date_offset();
+    let (hour_start, minute_start) = match (timestamp.hour_start(), second_date) {
+        (Some(hour), Some(offset)) if hour.start() > offset => (None, None),
+        (hour, _) => (number(hour), number(timestamp.minute_start())),
+    };
+    let (hour_end, minute_end) = match (timestamp.hour_end(), second_date) {
+        (Some(hour), Some(offset)) if hour.start() < offset => (None, None),
+        (hour, _) => (number(hour), number(timestamp.minute_end())),
+    };
+
+    let unit = |unit: TimeUnit| match unit {
+        TimeUnit::Hour => "hour",
+        TimeUnit::Day => "day",
+        TimeUnit::Week => "week",
+        TimeUnit::Month => "month",
+        TimeUnit::Year => "year",
+    };
+    let repeater = timestamp.repeater();
+    let warning = timestamp.warning();
+
+    Plist::default()
+        .symbol(":type", Some(ty))
+        .str(":raw-value", Some(timestamp.raw()))
+        .range(&timestamp.syntax, positions)
+        .int(":year-start", number(timestamp.year_start()))
+        .int(":month-start", number(timestamp.month_start()))
+        .int(":day-start", number(timestamp.day_start()))
+        .int(":hour-start", hour_start)
+        .int(":minute-start", minute_start)
+        .int(":year-end", number(timestamp.year_end()))
+        .int(":month-end", number(timestamp.month_end()))
+        .int(":day-end", number(timestamp.day_end()))
+        .int(":hour-end", hour_end)
+        .int(":minute-end", minute_end)
+        .symbol(
+            ":repeater-type",
+            repeater.map(|r| match r.ty {
+                RepeaterType::Cumulate => "cumulate",
+                RepeaterType::CatchUp => "catch-up",
+                RepeaterType::Restart => "restart",
+            }),
+        )
+        .int(":repeater-value", repeater.map(|r| r.value))
+        .symbol(":repeater-unit", repeater.map(|r| unit(r.unit)))
+        .symbol(
+            ":warning-type",
+            warning.map(|w| match w.ty {
+                DelayType::All => "all",
+                DelayType::First => "first",
+            }),
+        )
+        .int(":warning-value", warning.map(|w| w.value))
+        .symbol(":warning-unit", warning.map(|w| unit(w.unit)))
+}
+
+impl Org {
+    /// Serializes syntax tree to S-expression, see [SexpExport]
+    ///
+    /// ```rust
+    /// use orgize::Org;
+    ///
+    /// let sexp = Org::parse("[[https://example.com][site]]").to_sexp();
+    /// assert!(sexp.contains(r#"(link (:begin 1 :end 30 :type "https" :path "//example.com" :raw-link "https://example.com")"#));
+    /// ```
+    pub fn to_sexp(&self) -> String {
+        let mut handler = SexpExport::default();
+        self.traverse(&mut handler);
+        handler.finish()
+    }
+}
+
+#[test]
+fn sexp() {
+    insta::assert_snapshot!(
+        Org::parse(r#"#+TITLE: doc
+* DONE [#A] Heading *one* :tag:
+CLOSED: [2024-01-02 Tue 10:00]
+:PROPERTIES:
+:ID: 1
+:END:
+Some /text/[fn:1] with =code= and \alpha {{{m(a,b)}}}.
+- [X] item
+  <2024-01-02 Tue 10:00>--<2024-01-03 Wed> <2024-01-05 Fri 09:00 +1w -2d>
+| a | "b" |
+|---+-----|
+
+#+begin_src rust -n :tangle no
+fn main() {}
+#+end_src
+
+#+begin_note
+note
+#+end_note
+
+[fn:1] footnote
+"#)
+        .to_sexp(),
+        @r#"
+    (org-data (:begin 1 :end 369)
+      (section (:begin 1 :end 14)
+        (keyword (:begin 1 :end 14 :key "TITLE" :value "doc")))
+      (headline (:raw-value "Heading *one*" :begin 14 :end 369 :level 1 :priority 65 :tags ("tag") :todo-keyword "DONE" :todo-type done :archivedp nil :commentedp nil :title "Heading *one*")
+        (planning (:begin 46 :end 77 :closed (timestamp (:type inactive :raw-value "[2024-01-02 Tue 10:00]" :begin 54 :end 76 :year-start 2024 :month-start 1 :day-start 2 :hour-start 10 :minute-start 0 :year-end 2024 :month-end 1 :day-end 2 :hour-end 10 :minute-end 0 :repeater-type nil :repeater-value nil :repeater-unit nil :warning-type nil :warning-value nil :warning-unit nil)) :deadline nil :scheduled nil))
+        (property-drawer (:begin 77 :end 103)
+          (node-property (:begin 90 :end 97 :key "ID" :value "1")))
+        (section (:begin 103 :end 369)
+          (paragraph (:begin 103 :end 158)
+            "Some "
+            (italic (:begin 108 :end 114)
+              "text")
+            (footnote-reference (:begin 114 :end 120 :label "1"))
+            " with "
+            (verbatim (:begin 126 :end 132 :value "code"))
+            " and "
+            (entity (:begin 137 :end 143 :name "alpha" :use-brackets-p nil))
+            " "
+            (macro (:begin 144 :end 156 :key "m" :args ("a" "b")))
+            ".
+    ")
+          (plain-list (:begin 158 :end 243 :type unordered)
+            (item (:begin 158 :end 243 :bullet "- " :checkbox on :counter nil)
+              (paragraph (:begin 164 :end 243)
+                "item
+      "
+                (timestamp (:type active-range :raw-value "<2024-01-02 Tue 10:00>--<2024-01-03 Wed>" :begin 171 :end 211 :year-start 2024 :month-start 1 :day-start 2 :hour-start 10 :minute-start 0 :year-end 2024 :month-end 1 :day-end 3 :hour-end nil :minute-end nil :repeater-type nil :repeater-value nil :repeater-unit nil :warning-type nil :warning-value nil :warning-unit nil))
+                " "
+                (timestamp (:type active :raw-value "<2024-01-05 Fri 09:00 +1w -2d>" :begin 212 :end 242 :year-start 2024 :month-start 1 :day-start 5 :hour-start 9 :minute-start 0 :year-end 2024 :month-end 1 :day-end 5 :hour-end 9 :minute-end 0 :repeater-type cumulate :repeater-value 1 :repeater-unit week :warning-type all :warning-value 2 :warning-unit day))
+                "
+    ")))
+          (table (:begin 243 :end 268 :type org)
+            (table-row (:begin 243 :end 255 :type standard)
+              (table-cell (:begin 245 :end 246)
+                "a")
+              (table-cell (:begin 249 :end 252)
+                "\"b\""))
+            (table-row (:begin 255 :end 267 :type rule)))
+          (src-block (:begin 268 :end 323 :language "rust" :switches "-n" :parameters ":tangle no" :value "fn main() {}
+    "))
+          (special-block (:begin 323 :end 353 :type "note")
+            (paragraph (:begin 336 :end 341)
+              "note
+    "))
+          (footnote-definition (:begin 353 :end 369 :label "1")
+            " footnote"))))
+    "#
+    );
+}
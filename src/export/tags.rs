@@ -0,0 +1,129 @@
+use std::collections::HashSet;
+
+use rowan::{ast::AstNode, WalkEvent};
+
+use crate::ast::{Document, Headline};
+use crate::SyntaxNode;
+
+/// Tags controlling which subtrees are exported, see [`HtmlExport::with_export_tags`](super::HtmlExport::with_export_tags)
+///
+/// Subtrees tagged with any exclude tag are omitted. If any headline in the
+/// document is tagged with a select tag, only selected subtrees, their
+/// ancestors and the zeroth section are exported.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExportTags {
+    /// Equivalent to `org-export-select-tags`, defaults to `export`
+    pub select: Vec<String>,
+    /// Equivalent to `org-export-exclude-tags`, defaults to `noexport`
+    pub exclude: Vec<String>,
+}
+
+impl Default for ExportTags {
+    fn default() -> Self {
+        ExportTags {
+            select: vec!["export".into()],
+            exclude: vec!["noexport".into()],
+        }
+    }
+}
+
+impl ExportTags {
+    /// Creates tags from `#+EXPORT_SELECT_TAGS` and `#+EXPORT_EXCLUDE_TAGS` keywords
+    /// in the zeroth section, falling back to defaults if absent
+    ///
+    /// ```rust
+    /// use orgize::{Org, export::ExportTags};
+    ///
+    /// let org = Org::parse("#+EXPORT_EXCLUDE_TAGS: draft private\n* a");
+    /// let tags = ExportTags::from_document(&org.document());
+    /// assert_eq!(tags.select, vec!["export"]);
+    /// assert_eq!(tags.exclude, vec!["draft", "private"]);
+    /// ```
+    pub fn from_document(document: &Document) -> Self {
+        let mut tags = ExportTags::default();
+        let mut select: Option<Vec<String>> = None;
+        let mut exclude: Option<Vec<String>> = None;
+
+        for keyword in document.keywords() {
+            let target = if keyword.key().eq_ignore_ascii_case("EXPORT_SELECT_TAGS") {
+                &mut select
+            } else if keyword.key().eq_ignore_ascii_case("EXPORT_EXCLUDE_TAGS") {
+                &mut exclude
+            } else {
+                continue;
+            };
+            target
+                .get_or_insert_with(Vec::new)
+                .extend(keyword.value().split_whitespace().map(String::from));
+        }
+
+        if let Some(select) = select {
+            tags.select = select;
+        }
+        if let Some(exclude) = exclude {
+            tags.exclude = exclude;
+        }
+        tags
+    }
+
+    /// Returns headlines under `root` whose subtrees should be omitted
+    ///
+    /// A headline is excluded if it has an exclude tag, or if any headline has a
+    /// select tag and it's neither selected, nor an ancestor or a descendant of a
+    /// selected headline. Descendants of excluded headlines aren't checked.
+    pub(crate) fn excluded_headlines(&self, root: &SyntaxNode) -> HashSet<SyntaxNode> {
+        // (headline, has exclude tag, under a selected headline)
+        let mut entered = vec![];
+        // headlines containing a selected headline, including themselves
+        let mut leading_to_selected = HashSet::new();
+        // whether each open headline is selected, and contains a selected headline so far
+        let mut stack: Vec<(bool, bool)> = vec![];
+        let mut selected_ancestors = 0usize;
+
+        for event in root.preorder() {
+            match event {
+                WalkEvent::Enter(node) => {
+                    let Some(hdl) = Headline::cast(node) else {
+                        continue;
+                    };
+                    let selected = self.is_selected(&hdl);
+                    let excluded = hdl
+                        .tags()
+                        .any(|tag| self.exclude.iter().any(|t| t == tag.as_ref()));
+                    entered.push((hdl.syntax().clone(), excluded, selected_ancestors > 0));
+                    selected_ancestors += usize::from(selected);
+                    stack.push((selected, selected));
+                }
+                WalkEvent::Leave(node) => {
+                    if !Headline::can_cast(node.kind()) {
+                        continue;
+                    }
+                    let (selected, contains_selected) = stack.pop().unwrap_or_default();
+                    selected_ancestors -= usize::from(selected);
+                    if contains_selected {
+                        if let Some((_, parent)) = stack.last_mut() {
+                            *parent = true;
+                        }
+                        leading_to_selected.insert(node);
+                    }
+                }
+            }
+        }
+
+        let has_selected = !leading_to_selected.is_empty();
+        entered
+            .into_iter()
+            .filter(|(node, excluded, under_selected)| {
+                *excluded
+                    || (has_selected && !*under_selected && !leading_to_selected.contains(node))
+            })
+            .map(|(node, _, _)| node)
+            .collect()
+    }
+
+    fn is_selected(&self, headline: &Headline) -> bool {
+        headline
+            .tags()
+            .any(|tag| self.select.iter().any(|t| t == tag.as_ref()))
+    }
+}
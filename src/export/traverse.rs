@@ -14,12 +14,102 @@ enum TraversalControl {
     Continue,
 }
 
+/// Controls how traversal continues after handling an event
+///
+/// - [`skip`](TraversalContext::skip) only affects the current node: called in an
+///   `Event::Enter`, its descendants and its `Event::Leave` are not visited, and
+///   traversal goes on with the next sibling.
+/// - [`stop`](TraversalContext::stop) aborts the whole traversal: no more events are
+///   emitted, including `Event::Leave` of all ancestors.
+///
+/// If both are called while handling the same event, the last call wins.
+///
+/// ```rust
+/// use orgize::{export::{from_fn_with_ctx, Event}, rowan::ast::AstNode, Org};
+///
+/// let org = Org::parse("* a\n<2024-01-01>\n* b\n<2024-02-02>");
+///
+/// let mut events = 0;
+/// let mut first = None;
+/// let mut handler = from_fn_with_ctx(|event, ctx| {
+///     events += 1;
+///     if let Event::Timestamp(ts) = event {
+///         first = Some(ts.syntax().to_string());
+///         ctx.stop();
+///     }
+/// });
+/// org.traverse(&mut handler);
+/// assert_eq!(first.as_deref(), Some("<2024-01-01>"));
+/// // document, headline, section and paragraph entered, then the timestamp
+/// assert_eq!(events, 5);
+/// ```
 #[derive(Default)]
 pub struct TraversalContext {
     control: TraversalControl,
+    headline_path: Vec<HeadlineInfo>,
+}
+
+/// Headline entered during traversal, see [`TraversalContext::headline_path`]
+#[derive(Debug, Clone)]
+pub struct HeadlineInfo {
+    /// Level of headline
+    pub level: usize,
+    /// Headline node itself
+    pub headline: Headline,
+}
+
+impl HeadlineInfo {
+    fn new(headline: &Headline) -> Self {
+        HeadlineInfo {
+            level: headline.level(),
+            headline: headline.clone(),
+        }
+    }
+
+    /// Returns raw title of headline, see [`Headline::title_raw`]
+    ///
+    /// Computed on each call, so traversals that never ask for titles
+    /// don't pay for them.
+    pub fn title(&self) -> String {
+        self.headline.title_raw()
+    }
 }
 
 impl TraversalContext {
+    /// Returns headlines enclosing current event, outermost first
+    ///
+    /// Headline is pushed before its `Event::Enter`, and popped after its
+    /// `Event::Leave`, so it's also included when handling these two events.
+    ///
+    /// ```rust
+    /// use orgize::{export::{from_fn_with_ctx, Event}, Org};
+    ///
+    /// let org = Org::parse("x\n* a\n** b\n*y*\n* c\nz");
+    ///
+    /// let mut paths = vec![];
+    /// let mut handler = from_fn_with_ctx(|event, ctx| {
+    ///     match event {
+    ///         Event::Text(text) if !text.trim().is_empty() => {
+    ///             let path: Vec<_> = ctx.headline_path().iter().map(|h| h.title()).collect();
+    ///             paths.push((text.trim().to_string(), path.join("/")));
+    ///         }
+    ///         _ => {}
+    ///     }
+    /// });
+    /// org.traverse(&mut handler);
+    /// assert_eq!(
+    ///     paths,
+    ///     vec![
+    ///         ("x".to_string(), "".to_string()),
+    ///         ("y".to_string(), "a/b".to_string()),
+    ///         ("z".to_string(), "c".to_string()),
+    ///     ]
+    /// );
+    /// ```
+    pub fn headline_path(&self) -> &[HeadlineInfo] {
+        &self.headline_path
+    }
+
     /// Stops traversal completely
     pub fn stop(&mut self) {
         self.control = TraversalControl::Stop;
@@ -36,6 +126,11 @@ impl TraversalContext {
     pub fn r#continue(&mut self) {
         self.control = TraversalControl::Continue;
     }
+
+    /// Returns `true` if no control method was called since last reset
+    pub(crate) fn is_continue(&self) -> bool {
+        self.control == TraversalControl::Continue
+    }
 }
 
 /// A trait for enumerating org syntax tree
@@ -151,8 +246,27 @@ pub trait Traverser {
 
                 match node.kind() {
                     DOCUMENT => walk!(Document),
-                    HEADLINE => walk!(Headline),
+                    HEADLINE => {
+                        let node = Headline { syntax: node };
+                        ctx.headline_path.push(HeadlineInfo::new(&node));
+                        'walk: {
+                            self.event(Event::Enter(Container::Headline(node.clone())), ctx);
+                            if ctx.control != TraversalControl::Continue {
+                                break 'walk;
+                            }
+                            for child in node.syntax.children_with_tokens() {
+                                self.element(child, ctx);
+                                if ctx.control != TraversalControl::Continue {
+                                    break 'walk;
+                                }
+                            }
+                            self.event(Event::Leave(Container::Headline(node.clone())), ctx);
+                        }
+                        ctx.headline_path.pop();
+                        take_control!();
+                    }
                     SECTION => walk!(Section),
+                    INLINE_TASK => walk!(InlineTask),
                     PARAGRAPH => walk!(Paragraph),
                     BOLD => walk!(Bold),
                     ITALIC => walk!(Italic),
@@ -181,6 +295,7 @@ pub trait Traverser {
                     DYN_BLOCK => walk!(DynBlock),
                     FN_DEF => walk!(FnDef),
                     FN_REF => walk!(FnRef),
+                    CITATION => walk!(@Citation),
                     FN_CONTENT => walk!(FnContent),
                     MACROS => walk!(@Macros),
                     SNIPPET => walk!(@Snippet),
@@ -202,7 +317,7 @@ pub trait Traverser {
                     PROPERTY_DRAWER => walk!(PropertyDrawer),
                     #[cfg(feature = "syntax-org-fc")]
                     CLOZE => walk!(@Cloze),
-                    BLOCK_CONTENT | LIST_ITEM_CONTENT => {
+                    BLOCK_CONTENT | LIST_ITEM_CONTENT | DRAWER_CONTENT => {
                         for child in node.children_with_tokens() {
                             self.element(child, ctx);
                             take_control!();
@@ -0,0 +1,191 @@
+use rowan::{ast::AstNode, NodeOrToken, TextRange, TextSize};
+
+use crate::ast::{ListItem, Paragraph};
+use crate::{Org, SyntaxKind};
+
+/// A unit of text which can't be broken across lines
+struct Word {
+    text: String,
+    /// Forces a line break after this word, e.g. `\\`
+    hard_break: bool,
+}
+
+impl Org {
+    /// Reflows the text of given paragraph to fit in `width` columns,
+    /// equivalent to `org-fill-paragraph`
+    ///
+    /// Objects like links and emphasis are never broken across lines, and
+    /// continuation lines inside a list item are indented to its content.
+    ///
+    /// ```rust
+    /// use orgize::{Org, ast::Paragraph};
+    ///
+    /// let mut org = Org::parse("lorem ipsum *dolor sit* amet\nconsectetur [[https://example.com][adipiscing elit]]\n");
+    /// let para = org.first_node::<Paragraph>().unwrap();
+    /// org.fill_paragraph(&para, 20);
+    /// assert_eq!(
+    ///     org.to_org(),
+    ///     "lorem ipsum\n*dolor sit* amet\nconsectetur\n[[https://example.com][adipiscing elit]]\n"
+    /// );
+    ///
+    /// let mut org = Org::parse("- lorem ipsum dolor sit amet");
+    /// let para = org.first_node::<Paragraph>().unwrap();
+    /// org.fill_paragraph(&para, 15);
+    /// assert_eq!(org.to_org(), "- lorem ipsum\n  dolor sit\n  amet");
+    /// ```
+    pub fn fill_paragraph(&mut self, para: &Paragraph, width: usize) {
+        let mut words: Vec<Word> = vec![];
+        let mut current = String::new();
+        let mut start = None;
+        let mut end = para.start();
+
+        for elem in para.syntax().children_with_tokens() {
+            match elem {
+                NodeOrToken::Node(n) if n.kind() == SyntaxKind::AFFILIATED_KEYWORD => {}
+                NodeOrToken::Token(t) if t.kind() == SyntaxKind::BLANK_LINE => {}
+                NodeOrToken::Token(t) => {
+                    let text = t.text();
+                    let offset = t.text_range().start();
+                    for (idx, ch) in text.char_indices() {
+                        if ch.is_whitespace() {
+                            take_word(&mut words, &mut current, false);
+                        } else {
+                            let pos = offset + TextSize::from(idx as u32);
+                            start.get_or_insert(pos);
+                            end = pos + TextSize::of(ch);
+                            current.push(ch);
+                        }
+                    }
+                }
+                NodeOrToken::Node(n) if n.kind() == SyntaxKind::LINE_BREAK => {
+                    start.get_or_insert(n.text_range().start());
+                    end = n.text_range().start() + TextSize::of("\\\\");
+                    current.push_str("\\\\");
+                    take_word(&mut words, &mut current, true);
+                }
+                NodeOrToken::Node(n) => {
+                    start.get_or_insert(n.text_range().start());
+                    end = n.text_range().end();
+                    // newlines inside an object become plain spaces
+                    let text = n.to_string();
+                    for (idx, line) in text.lines().enumerate() {
+                        if idx != 0 {
+                            current.push(' ');
+                            current.push_str(line.trim_start());
+                        } else {
+                            current.push_str(line);
+                        }
+                    }
+                }
+            }
+        }
+        take_word(&mut words, &mut current, false);
+
+        let Some(start) = start else {
+            return;
+        };
+
+        let text = self.to_org();
+        let line_start = text[..usize::from(start)]
+            .rfind(['\n', '\r'])
+            .map(|i| i + 1)
+            .unwrap_or(0);
+        let column = text[line_start..usize::from(start)].chars().count();
+
+        let indent = para
+            .syntax()
+            .parent()
+            .filter(|p| {
+                p.kind() == SyntaxKind::LIST_ITEM_CONTENT
+                    && p.first_child().as_ref() == Some(para.syntax())
+            })
+            .and_then(|p| p.parent())
+            .and_then(ListItem::cast)
+            .map(|item| {
+                format!(
+                    "{}{}",
+                    item.indent_token(),
+                    " ".repeat(item.bullet().chars().count())
+                )
+            })
+            .unwrap_or_else(|| text[line_start..usize::from(start)].to_string());
+
+        let mut filled = String::new();
+        let mut line_len = column;
+        let mut at_line_start = true;
+        for word in words {
+            let len = word.text.chars().count();
+            if !at_line_start {
+                if line_len + 1 + len <= width {
+                    filled.push(' ');
+                    line_len += 1;
+                } else {
+                    filled.push('\n');
+                    filled.push_str(&indent);
+                    line_len = indent.chars().count();
+                }
+            }
+            filled.push_str(&word.text);
+            line_len += len;
+            at_line_start = false;
+            if word.hard_break {
+                filled.push('\n');
+                filled.push_str(&indent);
+                line_len = indent.chars().count();
+                at_line_start = true;
+            }
+        }
+        if at_line_start {
+            // paragraph ends with a line break, drop the dangling indentation
+            filled.truncate(filled.len() - indent.len() - 1);
+        }
+
+        let range = TextRange::new(start, end);
+        if text[range] != filled {
+            self.replace_range(range, filled);
+        }
+    }
+}
+
+fn take_word(words: &mut Vec<Word>, current: &mut String, hard_break: bool) {
+    if !current.is_empty() {
+        words.push(Word {
+            text: std::mem::take(current),
+            hard_break,
+        });
+    } else if hard_break {
+        if let Some(last) = words.last_mut() {
+            last.hard_break = true;
+        }
+    }
+}
+
+#[test]
+fn fill() {
+    use crate::ast::Paragraph;
+
+    let fill = |input: &str, width: usize| {
+        let mut org = Org::parse(input);
+        let para = org.first_node::<Paragraph>().unwrap();
+        org.fill_paragraph(&para, width);
+        org.to_org()
+    };
+
+    assert_eq!(fill("a b c d e f", 3), "a b\nc d\ne f");
+    assert_eq!(fill("a\nb\nc\n\nd", 80), "a b c\n\nd");
+    assert_eq!(fill("  a b c d", 5), "  a b\n  c d");
+    assert_eq!(
+        fill("#+CAPTION: foo\na b c d\n", 3),
+        "#+CAPTION: foo\na b\nc d\n"
+    );
+    assert_eq!(fill("a *b\nc* d", 4), "a\n*b c*\nd");
+    assert_eq!(fill("a [[b][c d]] e", 80), "a [[b][c d]] e");
+    assert_eq!(fill("a\\\\\nb c d", 80), "a\\\\\nb c d");
+    assert_eq!(fill("a b\\\\\n", 80), "a b\\\\\n");
+    assert_eq!(fill("+ a b c d\n  e f", 7), "+ a b c\n  d e f");
+    assert_eq!(fill("+ [ ] a b c d", 9), "+ [ ] a b\n  c d");
+    assert_eq!(
+        fill("* headline\nlorem ipsum dolor\n* next", 12),
+        "* headline\nlorem ipsum\ndolor\n* next"
+    );
+}
@@ -0,0 +1,334 @@
+use rowan::ast::AstNode;
+
+use crate::ast::{Headline, Keyword};
+use crate::builder::escape_code;
+use crate::{Org, SyntaxKind};
+
+impl Org {
+    /// Shifts all headlines in this document so that the shallowest one
+    /// becomes level `min_level`, equivalent to `:minlevel` in `#+INCLUDE`
+    ///
+    /// Relative levels between headlines are preserved, so the outline keeps
+    /// the same structure. `min_level` less than one is treated as one.
+    ///
+    /// ```rust
+    /// use orgize::Org;
+    ///
+    /// let mut org = Org::parse("** a\n*** b\n** c");
+    /// org.shift_headlines(1);
+    /// assert_eq!(org.to_org(), "* a\n** b\n* c");
+    ///
+    /// let mut org = Org::parse("* a\n** b\n* c");
+    /// org.shift_headlines(3);
+    /// assert_eq!(org.to_org(), "*** a\n**** b\n*** c");
+    /// ```
+    pub fn shift_headlines(&mut self, min_level: usize) {
+        let min_level = min_level.max(1);
+
+        let headlines: Vec<Headline> = self
+            .document()
+            .syntax()
+            .descendants()
+            .filter_map(Headline::cast)
+            .collect();
+
+        let Some(current) = headlines.iter().map(|hdl| hdl.level()).min() else {
+            return;
+        };
+
+        if current == min_level {
+            return;
+        }
+
+        let edits = headlines
+            .iter()
+            .filter_map(|hdl| {
+                let stars = hdl
+                    .syntax()
+                    .children_with_tokens()
+                    .find(|e| e.kind() == SyntaxKind::HEADLINE_STARS)?;
+                let level = hdl.level() + min_level - current;
+                Some((stars.text_range(), "*".repeat(level)))
+            })
+            .collect();
+
+        self.replace_ranges(edits);
+    }
+
+    /// Replaces `#+INCLUDE` keywords with content returned by `loader`, then reparses
+    ///
+    /// `loader` receives the file name as written in the keyword and returns
+    /// its content, or `None` to leave the keyword as is. Relative paths are
+    /// not resolved, that's up to the loader.
+    ///
+    /// Content is wrapped in a block if block type is given, e.g.
+    /// `#+INCLUDE: "main.py" src python` or `#+INCLUDE: "log.txt" example`.
+    /// Source, example and export blocks have their content escaped.
+    /// `:lines "5-10"` keeps only the given lines, and `:minlevel` shifts
+    /// headlines of included org content, see [`Org::shift_headlines`].
+    ///
+    /// Included org content is resolved recursively. An include which would
+    /// load a file already being included is left as is, to avoid cycles.
+    ///
+    /// ```rust
+    /// use orgize::Org;
+    ///
+    /// let loader = |path: &str| match path {
+    ///     "chapter.org" => Some("* Chapter\n#+INCLUDE: \"code.py\" src python :lines \"2-\"\n".to_string()),
+    ///     "code.py" => Some("#!/usr/bin/env python\nprint(1)\n".to_string()),
+    ///     "loop.org" => Some("#+INCLUDE: \"loop.org\"\n".to_string()),
+    ///     _ => None,
+    /// };
+    ///
+    /// let mut org = Org::parse("#+INCLUDE: \"chapter.org\" :minlevel 2\ntext\n");
+    /// org.resolve_includes(loader);
+    /// assert_eq!(
+    ///     org.to_org(),
+    ///     "** Chapter\n#+BEGIN_SRC python\nprint(1)\n#+END_SRC\ntext\n"
+    /// );
+    ///
+    /// let mut org = Org::parse("#+INCLUDE: \"missing.org\"\n#+INCLUDE: loop.org\n");
+    /// org.resolve_includes(loader);
+    /// assert_eq!(org.to_org(), "#+INCLUDE: \"missing.org\"\n#+INCLUDE: \"loop.org\"\n");
+    /// ```
+    pub fn resolve_includes(&mut self, loader: impl Fn(&str) -> Option<String>) {
+        self.resolve_includes_with_stack(&loader, &mut vec![]);
+    }
+
+    /// Resolves includes, `stack` contains files currently being included
+    fn resolve_includes_with_stack(
+        &mut self,
+        loader: &dyn Fn(&str) -> Option<String>,
+        stack: &mut Vec<String>,
+    ) {
+        let keywords: Vec<Keyword> = self
+            .document()
+            .syntax()
+            .descendants()
+            .filter_map(Keyword::cast)
+            .filter(|keyword| keyword.key().eq_ignore_ascii_case("INCLUDE"))
+            .collect();
+
+        let edits = keywords
+            .iter()
+            .filter_map(|keyword| {
+                let include = Include::parse(keyword.value().as_ref())?;
+                if stack.contains(&include.path) {
+                    return None;
+                }
+                let content = loader(&include.path)?;
+                let content = include.select_lines(&content);
+
+                let mut replacement = match &include.block {
+                    Some((name, args)) => {
+                        let name = name.to_ascii_uppercase();
+                        let content = if matches!(name.as_str(), "SRC" | "EXAMPLE" | "EXPORT") {
+                            escape_code(&content)
+                        } else {
+                            content
+                        };
+                        let mut block = format!("#+BEGIN_{name}");
+                        if !args.is_empty() {
+                            block.push(' ');
+                            block.push_str(args);
+                        }
+                        block.push('\n');
+                        block.push_str(&content);
+                        if !block.ends_with('\n') {
+                            block.push('\n');
+                        }
+                        block.push_str(&format!("#+END_{name}"));
+                        block
+                    }
+                    None => {
+                        let mut org = self.config.clone().parse(content);
+                        stack.push(include.path.clone());
+                        org.resolve_includes_with_stack(loader, stack);
+                        stack.pop();
+                        if let Some(min_level) = include.min_level {
+                            org.shift_headlines(min_level);
+                        }
+                        org.to_org()
+                    }
+                };
+
+                let text = keyword.syntax().to_string();
+                if text.ends_with('\n') && !replacement.ends_with('\n') {
+                    replacement.push('\n');
+                }
+                Some((keyword.syntax().text_range(), replacement))
+            })
+            .collect();
+
+        self.replace_ranges(edits);
+    }
+}
+
+/// Parsed value of an `#+INCLUDE` keyword
+#[derive(Debug, Default, PartialEq)]
+struct Include {
+    path: String,
+    /// block name and its arguments, e.g. `("src", "python")`
+    block: Option<(String, String)>,
+    /// one-based inclusive line range, either end may be open
+    lines: Option<(Option<usize>, Option<usize>)>,
+    min_level: Option<usize>,
+}
+
+impl Include {
+    fn parse(value: &str) -> Option<Include> {
+        let mut words = split_words(value).into_iter();
+        let path = words.next()?;
+        let path = path
+            .strip_prefix('<')
+            .and_then(|p| p.strip_suffix('>'))
+            .unwrap_or(&path)
+            .to_string();
+
+        let mut include = Include {
+            path,
+            ..Default::default()
+        };
+
+        let mut block_words = vec![];
+        let mut words = words.peekable();
+        while let Some(word) = words.next_if(|w| !w.starts_with(':')) {
+            block_words.push(word);
+        }
+        if let Some((name, args)) = block_words.split_first() {
+            include.block = Some((name.clone(), args.join(" ")));
+        }
+
+        while let Some(key) = words.next() {
+            let value = words.next_if(|w| !w.starts_with(':'));
+            match (key.to_ascii_lowercase().as_str(), value) {
+                (":lines", Some(value)) => {
+                    include.lines = match value.split_once('-') {
+                        Some((start, end)) => {
+                            Some((start.trim().parse().ok(), end.trim().parse().ok()))
+                        }
+                        // a single line, or nothing if it isn't a number
+                        None => value
+                            .trim()
+                            .parse()
+                            .ok()
+                            .map(|line| (Some(line), Some(line))),
+                    };
+                }
+                (":minlevel", Some(value)) => include.min_level = value.parse().ok(),
+                _ => {}
+            }
+        }
+
+        Some(include)
+    }
+
+    fn select_lines(&self, content: &str) -> String {
+        let Some((start, end)) = self.lines else {
+            return content.to_string();
+        };
+        let start = start.unwrap_or(1).max(1);
+        content
+            .split_inclusive('\n')
+            .enumerate()
+            .filter(|(idx, _)| idx + 1 >= start && end.is_none_or(|end| *idx < end))
+            .map(|(_, line)| line)
+            .collect()
+    }
+}
+
+/// Splits by whitespace, double-quoted strings are kept as one word without quotes
+fn split_words(value: &str) -> Vec<String> {
+    let mut words = vec![];
+    let mut chars = value.trim().chars().peekable();
+    while let Some(&c) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+        } else if c == '"' {
+            chars.next();
+            let mut word = String::new();
+            while let Some(c) = chars.next() {
+                match c {
+                    '"' => break,
+                    '\\' => word.extend(chars.next()),
+                    _ => word.push(c),
+                }
+            }
+            words.push(word);
+        } else {
+            let mut word = String::new();
+            while let Some(c) = chars.next_if(|c| !c.is_whitespace()) {
+                word.push(c);
+            }
+            words.push(word);
+        }
+    }
+    words
+}
+
+#[test]
+fn shift_headlines() {
+    let shift = |input: &str, min_level: usize| {
+        let mut org = Org::parse(input);
+        org.shift_headlines(min_level);
+        org.to_org()
+    };
+
+    assert_eq!(shift("", 2), "");
+    assert_eq!(shift("no headline", 2), "no headline");
+    assert_eq!(shift("* a", 0), "* a");
+    assert_eq!(shift("* a", 1), "* a");
+
+    // shifting down
+    assert_eq!(shift("* a\n** b\n* c", 2), "** a\n*** b\n** c");
+    assert_eq!(
+        shift("text\n* a\nb\n*** c\n** d", 2),
+        "text\n** a\nb\n**** c\n*** d"
+    );
+
+    // shifting up
+    assert_eq!(shift("*** a\n**** b\n*** c", 1), "* a\n** b\n* c");
+    assert_eq!(shift("**** a\n** b", 1), "*** a\n* b");
+
+    let mut org = Org::parse("*** a\n**** b\n*** c");
+    org.shift_headlines(1);
+    assert_eq!(
+        format!("{:#?}", org.document().syntax()),
+        format!("{:#?}", Org::parse("* a\n** b\n* c").document().syntax())
+    );
+}
+
+#[test]
+fn parse_include() {
+    assert_eq!(Include::parse(""), None);
+    assert_eq!(
+        Include::parse(r#" "a b.org""#).unwrap(),
+        Include {
+            path: "a b.org".into(),
+            ..Default::default()
+        }
+    );
+    assert_eq!(
+        Include::parse(r#" <main.py> src python -n :lines "5-10" :minlevel 2"#).unwrap(),
+        Include {
+            path: "main.py".into(),
+            block: Some(("src".into(), "python -n".into())),
+            lines: Some((Some(5), Some(10))),
+            min_level: Some(2),
+        }
+    );
+    assert_eq!(
+        Include::parse(r#" "a.txt" :lines "-3""#).unwrap().lines,
+        Some((None, Some(3)))
+    );
+
+    let include = Include::parse(r#" "a.txt" :lines "2-3""#).unwrap();
+    assert_eq!(include.select_lines("1\n2\n3\n4\n"), "2\n3\n");
+    let include = Include::parse(r#" "a.txt" :lines "3-""#).unwrap();
+    assert_eq!(include.select_lines("1\n2\n3\n4"), "3\n4");
+    let include = Include::parse(r#" "a.txt" :lines "2" :minlevel 2"#).unwrap();
+    assert_eq!(include.min_level, Some(2));
+    assert_eq!(include.select_lines("1\n2\n3\n4"), "2\n");
+    let include = Include::parse(r#" "a.txt" :lines "x""#).unwrap();
+    assert_eq!(include.lines, None);
+}
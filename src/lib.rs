@@ -1,20 +1,34 @@
 #![doc = include_str!("../README.md")]
 
 pub mod ast;
+mod babel;
+mod builder;
 pub mod config;
-mod entities;
+mod date;
+mod dyn_block;
+pub mod entities;
 pub mod export;
+mod fill;
+mod include;
+mod list;
 mod org;
+mod outline;
+mod property;
 mod replace;
 mod syntax;
 #[cfg(test)]
 mod tests;
+mod toc;
+mod todo;
 
 // Re-export of the rowan crate.
 pub use rowan;
 
+pub use builder::OrgBuilder;
 pub use config::ParseConfig;
 pub use org::Org;
+pub use outline::PromoteError;
+pub use replace::EditError;
 pub use rowan::{TextRange, TextSize};
 pub use syntax::{
     SyntaxElement, SyntaxElementChildren, SyntaxKind, SyntaxNode, SyntaxNodeChildren, SyntaxToken,
@@ -0,0 +1,54 @@
+use rowan::ast::AstNode;
+
+use crate::ast::{CounterDelimiter, ListBullet, ListItem};
+use crate::Org;
+
+impl Org {
+    /// Rewrites bullets of all list items to `-` and ordered counters to `1.`
+    /// style, see [`Org::normalize_lists_with`]
+    ///
+    /// ```rust
+    /// use orgize::Org;
+    ///
+    /// let mut org = Org::parse("+ a\n  * b\n  2) c\n- d");
+    /// org.normalize_lists();
+    /// assert_eq!(org.to_org(), "- a\n  - b\n  2. c\n- d");
+    /// ```
+    pub fn normalize_lists(&mut self) {
+        self.normalize_lists_with(ListBullet::default(), CounterDelimiter::default());
+    }
+
+    /// Rewrites bullets of all list items with given bullet and counter delimiter
+    ///
+    /// Counters and indentation are left untouched. Since a star at the
+    /// beginning of line starts a headline, top-level items use `-` instead
+    /// of `*`.
+    ///
+    /// ```rust
+    /// use orgize::{Org, ast::{CounterDelimiter, ListBullet}};
+    ///
+    /// let mut org = Org::parse("- a\n  + b\n    1. c\n\n10. d\n");
+    /// org.normalize_lists_with(ListBullet::Star, CounterDelimiter::Parenthesis);
+    /// assert_eq!(org.to_org(), "- a\n  * b\n    1) c\n\n10) d\n");
+    /// ```
+    pub fn normalize_lists_with(&mut self, bullet: ListBullet, delimiter: CounterDelimiter) {
+        let edits = self
+            .document()
+            .syntax()
+            .descendants()
+            .filter_map(ListItem::cast)
+            .filter_map(|item| {
+                let bullet = if item.indent() == 0 && bullet == ListBullet::Star {
+                    ListBullet::Minus
+                } else {
+                    bullet
+                };
+                let old = item.bullet();
+                let new = item.restyled_bullet(Some(bullet), Some(delimiter));
+                (old.as_ref() != new).then(|| (old.syntax().text_range(), new))
+            })
+            .collect();
+
+        self.replace_ranges(edits);
+    }
+}
@@ -1,11 +1,15 @@
 use rowan::ast::AstNode;
-use rowan::{GreenNode, TextSize};
+use rowan::{GreenNode, TextSize, TokenAtOffset};
+use std::collections::HashSet;
+use std::str::Utf8Error;
 
-use crate::ast::Document;
+use crate::ast::{Document, OptionValue, Timestamp};
 use crate::config::ParseConfig;
-use crate::export::{HtmlExport, TraversalContext, Traverser};
+use crate::export::{
+    Container, Event, HtmlExport, IcalExport, PlainTextExport, TraversalContext, Traverser,
+};
 use crate::syntax::{OrgLanguage, SyntaxNode};
-use crate::SyntaxElement;
+use crate::{SyntaxElement, SyntaxKind, SyntaxToken};
 
 #[derive(Debug)]
 pub struct Org {
@@ -19,6 +23,21 @@ impl Org {
         ParseConfig::default().parse(input)
     }
 
+    /// Parse UTF-8 bytes to Org element tree using default parse config
+    ///
+    /// See [`ParseConfig::parse_bytes`].
+    pub fn parse_bytes(input: &[u8]) -> Result<Org, Utf8Error> {
+        ParseConfig::default().parse_bytes(input)
+    }
+
+    /// Parse bytes to Org element tree using default parse config, replacing
+    /// invalid UTF-8 sequences
+    ///
+    /// See [`ParseConfig::parse_bytes_lossy`].
+    pub fn parse_bytes_lossy(input: &[u8]) -> Org {
+        ParseConfig::default().parse_bytes_lossy(input)
+    }
+
     pub fn green(&self) -> &GreenNode {
         &self.green
     }
@@ -40,8 +59,90 @@ impl Org {
     }
 
     /// Convert org element tree to html-format using default html handler
+    ///
+    /// Link abbreviations, user-defined entities and the parse config itself are used,
+    /// and `#+OPTIONS` items `\n:t`, `':t` and `d:t` enable preserving line
+    /// breaks, smart quotes and rendering drawers respectively. `num:t` or
+    /// `num:N` prepends section numbers to headlines.
     pub fn to_html(&self) -> String {
-        let mut handler = HtmlExport::default();
+        let options = self.options();
+        let enabled = |key: &str| options.get(key) == Some(&OptionValue::Bool(true));
+        let mut handler = HtmlExport::default()
+            .with_link_abbreviations(self.config.link_abbreviations.clone())
+            .with_user_entities(self.config.user_entities.clone())
+            .with_parse_config(self.config.clone())
+            .with_preserve_line_breaks(enabled("\\n"))
+            .with_smart_quotes(enabled("'"))
+            .with_render_drawers(enabled("d"))
+            .with_section_numbers(match options.get("num") {
+                Some(OptionValue::Bool(true)) => usize::MAX,
+                Some(OptionValue::Integer(depth)) => *depth,
+                _ => 0,
+            });
+        self.traverse(&mut handler);
+        handler.finish()
+    }
+
+    /// Convert org element tree to plain text with all markup stripped, see [`PlainTextExport`]
+    ///
+    /// ```rust
+    /// use orgize::Org;
+    ///
+    /// let org = Org::parse("* Hello *world*\nThis is /a/ [[https://example.com][link]].");
+    /// assert_eq!(org.to_plain_text(), "Hello world\nThis is a link.\n");
+    /// ```
+    pub fn to_plain_text(&self) -> String {
+        let mut handler =
+            PlainTextExport::default().with_user_entities(self.config.user_entities.clone());
+        self.traverse(&mut handler);
+        handler.finish()
+    }
+
+    /// Convert org element tree to iCalendar, see [`IcalExport`]
+    ///
+    /// ```rust
+    /// use orgize::Org;
+    ///
+    /// let org = Org::parse("* Trip\n<2024-01-02 Tue>--<2024-01-04 Thu>");
+    /// let ical = org.to_ical();
+    /// assert!(ical.contains("BEGIN:VEVENT\r\n"));
+    /// assert!(ical.contains("DTSTART;VALUE=DATE:20240102\r\nDTEND;VALUE=DATE:20240105\r\n"));
+    ///
+    /// let org = Org::parse("* TODO Trip\n<2024-01-02 Tue 10:00>--<2024-01-04 Thu 18:00>");
+    /// let ical = org.to_ical();
+    /// assert!(ical.contains("DTSTART:20240102T100000\r\nDUE:20240104T180000\r\n"));
+    /// assert!(!ical.contains("DTEND"));
+    ///
+    /// // date-only due is inclusive, whether from a deadline or from end of a range
+    /// let org = Org::parse("* TODO Trip\n<2024-01-02 Tue>--<2024-01-04 Thu>");
+    /// assert!(org.to_ical().contains("DTSTART;VALUE=DATE:20240102\r\nDUE;VALUE=DATE:20240104\r\n"));
+    /// let org = Org::parse("* TODO Trip\nSCHEDULED: <2024-01-02 Tue> DEADLINE: <2024-01-04 Thu>");
+    /// assert!(org.to_ical().contains("DTSTART;VALUE=DATE:20240102\r\nDUE;VALUE=DATE:20240104\r\n"));
+    ///
+    /// let org = Org::parse(
+    ///     "* DONE Pay rent, bills\nSCHEDULED: <2024-01-01 Mon 09:00 +1m> DEADLINE: <2024-01-05 Fri>\n:PROPERTIES:\n:ID: rent\n:END:",
+    /// );
+    /// assert_eq!(
+    ///     org.to_ical()
+    ///         .lines()
+    ///         .skip(4)
+    ///         .filter(|line| !line.starts_with("DTSTAMP:"))
+    ///         .collect::<Vec<_>>(),
+    ///     [
+    ///         "BEGIN:VTODO",
+    ///         "UID:rent",
+    ///         "SUMMARY:Pay rent\\, bills",
+    ///         "DTSTART:20240101T090000",
+    ///         "RRULE:FREQ=MONTHLY;INTERVAL=1",
+    ///         "DUE:20240106T000000",
+    ///         "STATUS:COMPLETED",
+    ///         "END:VTODO",
+    ///         "END:VCALENDAR",
+    ///     ]
+    /// );
+    /// ```
+    pub fn to_ical(&self) -> String {
+        let mut handler = IcalExport::default();
         self.traverse(&mut handler);
         handler.finish()
     }
@@ -55,6 +156,69 @@ impl Org {
         );
     }
 
+    /// Walk through org element tree, but only emits events of given syntax kinds
+    ///
+    /// Both `Event::Enter` and `Event::Leave` are emitted for a wanted container.
+    /// Subtrees without any element of given kinds aren't walked at all. Unlike
+    /// [`Org::traverse`], objects in headline and inline task titles, and
+    /// timestamps in planning lines are visited as well, so it finds the same
+    /// nodes as [`Org::nodes`]. Timestamps inside clocks only come with
+    /// `Event::Clock`.
+    ///
+    /// ```rust
+    /// use orgize::{ast::Link, export::{from_fn, Event}, rowan::ast::AstNode, Org, SyntaxKind};
+    ///
+    /// let org = Org::parse("* [[a]] <2024-01-01>\n[[b]] *[[c]]*\n* x\n** [[d]]");
+    /// let mut links = vec![];
+    /// org.traverse_filtered(
+    ///     &[SyntaxKind::LINK],
+    ///     &mut from_fn(|event| {
+    ///         if let Event::Enter(container) = event {
+    ///             links.push(container.syntax().to_string());
+    ///         }
+    ///     }),
+    /// );
+    /// assert_eq!(links, vec!["[[a]]", "[[b]]", "[[c]]", "[[d]]"]);
+    ///
+    /// let nodes: Vec<_> = org.nodes::<Link>().map(|link| link.syntax().to_string()).collect();
+    /// assert_eq!(links, nodes);
+    ///
+    /// let org = Org::parse("* <2024-01-01>\nSCHEDULED: <2024-01-02>\n<2024-01-03>");
+    /// let mut timestamps = vec![];
+    /// org.traverse_filtered(
+    ///     &[SyntaxKind::TIMESTAMP_ACTIVE],
+    ///     &mut from_fn(|event| {
+    ///         if let Event::Timestamp(ts) = event {
+    ///             timestamps.push(ts.syntax().to_string());
+    ///         }
+    ///     }),
+    /// );
+    /// assert_eq!(timestamps, vec!["<2024-01-01>", "<2024-01-02>", "<2024-01-03>"]);
+    /// ```
+    pub fn traverse_filtered<T: Traverser>(&self, kinds: &[SyntaxKind], t: &mut T) {
+        let root = SyntaxNode::new_root(self.green.clone());
+        let mut ctx = TraversalContext::default();
+        Filtered::new(&root, kinds, t).element(SyntaxElement::Node(root), &mut ctx);
+    }
+
+    /// Returns an iterator of all nodes of given type in depth first order
+    ///
+    /// ```rust
+    /// use orgize::{Org, ast::Timestamp, rowan::ast::AstNode};
+    ///
+    /// let org = Org::parse("* a <2024-01-01>\n[2024-02-02]\n** b <2024-03-03>");
+    /// let timestamps: Vec<_> = org
+    ///     .nodes::<Timestamp>()
+    ///     .map(|ts| ts.syntax().to_string())
+    ///     .collect();
+    /// assert_eq!(timestamps, vec!["<2024-01-01>", "[2024-02-02]", "<2024-03-03>"]);
+    /// ```
+    pub fn nodes<N: AstNode<Language = OrgLanguage>>(&self) -> impl Iterator<Item = N> {
+        SyntaxNode::new_root(self.green.clone())
+            .descendants()
+            .filter_map(N::cast)
+    }
+
     /// Returns the first node in org element tree in depth first order
     pub fn first_node<N: AstNode<Language = OrgLanguage>>(&self) -> Option<N> {
         fn find<N: AstNode<Language = OrgLanguage>>(node: SyntaxNode) -> Option<N> {
@@ -103,4 +267,104 @@ impl Org {
         }
         find(SyntaxNode::new_root(self.green.clone()), offset)
     }
+
+    /// Returns token in given offset
+    ///
+    /// If offset falls on the boundary of two tokens, the one starting at
+    /// offset is returned. The last token is returned at the end of file, and
+    /// `None` past it.
+    ///
+    /// ```rust
+    /// use orgize::{Org, SyntaxKind};
+    ///
+    /// let org = Org::parse("* foo *bar*");
+    ///
+    /// let token = org.token_at_offset(0).unwrap();
+    /// assert_eq!(token.kind(), SyntaxKind::HEADLINE_STARS);
+    ///
+    /// // boundary of `*` and `bar`
+    /// let token = org.token_at_offset(7).unwrap();
+    /// assert_eq!(token.text(), "bar");
+    ///
+    /// let token = org.token_at_offset(11).unwrap();
+    /// assert_eq!(token.kind(), SyntaxKind::STAR);
+    ///
+    /// assert!(org.token_at_offset(12).is_none());
+    /// assert!(Org::parse("").token_at_offset(0).is_none());
+    /// ```
+    pub fn token_at_offset(&self, offset: impl Into<TextSize>) -> Option<SyntaxToken> {
+        let offset = offset.into();
+        let root = SyntaxNode::new_root(self.green.clone());
+        if offset > root.text_range().end() {
+            return None;
+        }
+        match root.token_at_offset(offset) {
+            TokenAtOffset::None => None,
+            TokenAtOffset::Single(token) => Some(token),
+            // right token always contains the offset
+            TokenAtOffset::Between(_, right) => Some(right),
+        }
+    }
+}
+
+/// Traverser forwarding only events of given syntax kinds
+struct Filtered<'a, T> {
+    kinds: &'a [SyntaxKind],
+    /// nodes containing an element of given kinds, other subtrees are pruned
+    containing: HashSet<SyntaxNode>,
+    inner: &'a mut T,
+}
+
+impl<'a, T> Filtered<'a, T> {
+    fn new(root: &SyntaxNode, kinds: &'a [SyntaxKind], inner: &'a mut T) -> Self {
+        let mut containing = HashSet::new();
+        for element in root.descendants_with_tokens() {
+            if !kinds.contains(&element.kind()) {
+                continue;
+            }
+            for node in element.ancestors() {
+                if !containing.insert(node) {
+                    break;
+                }
+            }
+        }
+        Filtered {
+            kinds,
+            containing,
+            inner,
+        }
+    }
+}
+
+impl<T: Traverser> Traverser for Filtered<'_, T> {
+    fn event(&mut self, event: Event, ctx: &mut TraversalContext) {
+        // titles and planning lines aren't walked by the traversal itself
+        let (title, planning) = match &event {
+            Event::Enter(Container::Headline(hdl)) => (hdl.title().collect(), hdl.planning()),
+            Event::Enter(Container::InlineTask(task)) => (task.title().collect(), task.planning()),
+            _ => (vec![], None),
+        };
+        let planning = planning
+            .iter()
+            .flat_map(|planning| planning.syntax().descendants())
+            .filter(|node| Timestamp::can_cast(node.kind()))
+            .map(SyntaxElement::Node);
+        let pruned = match &event {
+            Event::Enter(container) => !self.containing.contains(container.syntax()),
+            _ => false,
+        };
+
+        if self.kinds.contains(&event.kind()) {
+            self.inner.event(event, ctx);
+        } else if pruned {
+            return ctx.skip();
+        }
+
+        for element in title.into_iter().chain(planning) {
+            if !ctx.is_continue() {
+                break;
+            }
+            self.element(element, ctx);
+        }
+    }
 }
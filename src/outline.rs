@@ -0,0 +1,145 @@
+use std::fmt;
+
+use rowan::{ast::AstNode, TextRange};
+
+use crate::ast::{filter_token, Headline};
+use crate::{Org, SyntaxKind};
+
+/// Reason why a headline can't be promoted
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PromoteError {
+    /// Headline is already at level 1
+    TopLevel,
+}
+
+impl fmt::Display for PromoteError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PromoteError::TopLevel => write!(f, "cannot promote a level-1 headline"),
+        }
+    }
+}
+
+impl std::error::Error for PromoteError {}
+
+impl Org {
+    /// Decreases level of given headline by one, equivalent to `org-promote`
+    ///
+    /// Descendant headlines keep their levels, so use [`Org::promote_subtree`]
+    /// to move the whole subtree.
+    ///
+    /// ```rust
+    /// use orgize::{Org, PromoteError, ast::Headline};
+    ///
+    /// let mut org = Org::parse("* a\n** b\n*** c\n");
+    /// let b = org.first_node::<Headline>().unwrap().headlines().next().unwrap();
+    /// assert_eq!(org.promote(&b), Ok(()));
+    /// assert_eq!(org.to_org(), "* a\n* b\n*** c\n");
+    ///
+    /// let a = org.first_node::<Headline>().unwrap();
+    /// assert_eq!(org.promote(&a), Err(PromoteError::TopLevel));
+    /// assert_eq!(org.to_org(), "* a\n* b\n*** c\n");
+    /// ```
+    pub fn promote(&mut self, headline: &Headline) -> Result<(), PromoteError> {
+        let level = headline.level();
+        if level == 1 {
+            return Err(PromoteError::TopLevel);
+        }
+        self.change_levels(headline, level - 1, false);
+        Ok(())
+    }
+
+    /// Increases level of given headline by one, equivalent to `org-demote`
+    ///
+    /// ```rust
+    /// use orgize::{Org, ast::Headline};
+    ///
+    /// let mut org = Org::parse("* a\n* b\n** c\n");
+    /// let b = org.document().headlines().nth(1).unwrap();
+    /// org.demote(&b);
+    /// assert_eq!(org.to_org(), "* a\n** b\n** c\n");
+    /// assert_eq!(org.first_node::<Headline>().unwrap().headlines().count(), 2);
+    /// ```
+    pub fn demote(&mut self, headline: &Headline) {
+        self.change_levels(headline, headline.level() + 1, false);
+    }
+
+    /// Decreases level of given headline and all its descendants by one,
+    /// equivalent to `org-promote-subtree`
+    ///
+    /// ```rust
+    /// use orgize::{Org, PromoteError, ast::Headline};
+    ///
+    /// let mut org = Org::parse("* a\n** b\n*** c\n**** d\n** e\n");
+    /// let b = org.first_node::<Headline>().unwrap().headlines().next().unwrap();
+    /// assert_eq!(org.promote_subtree(&b), Ok(()));
+    /// assert_eq!(org.to_org(), "* a\n* b\n** c\n*** d\n** e\n");
+    ///
+    /// let a = org.first_node::<Headline>().unwrap();
+    /// assert_eq!(org.promote_subtree(&a), Err(PromoteError::TopLevel));
+    /// ```
+    pub fn promote_subtree(&mut self, headline: &Headline) -> Result<(), PromoteError> {
+        let level = headline.level();
+        if level == 1 {
+            return Err(PromoteError::TopLevel);
+        }
+        self.change_levels(headline, level - 1, true);
+        Ok(())
+    }
+
+    /// Increases level of given headline and all its descendants by one,
+    /// equivalent to `org-demote-subtree`
+    ///
+    /// ```rust
+    /// use orgize::{Org, ast::Headline};
+    ///
+    /// let mut org = Org::parse("* a\n* b\n** c\n* d");
+    /// let b = org.document().headlines().nth(1).unwrap();
+    /// org.demote_subtree(&b);
+    /// assert_eq!(org.to_org(), "* a\n** b\n*** c\n* d");
+    /// ```
+    pub fn demote_subtree(&mut self, headline: &Headline) {
+        self.change_levels(headline, headline.level() + 1, true);
+    }
+
+    /// Sets level of given headline, and shifts levels of its descendants by
+    /// the same amount if `subtree` is true
+    fn change_levels(&mut self, headline: &Headline, new_level: usize, subtree: bool) {
+        let level = headline.level();
+        let headlines: Vec<Headline> = if subtree {
+            headline
+                .syntax
+                .descendants()
+                .filter_map(Headline::cast)
+                .collect()
+        } else {
+            vec![headline.clone()]
+        };
+
+        let edits = headlines
+            .iter()
+            .filter_map(|hdl| {
+                let stars = hdl
+                    .syntax
+                    .children_with_tokens()
+                    .find_map(filter_token(SyntaxKind::HEADLINE_STARS))?;
+                let new = stars.as_ref().len() + new_level - level;
+                Some((stars.text_range(), "*".repeat(new)))
+            })
+            .collect::<Vec<(TextRange, String)>>();
+
+        // changing level may move following headlines in or out of this
+        // headline, so reparse the closest ancestor which still contains it
+        let ancestor = headline
+            .syntax
+            .ancestors()
+            .skip(1)
+            .filter_map(Headline::cast)
+            .find(|hdl| hdl.level() < new_level);
+
+        match ancestor {
+            Some(ancestor) => self.edit_headline(&ancestor, edits),
+            None => self.replace_ranges(edits),
+        }
+    }
+}
@@ -0,0 +1,171 @@
+use rowan::{ast::AstNode, TextRange};
+
+use crate::ast::{Headline, PropertyDrawer, Token};
+use crate::{Org, SyntaxKind, SyntaxNode};
+
+impl Org {
+    /// Sets property of given headline, equivalent to `org-entry-put`
+    ///
+    /// Existing property with the same key, compared case-insensitively, is
+    /// replaced. Otherwise a new line is appended to the property drawer, and
+    /// the drawer itself is created right after the planning line if absent.
+    /// Indentation of the existing drawer or planning line is preserved.
+    ///
+    /// Properties can't span multiple lines, so line breaks are removed from
+    /// the key and replaced with spaces in the value.
+    ///
+    /// ```rust
+    /// use orgize::{Org, ast::Headline};
+    ///
+    /// let mut org = Org::parse("* TODO [#A] a :tag:\nSCHEDULED: <2024-01-01>\ntext\n");
+    /// let hdl = org.first_node::<Headline>().unwrap();
+    /// org.set_property(&hdl, "CUSTOM_ID", "intro");
+    /// assert_eq!(
+    ///     org.to_org(),
+    ///     "* TODO [#A] a :tag:\nSCHEDULED: <2024-01-01>\n:PROPERTIES:\n:CUSTOM_ID: intro\n:END:\ntext\n"
+    /// );
+    ///
+    /// let hdl = org.first_node::<Headline>().unwrap();
+    /// org.set_property(&hdl, "custom_id", "outro");
+    /// org.set_property(&org.first_node::<Headline>().unwrap(), "ID", "1");
+    /// assert_eq!(
+    ///     org.to_org(),
+    ///     "* TODO [#A] a :tag:\nSCHEDULED: <2024-01-01>\n:PROPERTIES:\n:CUSTOM_ID: outro\n:ID: 1\n:END:\ntext\n"
+    /// );
+    ///
+    /// let mut org = Org::parse("* a\n  :PROPERTIES:\n  :ID: 1\n  :END:\n** b");
+    /// org.set_property(&org.first_node::<Headline>().unwrap(), "CATEGORY", "work");
+    /// assert_eq!(
+    ///     org.to_org(),
+    ///     "* a\n  :PROPERTIES:\n  :ID: 1\n  :CATEGORY: work\n  :END:\n** b"
+    /// );
+    /// let hdl = org.first_node::<Headline>().unwrap();
+    /// assert_eq!(hdl.property("CATEGORY").unwrap(), "work");
+    ///
+    /// let mut org = Org::parse("* a");
+    /// org.set_property(&org.first_node::<Headline>().unwrap(), "ID", "1");
+    /// assert_eq!(org.to_org(), "* a\n:PROPERTIES:\n:ID: 1\n:END:\n");
+    ///
+    /// let mut org = Org::parse("* a");
+    /// org.set_property(&org.first_node::<Headline>().unwrap(), "NO\nTE", "a\r\n:END:\nb");
+    /// assert_eq!(org.to_org(), "* a\n:PROPERTIES:\n:NOTE: a :END: b\n:END:\n");
+    /// ```
+    pub fn set_property(&mut self, headline: &Headline, key: &str, value: &str) {
+        let key = key.replace(['\r', '\n'], "");
+        let key = key.as_str();
+        let value = value.trim().replace("\r\n", " ").replace(['\r', '\n'], " ");
+        let value = value.as_str();
+        let edit = match headline.properties() {
+            Some(drawer) => match find_property(&drawer, key) {
+                Some((existing_key, node)) => {
+                    let (indent, newline) = split_line(&node);
+                    (
+                        node.text_range(),
+                        format_property(&indent, existing_key.as_ref(), value, &newline),
+                    )
+                }
+                None => {
+                    let end = drawer
+                        .syntax()
+                        .children()
+                        .find(|n| n.kind() == SyntaxKind::DRAWER_END);
+                    let indent = drawer
+                        .syntax()
+                        .first_child()
+                        .map(|begin| split_line(&begin).0)
+                        .unwrap_or_default();
+                    let offset = end.map_or(drawer.end(), |n| n.text_range().start());
+                    (
+                        TextRange::empty(offset),
+                        format_property(&indent, key, value, "\n"),
+                    )
+                }
+            },
+            None => {
+                let (offset, indent, needs_newline) = match headline.planning() {
+                    Some(planning) => {
+                        let text = planning.syntax().to_string();
+                        let indent: String = text
+                            .chars()
+                            .take_while(|c| *c == ' ' || *c == '\t')
+                            .collect();
+                        (planning.end(), indent, !text.ends_with(['\n', '\r']))
+                    }
+                    None => match headline
+                        .syntax()
+                        .children_with_tokens()
+                        .find(|e| e.kind() == SyntaxKind::NEW_LINE)
+                    {
+                        Some(newline) => (newline.text_range().end(), String::new(), false),
+                        None => (headline.end(), String::new(), true),
+                    },
+                };
+                let mut text = String::new();
+                if needs_newline {
+                    text.push('\n');
+                }
+                text.push_str(&format!("{indent}:PROPERTIES:\n"));
+                text.push_str(&format_property(&indent, key, value, "\n"));
+                text.push_str(&format!("{indent}:END:\n"));
+                (TextRange::empty(offset), text)
+            }
+        };
+
+        self.edit_headline(headline, vec![edit]);
+    }
+
+    /// Removes property of given headline, key is compared case-insensitively
+    ///
+    /// Returns `false` if headline has no such property. The property drawer
+    /// is kept even if it becomes empty.
+    ///
+    /// ```rust
+    /// use orgize::{Org, ast::Headline};
+    ///
+    /// let mut org = Org::parse("* a\n:PROPERTIES:\n:ID: 1\n:CUSTOM_ID: a\n:END:\n");
+    /// let hdl = org.first_node::<Headline>().unwrap();
+    /// assert!(org.remove_property(&hdl, "id"));
+    /// assert_eq!(org.to_org(), "* a\n:PROPERTIES:\n:CUSTOM_ID: a\n:END:\n");
+    ///
+    /// let hdl = org.first_node::<Headline>().unwrap();
+    /// assert!(!org.remove_property(&hdl, "ID"));
+    /// ```
+    pub fn remove_property(&mut self, headline: &Headline, key: &str) -> bool {
+        let Some((_, node)) = headline
+            .properties()
+            .and_then(|drawer| find_property(&drawer, key))
+        else {
+            return false;
+        };
+
+        self.edit_headline(headline, vec![(node.text_range(), String::new())]);
+        true
+    }
+}
+
+/// Finds the key and `NODE_PROPERTY` node of given property
+fn find_property(drawer: &PropertyDrawer, key: &str) -> Option<(Token, SyntaxNode)> {
+    drawer
+        .iter()
+        .find(|(k, _)| k.as_ref().eq_ignore_ascii_case(key))
+        .and_then(|(k, _)| Some((k.clone(), k.syntax().parent()?)))
+}
+
+/// Returns leading whitespace and line ending of given line
+fn split_line(node: &SyntaxNode) -> (String, String) {
+    let text = node.to_string();
+    let content = text.trim_start_matches([' ', '\t']);
+    let trimmed = content.trim_end_matches(['\n', '\r']);
+    (
+        text[..text.len() - content.len()].into(),
+        content[trimmed.len()..].into(),
+    )
+}
+
+fn format_property(indent: &str, key: &str, value: &str, newline: &str) -> String {
+    if value.is_empty() {
+        format!("{indent}:{key}:{newline}")
+    } else {
+        format!("{indent}:{key}: {value}{newline}")
+    }
+}
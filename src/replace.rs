@@ -1,3 +1,5 @@
+use std::fmt;
+
 use rowan::{
     ast::{support, AstNode},
     SyntaxNode, TextRange, TextSize, TokenAtOffset,
@@ -9,6 +11,26 @@ use crate::syntax::{
 };
 use crate::Org;
 
+/// Reason why [`Org::apply_edits`] fails
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EditError {
+    /// Range is out of bounds, or not at char boundaries
+    InvalidRange(TextRange),
+    /// Two ranges overlap with each other
+    Overlapping(TextRange, TextRange),
+}
+
+impl fmt::Display for EditError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EditError::InvalidRange(range) => write!(f, "invalid range {range:?}"),
+            EditError::Overlapping(a, b) => write!(f, "range {a:?} overlaps with {b:?}"),
+        }
+    }
+}
+
+impl std::error::Error for EditError {}
+
 #[derive(Debug)]
 enum RangeShape {
     InsideHeadline { headline: Headline, level: usize },
@@ -64,7 +86,7 @@ impl ReplaceWithShape {
                 continue;
             }
 
-            if !matches!(text[start..].as_bytes().get(level), Some(b' ')) {
+            if !matches!(text.as_bytes()[start..].get(level), Some(b' ')) {
                 continue;
             }
 
@@ -99,6 +121,9 @@ impl Org {
     /// This method optimizes parsing by analyzing the selected range and given text, and reducing
     /// the amount of data processed by parser.
     ///
+    /// Returns range of the reparsed region in the new syntax tree: the new headline if only one
+    /// headline was reparsed, otherwise the whole document.
+    ///
     /// ```rust
     /// use orgize::{Org, ast::Headline, TextRange, TextSize};
     ///
@@ -107,12 +132,21 @@ impl Org {
     /// assert_eq!(hdl.level(), 2);
     ///
     /// // replace '**' with '*****'
-    /// org.replace_range(TextRange::new(0.into(), 2.into()), "*****");
+    /// let changed = org.replace_range(TextRange::new(0.into(), 2.into()), "*****");
     /// // since the syntax tree is changed, we have to query again
     /// let hdl = org.first_node::<Headline>().unwrap();
     /// assert_eq!(hdl.level(), 5);
+    /// assert_eq!(changed, hdl.text_range());
+    ///
+    /// let mut org = Org::parse("* a\n** b\n* c");
+    /// // only the second headline is reparsed
+    /// let changed = org.replace_range(TextRange::new(4.into(), 9.into()), "** bbb\n");
+    /// assert_eq!(changed, TextRange::new(4.into(), 11.into()));
+    /// // the whole document is reparsed
+    /// let changed = org.replace_range(TextRange::new(0.into(), 0.into()), "text\n");
+    /// assert_eq!(changed, TextRange::new(0.into(), 19.into()));
     /// ```
-    pub fn replace_range(&mut self, range: TextRange, replace_with: impl AsRef<str>) {
+    pub fn replace_range(&mut self, range: TextRange, replace_with: impl AsRef<str>) -> TextRange {
         let replace_with = replace_with.as_ref();
         match (
             RangeShape::new(self.document().syntax, range),
@@ -149,7 +183,198 @@ impl Org {
         }
     }
 
-    fn full_parse(&mut self, range: TextRange, replace_with: &str) {
+    /// Inserts text at given offset, see [`Org::replace_range`]
+    ///
+    /// Returns `None` without changing the document if offset is out of bounds or
+    /// not at a char boundary.
+    ///
+    /// ```rust
+    /// use orgize::{Org, TextRange};
+    ///
+    /// let mut org = Org::parse("* a\n");
+    /// let changed = org.insert_text(4.into(), "** b");
+    /// assert_eq!(changed, Some(TextRange::new(0.into(), 8.into())));
+    /// assert_eq!(org.to_org(), "* a\n** b");
+    ///
+    /// assert!(org.insert_text(9.into(), "c").is_none());
+    /// assert_eq!(org.to_org(), "* a\n** b");
+    ///
+    /// let mut org = Org::parse("é");
+    /// assert!(org.insert_text(1.into(), "c").is_none());
+    /// ```
+    pub fn insert_text(&mut self, at: TextSize, text: &str) -> Option<TextRange> {
+        if !self.is_valid_offset(at) {
+            return None;
+        }
+        Some(self.replace_range(TextRange::empty(at), text))
+    }
+
+    /// Deletes text in given range, see [`Org::replace_range`]
+    ///
+    /// Returns `None` without changing the document if range is out of bounds or
+    /// not at char boundaries.
+    ///
+    /// ```rust
+    /// use orgize::{Org, ast::Headline, TextRange};
+    ///
+    /// let mut org = Org::parse("* a\ntext\n* b\n** c");
+    /// // delete across headline boundary
+    /// let changed = org.delete_range(TextRange::new(4.into(), 13.into()));
+    /// assert_eq!(changed, Some(TextRange::new(0.into(), 8.into())));
+    /// assert_eq!(org.to_org(), "* a\n** c");
+    /// assert_eq!(org.first_node::<Headline>().unwrap().headlines().count(), 1);
+    ///
+    /// assert!(org.delete_range(TextRange::new(4.into(), 10.into())).is_none());
+    /// ```
+    pub fn delete_range(&mut self, range: TextRange) -> Option<TextRange> {
+        if !self.is_valid_offset(range.start()) || !self.is_valid_offset(range.end()) {
+            return None;
+        }
+        Some(self.replace_range(range, ""))
+    }
+
+    /// Returns `true` if offset is inside the document and at a char boundary
+    fn is_valid_offset(&self, offset: TextSize) -> bool {
+        if offset > self.green.text_len() {
+            return false;
+        }
+        match self.document().syntax.token_at_offset(offset) {
+            TokenAtOffset::Single(t) => {
+                let offset: usize = (offset - t.text_range().start()).into();
+                t.text().is_char_boundary(offset)
+            }
+            _ => true,
+        }
+    }
+
+    /// Replaces multiple ranges at once, then reparses the whole syntax tree only once
+    ///
+    /// All ranges refer to offsets in the current document, so there's no need to adjust
+    /// offsets between edits. Insertions at the same offset are applied in the given order,
+    /// before any other edit starting at that offset.
+    ///
+    /// Returns range of the new document, or an error without changing the document if
+    /// any range is invalid or two ranges overlap.
+    ///
+    /// ```rust
+    /// use orgize::{Org, EditError, TextRange};
+    ///
+    /// let mut org = Org::parse("* a\n* b\n* c");
+    /// let changed = org.apply_edits([
+    ///     (TextRange::new(10.into(), 11.into()), "C".to_string()),
+    ///     (TextRange::new(2.into(), 3.into()), "A".to_string()),
+    ///     (TextRange::new(4.into(), 4.into()), "*".to_string()),
+    ///     (TextRange::new(4.into(), 4.into()), "*".to_string()),
+    /// ]);
+    /// assert_eq!(changed, Ok(TextRange::new(0.into(), 13.into())));
+    /// assert_eq!(org.to_org(), "* A\n*** b\n* C");
+    ///
+    /// let edits = [
+    ///     (TextRange::new(0.into(), 5.into()), String::new()),
+    ///     (TextRange::new(4.into(), 6.into()), String::new()),
+    /// ];
+    /// assert_eq!(
+    ///     org.apply_edits(edits),
+    ///     Err(EditError::Overlapping(
+    ///         TextRange::new(0.into(), 5.into()),
+    ///         TextRange::new(4.into(), 6.into())
+    ///     ))
+    /// );
+    ///
+    /// let edits = [(TextRange::new(10.into(), 20.into()), String::new())];
+    /// assert_eq!(
+    ///     org.apply_edits(edits),
+    ///     Err(EditError::InvalidRange(TextRange::new(10.into(), 20.into())))
+    /// );
+    /// assert_eq!(org.to_org(), "* A\n*** b\n* C");
+    /// ```
+    pub fn apply_edits(
+        &mut self,
+        edits: impl IntoIterator<Item = (TextRange, String)>,
+    ) -> Result<TextRange, EditError> {
+        let mut edits: Vec<_> = edits.into_iter().collect();
+        // stable, so insertions at the same offset keep their order
+        edits.sort_by_key(|(range, _)| (range.start(), range.end()));
+
+        if let Some((range, _)) = edits.iter().find(|(range, _)| {
+            !self.is_valid_offset(range.start()) || !self.is_valid_offset(range.end())
+        }) {
+            return Err(EditError::InvalidRange(*range));
+        }
+
+        if let Some(pair) = edits
+            .windows(2)
+            .find(|pair| pair[0].0.end() > pair[1].0.start())
+        {
+            return Err(EditError::Overlapping(pair[0].0, pair[1].0));
+        }
+
+        self.replace_ranges(edits);
+        Ok(TextRange::up_to(self.green.text_len()))
+    }
+
+    /// Replaces multiple ranges at once, then reparses the whole syntax tree
+    ///
+    /// Ranges overlapping a preceding one are dropped, so an outer range wins
+    /// over ranges nested inside it. Insertions are kept in the given order,
+    /// before a range starting at the same offset.
+    pub(crate) fn replace_ranges(&mut self, mut edits: Vec<(TextRange, String)>) {
+        if edits.is_empty() {
+            return;
+        }
+
+        // when starting at the same offset, insertions come first, then outer ranges
+        edits.sort_by(|(a, _), (b, _)| {
+            (a.start(), !a.is_empty(), b.end()).cmp(&(b.start(), !b.is_empty(), a.end()))
+        });
+        let mut end = TextSize::default();
+        edits.retain(|(range, _)| {
+            let disjoint = range.start() >= end;
+            if disjoint {
+                end = range.end();
+            }
+            disjoint
+        });
+
+        let mut text = self.green.to_string();
+        // replace from the end, so that offsets of previous ranges stay valid
+        for (range, replace_with) in edits.iter().rev() {
+            let start: usize = range.start().into();
+            let end: usize = range.end().into();
+            text.replace_range(start..end, replace_with);
+        }
+
+        let input = (text.as_str(), &self.config).into();
+        self.green = document_node(input).unwrap().1.into_node().unwrap();
+    }
+
+    /// Applies non-overlapping edits inside given headline, then reparses only this headline
+    ///
+    /// Ranges are absolute offsets and must not cover the headline stars.
+    pub(crate) fn edit_headline(
+        &mut self,
+        headline: &Headline,
+        mut edits: Vec<(TextRange, String)>,
+    ) {
+        if edits.is_empty() {
+            return;
+        }
+
+        edits.sort_by_key(|(range, _)| range.start());
+
+        let range = headline.syntax.text_range();
+        let offset: usize = range.start().into();
+        let mut text = headline.syntax.to_string();
+        for (range, replace_with) in edits.iter().rev() {
+            let start: usize = range.start().into();
+            let end: usize = range.end().into();
+            text.replace_range((start - offset)..(end - offset), replace_with);
+        }
+
+        self.replace_range(range, text);
+    }
+
+    fn full_parse(&mut self, range: TextRange, replace_with: &str) -> TextRange {
         if self.document().syntax().text_range() == range {
             let input = (replace_with, &self.config).into();
             self.green = document_node(input).unwrap().1.into_node().unwrap();
@@ -161,15 +386,18 @@ impl Org {
             let input = (text.as_ref(), &self.config).into();
             self.green = document_node(input).unwrap().1.into_node().unwrap();
         }
+        TextRange::up_to(self.green.text_len())
     }
 
-    fn replace_headline(&mut self, headline: Headline, range: TextRange, replace_with: &str) {
-        if headline.syntax().text_range() == range {
+    fn replace_headline(
+        &mut self,
+        headline: Headline,
+        range: TextRange,
+        replace_with: &str,
+    ) -> TextRange {
+        let node = if headline.syntax().text_range() == range {
             let input = (replace_with, &self.config).into();
-
-            self.green = headline
-                .syntax
-                .replace_with(headline_node(input).unwrap().1.into_node().unwrap());
+            headline_node(input).unwrap().1.into_node().unwrap()
         } else {
             let offset: usize = headline.syntax.text_range().start().into();
             let start: usize = range.start().into();
@@ -179,11 +407,11 @@ impl Org {
             text.replace_range((start - offset)..(end - offset), replace_with);
 
             let input = (text.as_ref(), &self.config).into();
-
-            self.green = headline
-                .syntax
-                .replace_with(headline_node(input).unwrap().1.into_node().unwrap());
-        }
+            headline_node(input).unwrap().1.into_node().unwrap()
+        };
+        let new_range = TextRange::at(headline.start(), node.text_len());
+        self.green = headline.syntax.replace_with(node);
+        new_range
     }
 }
 
@@ -287,11 +515,15 @@ fn replace() {
             let output = format!("{}{}{}", &$input[0..start], $replace, &$input[end + 1..]);
 
             let mut org = Org::parse(input);
-            org.replace_range(
+            let changed = org.replace_range(
                 TextRange::new((start as u32).into(), (end as u32 - 1).into()),
                 $replace,
             );
 
+            // reparsed region must cover the replaced text
+            assert!(changed.start() <= TextSize::from(start as u32));
+            assert!(changed.end() >= TextSize::from((start + $replace.len()) as u32));
+
             debug_assert_eq!(
                 format!("{:#?}", org.document().syntax),
                 format!("{:#?}", Org::parse(output).document().syntax),
@@ -324,3 +556,52 @@ fn replace() {
     t!("* abc \n|* edf\n|* gh", "* hg\n");
     t!("* abc \n* edf\n|* gh|", "* hg");
 }
+
+#[test]
+fn replace_ranges() {
+    let range = |start: u32, end: u32| TextRange::new(start.into(), end.into());
+
+    let mut org = Org::parse("0123456789");
+    org.replace_ranges(vec![
+        (range(4, 6), "x".into()),
+        (range(1, 3), "y".into()),
+        (range(2, 8), "z".into()),
+        (range(8, 8), "w".into()),
+    ]);
+    assert_eq!(org.to_org(), "0y3x67w89");
+
+    // outer range wins over ranges nested inside it
+    let mut org = Org::parse("0123456789");
+    org.replace_ranges(vec![(range(2, 4), "x".into()), (range(2, 8), "z".into())]);
+    assert_eq!(org.to_org(), "01z89");
+
+    // insertion is kept next to a range starting at the same offset
+    let mut org = Org::parse("0123456789");
+    org.replace_ranges(vec![(range(4, 6), "".into()), (range(4, 4), "X".into())]);
+    assert_eq!(org.to_org(), "0123X6789");
+}
+
+#[test]
+fn apply_edits_at_same_offset() {
+    let range = |start: u32, end: u32| TextRange::new(start.into(), end.into());
+
+    let mut org = Org::parse("0123456789");
+    let changed = org.apply_edits([(range(4, 4), "X".into()), (range(4, 6), "".into())]);
+    assert_eq!(changed, Ok(range(0, 9)));
+    assert_eq!(org.to_org(), "0123X6789");
+
+    let mut org = Org::parse("0123456789");
+    let changed = org.apply_edits([(range(4, 6), "".into()), (range(4, 4), "X".into())]);
+    assert_eq!(changed, Ok(range(0, 9)));
+    assert_eq!(org.to_org(), "0123X6789");
+
+    let mut org = Org::parse("0123456789");
+    let changed = org.apply_edits([
+        (range(4, 6), "Z".into()),
+        (range(4, 4), "X".into()),
+        (range(4, 4), "Y".into()),
+        (range(6, 6), "W".into()),
+    ]);
+    assert_eq!(changed, Ok(range(0, 12)));
+    assert_eq!(org.to_org(), "0123XYZW6789");
+}
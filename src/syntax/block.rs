@@ -1,10 +1,10 @@
 use nom::{
     branch::alt,
-    bytes::complete::{tag, tag_no_case, take_while, take_while1},
-    character::complete::{alpha1, space0, space1},
+    bytes::complete::{tag, tag_no_case, take_while1},
+    character::complete::{alpha1, digit1, space0, space1},
     combinator::{cond, opt},
     sequence::{separated_pair, tuple},
-    IResult, InputTake,
+    IResult, InputTake, Slice,
 };
 
 use super::{
@@ -15,6 +15,7 @@ use super::{
     element::element_nodes,
     input::Input,
     keyword::affiliated_keyword_nodes,
+    object::standard_object_nodes,
     SyntaxKind::*,
 };
 
@@ -44,6 +45,9 @@ fn block_node_base(input: Input) -> IResult<Input, GreenElement, ()> {
             children.extend(pre_blank);
             if kind.is_greater_element() {
                 children.push(node(BLOCK_CONTENT, element_nodes(contents)?));
+            } else if kind == VERSE_BLOCK {
+                // verse block contains objects, but not elements
+                children.push(node(BLOCK_CONTENT, comma_quoted_object_nodes(contents)));
             } else {
                 children.push(node(BLOCK_CONTENT, comma_quoted_text_nodes(contents)));
             }
@@ -56,7 +60,7 @@ fn block_node_base(input: Input) -> IResult<Input, GreenElement, ()> {
     Err(nom::Err::Error(()))
 }
 
-fn block_begin_node(input: Input) -> IResult<Input, (GreenElement, &str), ()> {
+fn block_begin_node(input: Input<'_>) -> IResult<Input<'_>, (GreenElement, &str), ()> {
     let (input, (ws1, begin, name)) = tuple((space0, tag_no_case("#+BEGIN_"), alpha1))(input)?;
 
     let mut b = NodeBuilder::new();
@@ -93,21 +97,21 @@ fn block_begin_node(input: Input) -> IResult<Input, (GreenElement, &str), ()> {
             space1,
             take_while1(|c: char| c != ' ' && c != '\t' && c != '\n' && c != '\r'),
         )))(input)?;
-        let (input, data) = take_while(|c: char| c != '\n' && c != '\r')(input)?;
-        let (input, nl) = eol_or_eof(input)?;
+        let (input, (data, ws_, nl)) = trim_line_end(input)?;
 
         if let Some((ws, ty)) = ty {
             b.ws(ws);
             b.token(EXPORT_BLOCK_TYPE, ty);
         }
         b.text(data);
+        b.ws(ws_);
         b.nl(nl);
         Ok((input, (b.finish(BLOCK_BEGIN), name.as_str())))
     } else {
-        let (input, data) = take_while(|c: char| c != '\n' && c != '\r')(input)?;
-        let (input, nl) = eol_or_eof(input)?;
+        let (input, (data, ws, nl)) = trim_line_end(input)?;
 
         b.text(data);
+        b.ws(ws);
         b.nl(nl);
         Ok((input, (b.finish(BLOCK_BEGIN), name.as_str())))
     }
@@ -121,10 +125,11 @@ fn source_block_switches(input: Input) -> IResult<Input, Input, ()> {
             cond(i.len() != input.len(), space1),
             alt((
                 separated_pair(
-                    alt((tag("-l"), tag("-n"))),
+                    tag("-l"),
                     space1,
                     take_while1(|c: char| c != ' ' && c != '\t' && c != '\n' && c != '\r'),
                 ),
+                separated_pair(alt((tag("-n"), tag("+n"))), space1, digit1),
                 tuple((tag("+"), alpha1)),
                 tuple((tag("-"), alpha1)),
             )),
@@ -145,8 +150,13 @@ fn source_block_switches(input: Input) -> IResult<Input, Input, ()> {
 }
 
 fn block_end_node<'a>(input: Input<'a>, name: &str) -> IResult<Input<'a>, GreenElement, ()> {
-    let (input, (ws, end, name, ws_, nl)) =
-        tuple((space0, tag_no_case("#+END_"), tag(name), space0, eol_or_eof))(input)?;
+    let (input, (ws, end, name, ws_, nl)) = tuple((
+        space0,
+        tag_no_case("#+END_"),
+        tag_no_case(name),
+        space0,
+        eol_or_eof,
+    ))(input)?;
 
     let mut b = NodeBuilder::new();
     b.ws(ws);
@@ -186,6 +196,28 @@ fn comma_quoted_text_nodes(input: Input) -> Vec<GreenElement> {
     nodes
 }
 
+/// Same as [`comma_quoted_text_nodes`], but parses objects between escaping commas
+fn comma_quoted_object_nodes(input: Input) -> Vec<GreenElement> {
+    let mut nodes = vec![];
+
+    let s = input.as_str();
+
+    let mut start = 0;
+    for i in line_starts_iter(s) {
+        if s.get(i..i + 2) != Some(",*") && s.get(i..i + 3) != Some(",#+") {
+            continue;
+        }
+
+        nodes.extend(standard_object_nodes(input.slice(start..i)));
+        nodes.push(token(COMMA, ","));
+        start = i + 1;
+    }
+
+    nodes.extend(standard_object_nodes(input.slice(start..)));
+
+    nodes
+}
+
 #[cfg_attr(
     feature = "tracing",
     tracing::instrument(level = "debug", skip(input), fields(input = input.s))
@@ -196,11 +228,12 @@ pub fn block_node(input: Input) -> IResult<Input, GreenElement, ()> {
 
 #[test]
 fn test_parse() {
-    use crate::ast::{ExampleBlock, SourceBlock};
-    use crate::tests::to_ast;
+    use crate::ast::{ExampleBlock, SourceBlock, VerseBlock};
+    use crate::{tests::to_ast, ParseConfig};
 
     let to_src_block = to_ast::<SourceBlock>(block_node);
     let to_example_block = to_ast::<ExampleBlock>(block_node);
+    let to_verse_block = to_ast::<VerseBlock>(block_node);
 
     insta::assert_debug_snapshot!(
         to_example_block(
@@ -301,5 +334,110 @@ alert('Hello World!');
     "###
     );
 
+    insta::assert_debug_snapshot!(
+        to_verse_block(
+r#"#+BEGIN_VERSE
+  Great *clouds*
+#+END_VERSE"#
+        ).syntax,
+        @r##"
+    VERSE_BLOCK@0..42
+      BLOCK_BEGIN@0..14
+        TEXT@0..8 "#+BEGIN_"
+        TEXT@8..13 "VERSE"
+        NEW_LINE@13..14 "\n"
+      BLOCK_CONTENT@14..31
+        TEXT@14..22 "  Great "
+        BOLD@22..30
+          STAR@22..23 "*"
+          TEXT@23..29 "clouds"
+          STAR@29..30 "*"
+        TEXT@30..31 "\n"
+      BLOCK_END@31..42
+        TEXT@31..37 "#+END_"
+        TEXT@37..42 "VERSE"
+    "##
+    );
+
+    insta::assert_debug_snapshot!(
+        to_verse_block(
+r#"#+BEGIN_VERSE
+,* not a *headline*
+,#+not a keyword
+#+END_VERSE"#
+        ).syntax,
+        @r##"
+    VERSE_BLOCK@0..62
+      BLOCK_BEGIN@0..14
+        TEXT@0..8 "#+BEGIN_"
+        TEXT@8..13 "VERSE"
+        NEW_LINE@13..14 "\n"
+      BLOCK_CONTENT@14..51
+        COMMA@14..15 ","
+        TEXT@15..23 "* not a "
+        BOLD@23..33
+          STAR@23..24 "*"
+          TEXT@24..32 "headline"
+          STAR@32..33 "*"
+        TEXT@33..34 "\n"
+        COMMA@34..35 ","
+        TEXT@35..51 "#+not a keyword\n"
+      BLOCK_END@51..62
+        TEXT@51..57 "#+END_"
+        TEXT@57..62 "VERSE"
+    "##
+    );
+
+    // block type is case-insensitive, and may differ in case between begin and end
+    insta::assert_debug_snapshot!(
+        to_src_block("#+begin_SRC rust\nfn main() {}\n#+END_src  \n").syntax,
+        @r##"
+    SOURCE_BLOCK@0..42
+      BLOCK_BEGIN@0..17
+        TEXT@0..8 "#+begin_"
+        TEXT@8..11 "SRC"
+        WHITESPACE@11..12 " "
+        SRC_BLOCK_LANGUAGE@12..16 "rust"
+        NEW_LINE@16..17 "\n"
+      BLOCK_CONTENT@17..30
+        TEXT@17..30 "fn main() {}\n"
+      BLOCK_END@30..42
+        TEXT@30..36 "#+END_"
+        TEXT@36..39 "src"
+        WHITESPACE@39..41 "  "
+        NEW_LINE@41..42 "\n"
+    "##
+    );
+
+    // CRLF line endings with trailing whitespace
+    insta::assert_debug_snapshot!(
+        to_example_block("#+Begin_Example \r\ntext\r\n#+end_EXAMPLE\t\r\n\r\n").syntax,
+        @r##"
+    EXAMPLE_BLOCK@0..42
+      BLOCK_BEGIN@0..18
+        TEXT@0..8 "#+Begin_"
+        TEXT@8..15 "Example"
+        WHITESPACE@15..16 " "
+        NEW_LINE@16..18 "\r\n"
+      BLOCK_CONTENT@18..24
+        TEXT@18..24 "text\r\n"
+      BLOCK_END@24..40
+        TEXT@24..30 "#+end_"
+        TEXT@30..37 "EXAMPLE"
+        WHITESPACE@37..38 "\t"
+        NEW_LINE@38..40 "\r\n"
+      BLANK_LINE@40..42 "\r\n"
+    "##
+    );
+
+    let config = &ParseConfig::default();
+
+    // mismatched begin and end type
+    assert!(block_node(("#+BEGIN_SRC\ntext\n#+END_EXAMPLE", config).into()).is_err());
+    assert!(block_node(("#+BEGIN_SRC\ntext\n#+END_SRCX", config).into()).is_err());
+    let org = crate::Org::parse("#+BEGIN_SRC\n*text*\n#+END_EXAMPLE");
+    assert!(org.first_node::<SourceBlock>().is_none());
+    assert!(org.first_node::<crate::ast::Bold>().is_some());
+
     // TODO: more testing
 }
@@ -0,0 +1,182 @@
+use memchr::memchr_iter;
+use nom::{
+    bytes::complete::{tag, take_while1},
+    combinator::opt,
+    sequence::tuple,
+    Err, IResult, Slice,
+};
+
+use super::{
+    combinator::{
+        balanced_brackets, colon_token, l_bracket_token, node, r_bracket_token, GreenElement,
+    },
+    input::Input,
+    object::standard_object_nodes,
+    SyntaxKind::*,
+};
+
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(level = "debug", skip(input), fields(input = input.s))
+)]
+pub fn citation_node(input: Input) -> IResult<Input, GreenElement, ()> {
+    crate::lossless_parser!(citation_node_base, input)
+}
+
+fn citation_node_base(input: Input) -> IResult<Input, GreenElement, ()> {
+    let (input, (l_bracket, cite, style, colon, body, r_bracket)) = tuple((
+        l_bracket_token,
+        tag("cite"),
+        opt(tuple((
+            tag("/"),
+            take_while1(|c: char| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '/')),
+        ))),
+        colon_token,
+        balanced_brackets,
+        r_bracket_token,
+    ))(input)?;
+
+    let mut children = vec![l_bracket, cite.token(KEYWORD)];
+    if let Some((slash, style)) = style {
+        children.push(slash.token(SLASH));
+        children.push(style.token(CITATION_STYLE));
+    }
+    children.push(colon);
+    citation_body(body, &mut children)?;
+    children.push(r_bracket);
+
+    Ok((input, node(CITATION, children)))
+}
+
+/// Parses semicolon-separated global prefix, references and global suffix
+fn citation_body<'a>(body: Input<'a>, children: &mut Vec<GreenElement>) -> Result<(), Err<()>> {
+    let semicolons: Vec<_> = memchr_iter(b';', body.as_bytes()).collect();
+    let parts: Vec<_> = (0..=semicolons.len())
+        .map(|idx| {
+            let start = if idx == 0 { 0 } else { semicolons[idx - 1] + 1 };
+            let end = semicolons.get(idx).copied().unwrap_or(body.len());
+            body.slice(start..end)
+        })
+        .collect();
+
+    let is_reference = |part: &Input<'a>| key_range(part).is_some();
+    let first = parts.iter().position(is_reference).ok_or(Err::Error(()))?;
+    let last = parts.iter().rposition(is_reference).ok_or(Err::Error(()))?;
+
+    // only the first and last parts can be global prefix and suffix
+    if first > 1 || last + 2 < parts.len() || !parts[first..=last].iter().all(is_reference) {
+        return Err(Err::Error(()));
+    }
+
+    for (idx, part) in parts.into_iter().enumerate() {
+        if idx > 0 {
+            let semicolon = semicolons[idx - 1];
+            children.push(body.slice(semicolon..semicolon + 1).token(SEMICOLON));
+        }
+        if idx < first {
+            children.push(node(CITATION_PREFIX, standard_object_nodes(part)));
+        } else if idx > last {
+            children.push(node(CITATION_SUFFIX, standard_object_nodes(part)));
+        } else {
+            children.push(citation_reference_node(part));
+        }
+    }
+
+    Ok(())
+}
+
+fn citation_reference_node(input: Input) -> GreenElement {
+    let (at, end) = key_range(&input).expect("reference must contains a key");
+
+    let mut children = vec![];
+    if at > 0 {
+        children.push(node(
+            CITATION_PREFIX,
+            standard_object_nodes(input.slice(..at)),
+        ));
+    }
+    children.push(input.slice(at..at + 1).token(AT));
+    children.push(input.slice(at + 1..end).token(CITATION_KEY));
+    if end < input.len() {
+        children.push(node(
+            CITATION_SUFFIX,
+            standard_object_nodes(input.slice(end..)),
+        ));
+    }
+
+    node(CITATION_REFERENCE, children)
+}
+
+/// Returns range of the first `@key` in given text
+fn key_range(input: &str) -> Option<(usize, usize)> {
+    input.match_indices('@').find_map(|(at, _)| {
+        let key = &input[at + 1..];
+        let len = key.find(|c: char| !is_key_char(c)).unwrap_or(key.len());
+        (len > 0).then_some((at, at + 1 + len))
+    })
+}
+
+fn is_key_char(c: char) -> bool {
+    c.is_alphanumeric() || "-.:?!`'/*@+|(){}<>&_^$#%~".contains(c)
+}
+
+#[test]
+fn parse() {
+    use crate::{ast::Citation, tests::to_ast};
+
+    let to_citation = to_ast::<Citation>(citation_node);
+
+    insta::assert_debug_snapshot!(
+        to_citation("[cite:@doe2020]").syntax,
+        @r#"
+    CITATION@0..15
+      L_BRACKET@0..1 "["
+      KEYWORD@1..5 "cite"
+      COLON@5..6 ":"
+      CITATION_REFERENCE@6..14
+        AT@6..7 "@"
+        CITATION_KEY@7..14 "doe2020"
+      R_BRACKET@14..15 "]"
+    "#
+    );
+
+    insta::assert_debug_snapshot!(
+        to_citation("[cite/t/b:see ;pre @a, p. 3;@b; and *more*]").syntax,
+        @r#"
+    CITATION@0..43
+      L_BRACKET@0..1 "["
+      KEYWORD@1..5 "cite"
+      SLASH@5..6 "/"
+      CITATION_STYLE@6..9 "t/b"
+      COLON@9..10 ":"
+      CITATION_PREFIX@10..14
+        TEXT@10..14 "see "
+      SEMICOLON@14..15 ";"
+      CITATION_REFERENCE@15..27
+        CITATION_PREFIX@15..19
+          TEXT@15..19 "pre "
+        AT@19..20 "@"
+        CITATION_KEY@20..21 "a"
+        CITATION_SUFFIX@21..27
+          TEXT@21..27 ", p. 3"
+      SEMICOLON@27..28 ";"
+      CITATION_REFERENCE@28..30
+        AT@28..29 "@"
+        CITATION_KEY@29..30 "b"
+      SEMICOLON@30..31 ";"
+      CITATION_SUFFIX@31..42
+        TEXT@31..36 " and "
+        BOLD@36..42
+          STAR@36..37 "*"
+          TEXT@37..41 "more"
+          STAR@41..42 "*"
+      R_BRACKET@42..43 "]"
+    "#
+    );
+
+    let config = &crate::ParseConfig::default();
+    assert!(citation_node(("[cite:no key]", config).into()).is_err());
+    assert!(citation_node(("[cite:@a;no key;@b]", config).into()).is_err());
+    assert!(citation_node(("[cite:a;b;@c]", config).into()).is_err());
+    assert!(citation_node(("[citep:@a]", config).into()).is_err());
+}
@@ -230,6 +230,23 @@ pub fn eol_or_eof(input: Input) -> IResult<Input, Input, ()> {
     Ok(input.take_split(count))
 }
 
+/// Takes everything before the `]` closing an already opened bracket,
+/// skipping nested bracket pairs
+pub fn balanced_brackets(input: Input) -> IResult<Input, Input, ()> {
+    let mut pairs = 1;
+    let bytes = input.as_bytes();
+    for i in memchr2_iter(b'[', b']', bytes) {
+        if bytes[i] == b'[' {
+            pairs += 1;
+        } else if pairs != 1 {
+            pairs -= 1;
+        } else {
+            return Ok(input.take_split(i));
+        }
+    }
+    Err(nom::Err::Error(()))
+}
+
 struct LineStart<'a> {
     bytes: &'a [u8],
     iter: Memchr2<'a>,
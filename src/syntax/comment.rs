@@ -1,6 +1,6 @@
 use nom::{
-    bytes::complete::{tag, take_while},
-    character::complete::{space0, space1},
+    bytes::complete::{tag, take_while, take_while_m_n},
+    character::complete::space0,
     combinator::{iterator, opt},
     sequence::tuple,
     IResult,
@@ -20,7 +20,11 @@ fn comment_node_base(input: Input) -> IResult<Input, GreenElement, ()> {
         opt(tuple((
             space0,
             tag("#"),
-            opt(tuple((space1, take_while(|c| c != '\r' && c != '\n')))),
+            // only one whitespace separates the marker, the rest belongs to content
+            opt(tuple((
+                take_while_m_n(1, 1, |c| c == ' ' || c == '\t'),
+                take_while(|c| c != '\r' && c != '\n'),
+            ))),
             eol_or_eof,
         ))),
     );
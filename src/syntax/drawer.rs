@@ -16,7 +16,7 @@ use super::{
     SyntaxKind::*,
 };
 
-fn drawer_begin_node(input: Input) -> IResult<Input, (GreenElement, &str), ()> {
+fn drawer_begin_node(input: Input<'_>) -> IResult<Input<'_>, (GreenElement, &str), ()> {
     let mut b = NodeBuilder::new();
 
     let (input, (ws, colon, name, colon_, ws_, nl)) = tuple((
@@ -60,7 +60,11 @@ fn drawer_end_node(input: Input) -> IResult<Input, GreenElement, ()> {
 }
 
 fn drawer_node_base(input: Input) -> IResult<Input, GreenElement, ()> {
-    let (input, (begin, _)) = drawer_begin_node(input)?;
+    let (input, (begin, name)) = drawer_begin_node(input)?;
+
+    if !input.c.is_drawer_name(name) {
+        return Err(nom::Err::Error(()));
+    }
 
     let (input, pre_blank) = blank_lines(input)?;
 
@@ -266,10 +270,73 @@ fn parse() {
     "###
     );
 
+    insta::assert_debug_snapshot!(
+        to_drawer(":DRAWER:  \r\n  :CUSTOM_ID: id\r\n  :end:\t\r\n\r\n").syntax,
+        @r#"
+    DRAWER@0..42
+      DRAWER_BEGIN@0..12
+        COLON@0..1 ":"
+        TEXT@1..7 "DRAWER"
+        COLON@7..8 ":"
+        WHITESPACE@8..10 "  "
+        NEW_LINE@10..12 "\r\n"
+      DRAWER_CONTENT@12..30
+        PARAGRAPH@12..30
+          TEXT@12..21 "  :CUSTOM"
+          SUBSCRIPT@21..24
+            UNDERSCORE@21..22 "_"
+            TEXT@22..24 "ID"
+          TEXT@24..30 ": id\r\n"
+      DRAWER_END@30..40
+        WHITESPACE@30..32 "  "
+        COLON@32..33 ":"
+        TEXT@33..36 "end"
+        COLON@36..37 ":"
+        WHITESPACE@37..38 "\t"
+        NEW_LINE@38..40 "\r\n"
+      BLANK_LINE@40..42 "\r\n"
+    "#
+    );
+
+    insta::assert_debug_snapshot!(
+        to_property_drawer(":PROPERTIES:\r\n:NAME: VALUE  \r\n:END:\r\n").syntax,
+        @r#"
+    PROPERTY_DRAWER@0..37
+      DRAWER_BEGIN@0..14
+        COLON@0..1 ":"
+        TEXT@1..11 "PROPERTIES"
+        COLON@11..12 ":"
+        NEW_LINE@12..14 "\r\n"
+      NODE_PROPERTY@14..30
+        COLON@14..15 ":"
+        TEXT@15..19 "NAME"
+        COLON@19..20 ":"
+        WHITESPACE@20..21 " "
+        TEXT@21..26 "VALUE"
+        WHITESPACE@26..28 "  "
+        NEW_LINE@28..30 "\r\n"
+      DRAWER_END@30..37
+        COLON@30..31 ":"
+        TEXT@31..34 "END"
+        COLON@34..35 ":"
+        NEW_LINE@35..37 "\r\n"
+    "#
+    );
+
     let config = &ParseConfig::default();
 
     // https://github.com/PoiScript/orgize/issues/9
     assert!(drawer_node((":SPAGHETTI:\n", config).into()).is_err());
 
     assert!(property_drawer_node((":PROPERTIES:\n:NAME:VALUE\n:END:", config).into()).is_err());
+
+    let config = &ParseConfig {
+        drawer_names: Some(vec!["NOTES".into()]),
+        ..Default::default()
+    };
+
+    assert!(drawer_node((":notes:\n:END:", config).into()).is_ok());
+    assert!(drawer_node((":LOGBOOK:\n:END:", config).into()).is_ok());
+    assert!(drawer_node((":FOO:\n:END:", config).into()).is_err());
+    assert!(property_drawer_node((":PROPERTIES:\n:END:", config).into()).is_ok());
 }
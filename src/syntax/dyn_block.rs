@@ -1,6 +1,6 @@
 use nom::{
-    bytes::complete::tag_no_case,
-    character::complete::{alpha1, space0, space1},
+    bytes::complete::{tag_no_case, take_while1},
+    character::complete::{space0, space1},
     sequence::tuple,
     IResult, InputTake,
 };
@@ -9,20 +9,29 @@ use super::{
     combinator::{
         blank_lines, eol_or_eof, line_starts_iter, node, trim_line_end, GreenElement, NodeBuilder,
     },
+    element::element_nodes,
     input::Input,
+    keyword::affiliated_keyword_nodes,
     SyntaxKind::*,
 };
 
 fn dyn_block_node_base(input: Input) -> IResult<Input, GreenElement, ()> {
+    let (input, affiliated_keywords) = affiliated_keyword_nodes(input)?;
     let (input, begin) = dyn_block_begin_node(input)?;
     let (input, pre_blank) = blank_lines(input)?;
 
     for (input, contents) in line_starts_iter(input.as_str()).map(|i| input.take_split(i)) {
         if let Ok((input, end)) = dyn_block_end_node(input) {
             let (input, post_blank) = blank_lines(input)?;
-            let mut children = vec![begin];
+            let mut children = vec![];
+            children.extend(affiliated_keywords);
+            children.push(begin);
             children.extend(pre_blank);
-            children.push(contents.text_token());
+            if !contents.is_empty() {
+                children.push(node(BLOCK_CONTENT, element_nodes(contents)?));
+            } else {
+                children.push(node(BLOCK_CONTENT, []));
+            }
             children.push(end);
             children.extend(post_blank);
 
@@ -34,11 +43,12 @@ fn dyn_block_node_base(input: Input) -> IResult<Input, GreenElement, ()> {
 }
 
 fn dyn_block_begin_node(input: Input) -> IResult<Input, GreenElement, ()> {
-    let (input, (ws, begin, ws_, name, (args, ws__, nl))) = tuple((
+    let (input, (ws, begin, ws_, name, ws__, (parameters, ws___, nl))) = tuple((
         space0,
         tag_no_case("#+BEGIN:"),
         space1,
-        alpha1,
+        take_while1(|c: char| !c.is_ascii_whitespace()),
+        space0,
         trim_line_end,
     ))(input)?;
 
@@ -46,9 +56,12 @@ fn dyn_block_begin_node(input: Input) -> IResult<Input, GreenElement, ()> {
     b.ws(ws);
     b.text(begin);
     b.ws(ws_);
-    b.text(name);
-    b.text(args);
+    b.token(DYN_BLOCK_NAME, name);
     b.ws(ws__);
+    if !parameters.is_empty() {
+        b.token(DYN_BLOCK_PARAMETERS, parameters);
+    }
+    b.ws(ws___);
     b.nl(nl);
 
     Ok((input, b.finish(DYN_BLOCK_BEGIN)))
@@ -88,20 +101,59 @@ fn parse() {
 CONTENTS
 #+END:
     "#).syntax,
-        @r###"
+        @r##"
     DYN_BLOCK@0..53
       DYN_BLOCK_BEGIN@0..32
         TEXT@0..8 "#+BEGIN:"
         WHITESPACE@8..9 " "
-        TEXT@9..19 "clocktable"
-        TEXT@19..31 " :scope file"
+        DYN_BLOCK_NAME@9..19 "clocktable"
+        WHITESPACE@19..20 " "
+        DYN_BLOCK_PARAMETERS@20..31 ":scope file"
         NEW_LINE@31..32 "\n"
       BLANK_LINE@32..33 "\n"
-      TEXT@33..42 "CONTENTS\n"
+      BLOCK_CONTENT@33..42
+        PARAGRAPH@33..42
+          TEXT@33..42 "CONTENTS\n"
       DYN_BLOCK_END@42..49
         TEXT@42..48 "#+END:"
         NEW_LINE@48..49 "\n"
       BLANK_LINE@49..53 "    "
-    "###
+    "##
+    );
+
+    insta::assert_debug_snapshot!(
+        to_dyn_block("#+BEGIN: columnview\n#+END:").syntax,
+        @r##"
+    DYN_BLOCK@0..26
+      DYN_BLOCK_BEGIN@0..20
+        TEXT@0..8 "#+BEGIN:"
+        WHITESPACE@8..9 " "
+        DYN_BLOCK_NAME@9..19 "columnview"
+        NEW_LINE@19..20 "\n"
+      BLOCK_CONTENT@20..20
+      DYN_BLOCK_END@20..26
+        TEXT@20..26 "#+END:"
+    "##
+    );
+
+    insta::assert_debug_snapshot!(
+        to_dyn_block("#+NAME: report\n#+BEGIN: clocktable\n#+END:").syntax,
+        @r##"
+    DYN_BLOCK@0..41
+      AFFILIATED_KEYWORD@0..15
+        HASH_PLUS@0..2 "#+"
+        TEXT@2..6 "NAME"
+        COLON@6..7 ":"
+        TEXT@7..14 " report"
+        NEW_LINE@14..15 "\n"
+      DYN_BLOCK_BEGIN@15..35
+        TEXT@15..23 "#+BEGIN:"
+        WHITESPACE@23..24 " "
+        DYN_BLOCK_NAME@24..34 "clocktable"
+        NEW_LINE@34..35 "\n"
+      BLOCK_CONTENT@35..35
+      DYN_BLOCK_END@35..41
+        TEXT@35..41 "#+END:"
+    "##
     );
 }
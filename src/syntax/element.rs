@@ -12,6 +12,7 @@ use super::{
     dyn_block::dyn_block_node,
     fixed_width::fixed_width_node,
     fn_def::fn_def_node,
+    inline_task::inline_task_node,
     input::Input,
     keyword::{affiliated_keyword_nodes, keyword_node},
     latex_environment::latex_environment_node,
@@ -87,16 +88,18 @@ pub fn element_node(input: Input) -> IResult<Input, GreenElement, ()> {
 
     let result = match byte {
         Some(b'[') => fn_def_node(input),
-        Some(b'0'..=b'9') | Some(b'*') => list_node(input),
+        Some(b'0'..=b'9') => list_node(input),
+        Some(b'*') => inline_task_node(input).or_else(|_| list_node(input)),
         // clock doesn't have affiliated keywords
         Some(b'C') if !has_affiliated_keyword => clock_node(input),
         Some(b'-') => rule_node(input).or_else(|_| list_node(input)),
         Some(b':') => drawer_node(input).or_else(|_| fixed_width_node(input)),
         Some(b'|') => org_table_node(input),
         Some(b'+') => table_el_node(input).or_else(|_| list_node(input)),
+        // `#+BEGIN:` line is also a valid keyword, so dynamic block goes first
         Some(b'#') => block_node(input)
-            .or_else(|_| keyword_node(input))
             .or_else(|_| dyn_block_node(input))
+            .or_else(|_| keyword_node(input))
             .or_else(|_| comment_node(input)),
         Some(b'\\') => latex_environment_node(input),
         _ => Err(nom::Err::Error(())),
@@ -96,8 +96,13 @@ pub fn italic_node(input: Input) -> IResult<Input, GreenElement, ()> {
 fn emphasis(marker: u8) -> impl Fn(Input) -> IResult<Input, Input, ()> {
     move |input: Input| {
         let bytes = input.as_bytes();
+        let components = &input.c.emphasis_regexp_components;
 
-        if bytes.len() < 3 || bytes[0] != marker || bytes[1].is_ascii_whitespace() {
+        if bytes.len() < 3
+            || bytes[0] != marker
+            || !input.c.text_markup.is_enabled(marker)
+            || input.s[1..].starts_with(|c| components.is_border(c))
+        {
             return Err(nom::Err::Error(()));
         }
 
@@ -105,7 +110,7 @@ fn emphasis(marker: u8) -> impl Fn(Input) -> IResult<Input, Input, ()> {
             // contains at least one character
             if idx == 1 {
                 continue;
-            } else if count(&bytes[1..idx], b'\n') >= 2 {
+            } else if count(&bytes[1..idx], b'\n') > components.newline {
                 break;
             } else if validate_marker(idx, input) {
                 return Ok((input.slice(idx + 1..), input.slice(1..idx)));
@@ -117,27 +122,23 @@ fn emphasis(marker: u8) -> impl Fn(Input) -> IResult<Input, Input, ()> {
 }
 
 fn validate_marker(pos: usize, text: Input) -> bool {
-    if text.as_bytes()[pos - 1].is_ascii_whitespace() {
+    let components = &text.c.emphasis_regexp_components;
+
+    if text.s[..pos].ends_with(|c| components.is_border(c)) {
         false
-    } else if let Some(post) = text.as_bytes().get(pos + 1) {
-        [
-            b' ', b'\t', b'\r', b'\n', b'-', b'.', b',', b';', b':', b'!', b'?', b'\'', b')', b'}',
-            b'[',
-        ]
-        .contains(post)
+    } else if let Some(post) = text.s[pos + 1..].chars().next() {
+        components.is_post(post)
     } else {
         true
     }
 }
 
-pub fn verify_pre(input: &str) -> bool {
-    if input.is_empty() {
-        return true;
-    }
-    matches!(
-        input.as_bytes()[input.len() - 1],
-        b'\t' | b' ' | b'-' | b'(' | b'{' | b'\\' | b'"' | b'\r' | b'\n'
-    )
+pub fn verify_pre(input: &Input) -> bool {
+    input
+        .s
+        .chars()
+        .last()
+        .is_none_or(|c| input.c.emphasis_regexp_components.is_pre(c))
 }
 
 #[test]
@@ -183,4 +184,30 @@ fn parse() {
     assert!(bold_node(("* bold*", config).into()).is_err());
     assert!(bold_node(("*b\nol\nd*", config).into()).is_err());
     assert!(italic_node(("*bold*", config).into()).is_err());
+
+    let config = &ParseConfig {
+        emphasis_regexp_components: crate::config::EmphasisRegexpComponents {
+            pre: None,
+            post: None,
+            newline: 2,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    assert!(bold_node(("*bold*a", config).into()).is_ok());
+    assert!(bold_node(("*b\nol\nd*", config).into()).is_ok());
+    assert!(bold_node(("*bold *", config).into()).is_err());
+    assert!(bold_node(("* bold*", config).into()).is_err());
+
+    let config = &ParseConfig {
+        text_markup: crate::config::TextMarkup {
+            bold: false,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    assert!(bold_node(("*bold*", config).into()).is_err());
+    assert!(italic_node(("/italic/", config).into()).is_ok());
 }
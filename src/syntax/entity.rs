@@ -25,7 +25,9 @@ fn template1(input: Input) -> IResult<Input, GreenElement, ()> {
     let (input, backslash) = backslash_token(input)?;
     let (input, name) = alphanumeric1(input)?;
 
-    if ENTITIES.iter().all(|i| i.0 != name.s) {
+    if ENTITIES.iter().all(|i| i.0 != name.s)
+        && input.c.user_entities.iter().all(|i| i.name != name.s)
+    {
         return Err(nom::Err::Error(()));
     }
     let (input, brackets) = opt(tag("{}"))(input)?;
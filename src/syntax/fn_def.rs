@@ -150,6 +150,23 @@ fn parse() {
     "#
     );
 
+    insta::assert_debug_snapshot!(
+         to_fn_def("[fn:1] footnote  \r\n\r\n").syntax,
+         @r#"
+    FN_DEF@0..21
+      L_BRACKET@0..1 "["
+      KEYWORD@1..3 "fn"
+      COLON@3..4 ":"
+      FN_LABEL@4..5 "1"
+      R_BRACKET@5..6 "]"
+      FN_CONTENT@6..15
+        TEXT@6..15 " footnote"
+      WHITESPACE@15..17 "  "
+      NEW_LINE@17..19 "\r\n"
+      BLANK_LINE@19..21 "\r\n"
+    "#
+    );
+
     let config = &ParseConfig::default();
 
     assert!(fn_def_node(("[fn:] https://orgmode.org", config).into()).is_err());
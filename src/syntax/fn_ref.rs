@@ -1,13 +1,14 @@
-use memchr::memchr2_iter;
 use nom::{
     bytes::complete::{tag, take_while},
     combinator::opt,
     sequence::tuple,
-    Err, IResult, InputTake,
+    IResult,
 };
 
 use super::{
-    combinator::{colon_token, l_bracket_token, node, r_bracket_token, GreenElement},
+    combinator::{
+        balanced_brackets, colon_token, l_bracket_token, node, r_bracket_token, GreenElement,
+    },
     input::Input,
     object::standard_object_nodes,
     SyntaxKind::*,
@@ -41,21 +42,6 @@ fn fn_ref_node_base(input: Input) -> IResult<Input, GreenElement, ()> {
     Ok((input, node(FN_REF, children)))
 }
 
-fn balanced_brackets(input: Input) -> IResult<Input, Input, ()> {
-    let mut pairs = 1;
-    let bytes = input.as_bytes();
-    for i in memchr2_iter(b'[', b']', bytes) {
-        if bytes[i] == b'[' {
-            pairs += 1;
-        } else if pairs != 1 {
-            pairs -= 1;
-        } else {
-            return Ok(input.take_split(i));
-        }
-    }
-    Err(Err::Error(()))
-}
-
 #[test]
 fn parse() {
     use crate::{ast::FnRef, tests::to_ast, ParseConfig};
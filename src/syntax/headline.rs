@@ -2,7 +2,7 @@ use memchr::memrchr_iter;
 use nom::{
     bytes::complete::take_while1,
     character::complete::{anychar, space0},
-    combinator::{map, opt},
+    combinator::{map, opt, verify},
     sequence::tuple,
     IResult, InputTake, Slice,
 };
@@ -36,32 +36,7 @@ fn headline_node_base(input: Input) -> IResult<Input, GreenElement, ()> {
 
     b.token(HEADLINE_STARS, stars);
 
-    let (input, ws) = space0(input)?;
-    b.ws(ws);
-
-    let (input, headline_keyword) = opt(headline_keyword_token)(input)?;
-
-    if let Some((headline_keyword, ws)) = headline_keyword {
-        b.push(headline_keyword);
-        b.ws(ws);
-    }
-
-    let (input, headline_priority) = opt(headline_priority_node)(input)?;
-
-    if let Some((headline_priority, ws)) = headline_priority {
-        b.push(headline_priority);
-        b.ws(ws);
-    }
-
-    let (input, (title_and_tags, ws_, nl)) = trim_line_end(input)?;
-    let (title, tags) = opt(headline_tags_node)(title_and_tags)?;
-
-    if !title.is_empty() {
-        b.push(node(HEADLINE_TITLE, standard_object_nodes(title)));
-    }
-    b.push_opt(tags);
-    b.ws(ws_);
-    b.nl(nl);
+    let (input, ()) = headline_line(input, &mut b)?;
 
     if input.is_empty() {
         return Ok((input, b.finish(HEADLINE)));
@@ -102,6 +77,39 @@ fn headline_node_base(input: Input) -> IResult<Input, GreenElement, ()> {
     Ok((i, b.finish(HEADLINE)))
 }
 
+/// Recognizes the rest of headline line after stars, i.e. keyword, priority,
+/// title and tags, including the line ending
+pub fn headline_line<'a>(input: Input<'a>, b: &mut NodeBuilder) -> IResult<Input<'a>, (), ()> {
+    let (input, ws) = space0(input)?;
+    b.ws(ws);
+
+    let (input, headline_keyword) = opt(headline_keyword_token)(input)?;
+
+    if let Some((headline_keyword, ws)) = headline_keyword {
+        b.push(headline_keyword);
+        b.ws(ws);
+    }
+
+    let (input, headline_priority) = opt(headline_priority_node)(input)?;
+
+    if let Some((headline_priority, ws)) = headline_priority {
+        b.push(headline_priority);
+        b.ws(ws);
+    }
+
+    let (input, (title_and_tags, ws_, nl)) = trim_line_end(input)?;
+    let (title, tags) = opt(headline_tags_node)(title_and_tags)?;
+
+    if !title.is_empty() {
+        b.push(node(HEADLINE_TITLE, standard_object_nodes(title)));
+    }
+    b.push_opt(tags);
+    b.ws(ws_);
+    b.nl(nl);
+
+    Ok((input, ()))
+}
+
 #[cfg_attr(
     feature = "tracing",
     tracing::instrument(level = "debug", skip(input), fields(input = input.s))
@@ -130,11 +138,19 @@ fn section_text(input: Input) -> IResult<Input, Input, ()> {
     feature = "tracing",
     tracing::instrument(level = "debug", skip(input), fields(input = input.s))
 )]
-fn headline_stars(input: Input) -> IResult<Input, Input, ()> {
+pub fn headline_stars(input: Input) -> IResult<Input, Input, ()> {
     let bytes = input.as_bytes();
     let level = bytes.iter().take_while(|&&c| c == b'*').count();
 
-    if level == 0 {
+    // too many stars make an inline task instead, or plain text
+    // when exceeding the maximum headline level
+    if level == 0
+        || input
+            .c
+            .inline_task_min_level
+            .is_some_and(|min| level >= min)
+        || input.c.max_headline_level.is_some_and(|max| level > max)
+    {
         Err(nom::Err::Error(()))
     }
     // headline stars must be followed by space
@@ -219,8 +235,14 @@ fn headline_keyword_token(input: Input) -> IResult<Input, (GreenElement, Input),
 }
 
 fn headline_priority_node(input: Input) -> IResult<Input, (GreenElement, Input), ()> {
+    let config = input.c;
     let (input, node) = map(
-        tuple((l_bracket_token, hash_token, anychar, r_bracket_token)),
+        tuple((
+            l_bracket_token,
+            hash_token,
+            verify(anychar, |c| config.is_priority(*c)),
+            r_bracket_token,
+        )),
         |(l_bracket, hash, char, r_bracket)| {
             node(
                 HEADLINE_PRIORITY,
@@ -323,6 +345,26 @@ fn parse() {
     assert!(headline_node(("**\t", config).into()).is_err());
 }
 
+#[test]
+fn max_headline_level() {
+    use crate::ParseConfig;
+
+    let config = &ParseConfig {
+        max_headline_level: Some(2),
+        ..Default::default()
+    };
+
+    assert!(headline_node(("** a", config).into()).is_ok());
+    assert!(headline_node(("*** a", config).into()).is_err());
+
+    let (input, _) = headline_node(("** a\n*** b\n** c", config).into()).unwrap();
+    assert_eq!(input.s, "** c");
+
+    // inline tasks aren't affected
+    let org = config.clone().parse("* a\n*************** task");
+    assert!(org.first_node::<crate::ast::InlineTask>().is_some());
+}
+
 #[test]
 fn issue_15_16() {
     use crate::{ast::Headline, tests::to_ast};
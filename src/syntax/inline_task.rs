@@ -0,0 +1,175 @@
+use nom::{
+    bytes::complete::tag,
+    character::complete::{space0, space1},
+    combinator::opt,
+    sequence::tuple,
+    IResult, InputTake,
+};
+
+use super::{
+    combinator::{blank_lines, eol_or_eof, line_starts_iter, node, GreenElement, NodeBuilder},
+    drawer::property_drawer_node,
+    element::element_nodes,
+    headline::{headline_line, headline_stars},
+    input::Input,
+    planning::planning_node,
+    SyntaxKind::*,
+};
+
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(level = "debug", skip(input), fields(input = input.s))
+)]
+pub fn inline_task_node(input: Input) -> IResult<Input, GreenElement, ()> {
+    crate::lossless_parser!(inline_task_node_base, input)
+}
+
+fn inline_task_node_base(input: Input) -> IResult<Input, GreenElement, ()> {
+    // a standalone `END` line isn't an inline task
+    if inline_task_end_node(input).is_ok() {
+        return Err(nom::Err::Error(()));
+    }
+
+    let (input, stars) = inline_task_stars(input)?;
+
+    let mut b = NodeBuilder::new();
+    b.token(HEADLINE_STARS, stars);
+
+    let (input, ()) = headline_line(input, &mut b)?;
+
+    for (i, contents) in line_starts_iter(input.as_str()).map(|i| input.take_split(i)) {
+        if let Ok((i, end)) = inline_task_end_node(i) {
+            let (contents, planning) = if contents.is_empty() {
+                (contents, None)
+            } else {
+                opt(planning_node)(contents)?
+            };
+            b.push_opt(planning);
+
+            let (contents, property_drawer) = if contents.is_empty() {
+                (contents, None)
+            } else {
+                opt(property_drawer_node)(contents)?
+            };
+            b.push_opt(property_drawer);
+
+            if !contents.is_empty() {
+                b.push(node(SECTION, element_nodes(contents)?));
+            }
+
+            b.push(end);
+
+            let (i, post_blank) = blank_lines(i)?;
+            b.children.extend(post_blank);
+
+            return Ok((i, b.finish(INLINE_TASK)));
+        }
+
+        // inline task can't span across headlines
+        if headline_stars(i).is_ok() {
+            break;
+        }
+    }
+
+    // without an `END` line, inline task consists of a single line
+    let (input, post_blank) = blank_lines(input)?;
+    b.children.extend(post_blank);
+
+    Ok((input, b.finish(INLINE_TASK)))
+}
+
+fn inline_task_stars(input: Input) -> IResult<Input, Input, ()> {
+    let min_level = input.c.inline_task_min_level.ok_or(nom::Err::Error(()))?;
+    let bytes = input.as_bytes();
+    let level = bytes.iter().take_while(|&&c| c == b'*').count();
+
+    if level >= min_level && matches!(bytes.get(level), Some(b' ')) {
+        Ok(input.take_split(level))
+    } else {
+        Err(nom::Err::Error(()))
+    }
+}
+
+fn inline_task_end_node(input: Input) -> IResult<Input, GreenElement, ()> {
+    let (input, (stars, ws, end, ws_, nl)) =
+        tuple((inline_task_stars, space1, tag("END"), space0, eol_or_eof))(input)?;
+
+    let mut b = NodeBuilder::new();
+    b.token(HEADLINE_STARS, stars);
+    b.ws(ws);
+    b.text(end);
+    b.ws(ws_);
+    b.nl(nl);
+
+    Ok((input, b.finish(INLINE_TASK_END)))
+}
+
+#[test]
+fn parse() {
+    use crate::{ast::InlineTask, tests::to_ast, ParseConfig};
+
+    let to_inline_task = to_ast::<InlineTask>(inline_task_node);
+
+    insta::assert_debug_snapshot!(
+        to_inline_task("*************** TODO task :tag:\nSCHEDULED: <2023-01-01>\nbody\n*************** END\n\n").syntax,
+        @r#"
+    INLINE_TASK@0..82
+      HEADLINE_STARS@0..15 "***************"
+      WHITESPACE@15..16 " "
+      HEADLINE_KEYWORD_TODO@16..20 "TODO"
+      WHITESPACE@20..21 " "
+      HEADLINE_TITLE@21..26
+        TEXT@21..26 "task "
+      HEADLINE_TAGS@26..31
+        COLON@26..27 ":"
+        TEXT@27..30 "tag"
+        COLON@30..31 ":"
+      NEW_LINE@31..32 "\n"
+      PLANNING@32..56
+        PLANNING_SCHEDULED@32..55
+          TEXT@32..42 "SCHEDULED:"
+          WHITESPACE@42..43 " "
+          TIMESTAMP_ACTIVE@43..55
+            L_ANGLE@43..44 "<"
+            TIMESTAMP_YEAR@44..48 "2023"
+            MINUS@48..49 "-"
+            TIMESTAMP_MONTH@49..51 "01"
+            MINUS@51..52 "-"
+            TIMESTAMP_DAY@52..54 "01"
+            R_ANGLE@54..55 ">"
+        NEW_LINE@55..56 "\n"
+      SECTION@56..61
+        PARAGRAPH@56..61
+          TEXT@56..61 "body\n"
+      INLINE_TASK_END@61..81
+        HEADLINE_STARS@61..76 "***************"
+        WHITESPACE@76..77 " "
+        TEXT@77..80 "END"
+        NEW_LINE@80..81 "\n"
+      BLANK_LINE@81..82 "\n"
+    "#
+    );
+
+    insta::assert_debug_snapshot!(
+        to_inline_task("*************** task\n\nparagraph").syntax,
+        @r#"
+    INLINE_TASK@0..22
+      HEADLINE_STARS@0..15 "***************"
+      WHITESPACE@15..16 " "
+      HEADLINE_TITLE@16..20
+        TEXT@16..20 "task"
+      NEW_LINE@20..21 "\n"
+      BLANK_LINE@21..22 "\n"
+    "#
+    );
+
+    let config = &ParseConfig::default();
+    assert!(inline_task_node(("*************** END", config).into()).is_err());
+    assert!(inline_task_node(("************** task", config).into()).is_err());
+
+    let config = &ParseConfig {
+        inline_task_min_level: None,
+        ..Default::default()
+    };
+    assert!(inline_task_node(("*************** task", config).into()).is_err());
+}
@@ -2,7 +2,7 @@
 
 use nom::{
     branch::alt,
-    bytes::complete::{tag, take_till, take_while1},
+    bytes::complete::{tag, tag_no_case, take_till, take_while1},
     character::complete::space0,
     combinator::{recognize, verify},
     sequence::tuple,
@@ -94,7 +94,7 @@ pub fn tblfm_keyword_nodes(input: Input) -> IResult<Input, Vec<GreenElement>, ()
     Ok((i, children))
 }
 
-fn keyword_node_base(input: Input) -> IResult<Input, (&str, Vec<GreenElement>), ()> {
+fn keyword_node_base(input: Input<'_>) -> IResult<Input<'_>, (&str, Vec<GreenElement>), ()> {
     let (input, (ws, hash_plus)) = tuple((space0, hash_plus_token))(input)?;
 
     let (input, (key, optional, colon)) = alt((key_with_optional, key))(input)?;
@@ -109,7 +109,7 @@ fn keyword_node_base(input: Input) -> IResult<Input, (&str, Vec<GreenElement>),
     children.push(key.text_token());
     if let Some((l_bracket, optional, r_bracket)) = optional {
         children.push(l_bracket.token(SyntaxKind::L_BRACKET));
-        children.push(optional.text_token());
+        children.push(optional.token(SyntaxKind::KEYWORD_OPTIONAL));
         children.push(r_bracket.token(SyntaxKind::R_BRACKET));
     }
     children.push(colon.token(SyntaxKind::COLON));
@@ -140,7 +140,7 @@ fn key_with_optional(
     input: Input,
 ) -> IResult<Input, (Input, Option<(Input, Input, Input)>, Input), ()> {
     let (input, (key, r_backer, optional, l_backer, colon)) = tuple((
-        alt((tag("CAPTION"), tag("RESULTS"))),
+        alt((tag_no_case("CAPTION"), tag_no_case("RESULTS"))),
         tag("["),
         take_till(|c| c == '\r' || c == '\n' || c == ']'),
         tag("]"),
@@ -276,7 +276,7 @@ fn parse() {
       HASH_PLUS@0..2 "#+"
       TEXT@2..9 "CAPTION"
       L_BRACKET@9..10 "["
-      TEXT@10..26 "caption optional"
+      KEYWORD_OPTIONAL@10..26 "caption optional"
       R_BRACKET@26..27 "]"
       COLON@27..28 ":"
       TEXT@28..34 " value"
@@ -42,7 +42,7 @@ fn list_node_base(input: Input) -> IResult<Input, GreenElement, ()> {
     while !ends_with_empty_blank_lines && !input.is_empty() {
         let (input_, indent) = space0(input)?;
 
-        if indent.len() != first_indent.len() {
+        if input.c.indent_width(indent.s) != input.c.indent_width(first_indent.s) {
             break;
         }
 
@@ -117,7 +117,7 @@ fn list_item_node<'a>(
     let (input, checkbox) = opt(list_item_checkbox)(input)?;
     let (input, tag) = cond(!is_ordered, opt(list_item_tag))(input)?;
     let (input, (ends_with_empty_blank_lines, content)) =
-        list_item_content_node(input, indent.len())?;
+        list_item_content_node(input, input.c.indent_width(indent.s))?;
     let (input, post_blank) = cond(!ends_with_empty_blank_lines, blank_lines)(input)?;
 
     let mut children = vec![
@@ -241,7 +241,7 @@ fn list_item_content_node(input: Input, indent: usize) -> IResult<Input, (bool,
             .skip(if skip_one { 1 } else { 0 })
             .map(|idx| i.take_split(idx))
         {
-            match get_line_indent(input.as_str()) {
+            match get_line_indent(input) {
                 Some(next_indent) => {
                     if next_indent <= indent {
                         let (input, head) = previous_blank_line.unwrap_or((input, head));
@@ -285,11 +285,13 @@ fn list_item_content_node(input: Input, indent: usize) -> IResult<Input, (bool,
     Ok((input.of(""), (false, node(LIST_ITEM_CONTENT, children))))
 }
 
-fn get_line_indent(input: &str) -> Option<usize> {
+fn get_line_indent(input: Input) -> Option<usize> {
     input
+        .s
         .bytes()
         .take_while(|b| *b != b'\n')
         .position(|b| !b.is_ascii_whitespace())
+        .map(|idx| input.c.indent_width(&input.s[0..idx]))
 }
 
 #[test]
@@ -602,6 +604,36 @@ fn parse() {
     "###
     );
 
+    // tabs expand to the next multiple of tab width
+    insta::assert_debug_snapshot!(
+        to_list("- a\n\t- b\n  \tc\n    - d\n").syntax,
+        @r#"
+    LIST@0..22
+      LIST_ITEM@0..22
+        LIST_ITEM_INDENT@0..0 ""
+        LIST_ITEM_BULLET@0..2 "- "
+        LIST_ITEM_CONTENT@2..22
+          PARAGRAPH@2..4
+            TEXT@2..4 "a\n"
+          LIST@4..9
+            LIST_ITEM@4..9
+              LIST_ITEM_INDENT@4..5 "\t"
+              LIST_ITEM_BULLET@5..7 "- "
+              LIST_ITEM_CONTENT@7..9
+                PARAGRAPH@7..9
+                  TEXT@7..9 "b\n"
+          PARAGRAPH@9..14
+            TEXT@9..14 "  \tc\n"
+          LIST@14..22
+            LIST_ITEM@14..22
+              LIST_ITEM_INDENT@14..18 "    "
+              LIST_ITEM_BULLET@18..20 "- "
+              LIST_ITEM_CONTENT@20..22
+                PARAGRAPH@20..22
+                  TEXT@20..22 "d\n"
+    "#
+    );
+
     to_list("- ");
     to_list("-\t");
     to_list("-\r");
@@ -1,6 +1,7 @@
 //! Org-mode elements
 
 pub mod block;
+pub mod citation;
 pub mod clock;
 #[cfg(feature = "syntax-org-fc")]
 pub mod cloze;
@@ -19,6 +20,7 @@ pub mod fn_ref;
 pub mod headline;
 pub mod inline_call;
 pub mod inline_src;
+pub mod inline_task;
 pub mod input;
 pub mod keyword;
 pub mod latex_environment;
@@ -110,6 +112,7 @@ pub enum SyntaxKind {
     DOUBLE_ARROW, // '=>'
     PIPE,         // '|'
     COMMA,        // ','
+    SEMICOLON,    // ';'
     CARET,        // '^'
     NEW_LINE,     // '\n' or '\r\n' or '\r'
     WHITESPACE,   // ' ' or '\t'
@@ -128,6 +131,8 @@ pub enum SyntaxKind {
     HEADLINE_PRIORITY,
     HEADLINE_TAGS,
     PROPERTY_DRAWER,
+    INLINE_TASK,
+    INLINE_TASK_END,
     NODE_PROPERTY,
     PLANNING,
     PLANNING_DEADLINE,
@@ -159,6 +164,7 @@ pub enum SyntaxKind {
     KEYWORD,
     BABEL_CALL,
     AFFILIATED_KEYWORD,
+    KEYWORD_OPTIONAL,
     TABLE_EL,
     CLOCK,
     FN_DEF,
@@ -168,6 +174,8 @@ pub enum SyntaxKind {
     /* dyn block */
     DYN_BLOCK,
     DYN_BLOCK_BEGIN,
+    DYN_BLOCK_NAME,
+    DYN_BLOCK_PARAMETERS,
     DYN_BLOCK_END,
     /* block */
     SPECIAL_BLOCK,
@@ -214,6 +222,12 @@ pub enum SyntaxKind {
     ENTITY,
     SUPERSCRIPT,
     SUBSCRIPT,
+    CITATION,
+    CITATION_STYLE,
+    CITATION_PREFIX,
+    CITATION_SUFFIX,
+    CITATION_REFERENCE,
+    CITATION_KEY,
 
     /* timestamp */
     TIMESTAMP_ACTIVE,
@@ -251,6 +265,7 @@ impl SyntaxKind {
                 | SyntaxKind::LATEX_FRAGMENT
                 | SyntaxKind::SNIPPET
                 | SyntaxKind::FN_REF
+                | SyntaxKind::CITATION
                 | SyntaxKind::INLINE_CALL
                 | SyntaxKind::INLINE_SRC
                 | SyntaxKind::LINE_BREAK
@@ -314,6 +329,7 @@ impl SyntaxKind {
                 | SyntaxKind::SPECIAL_BLOCK
                 | SyntaxKind::DRAWER
                 | SyntaxKind::DYN_BLOCK
+                | SyntaxKind::INLINE_TASK
                 | SyntaxKind::FN_DEF
                 | SyntaxKind::LIST_ITEM
                 | SyntaxKind::LIST
@@ -1,6 +1,7 @@
 use nom::{IResult, InputTake};
 
 use super::{
+    citation::citation_node,
     combinator::GreenElement,
     cookie::cookie_node,
     emphasis::{
@@ -120,12 +121,12 @@ pub fn minimal_object_nodes(input: Input) -> Vec<GreenElement> {
     object_nodes(
         ObjectPositions::minimal,
         |i: Input, pre: Input| match &i.as_bytes()[0] {
-            b'*' if emphasis::verify_pre(pre.s) => bold_node(i),
-            b'+' if emphasis::verify_pre(pre.s) => strike_node(i),
-            b'/' if emphasis::verify_pre(pre.s) => italic_node(i),
-            b'_' if emphasis::verify_pre(pre.s) => underline_node(i),
-            b'=' if emphasis::verify_pre(pre.s) => verbatim_node(i),
-            b'~' if emphasis::verify_pre(pre.s) => code_node(i),
+            b'*' if emphasis::verify_pre(&pre) => bold_node(i),
+            b'+' if emphasis::verify_pre(&pre) => strike_node(i),
+            b'/' if emphasis::verify_pre(&pre) => italic_node(i),
+            b'_' if emphasis::verify_pre(&pre) => underline_node(i),
+            b'=' if emphasis::verify_pre(&pre) => verbatim_node(i),
+            b'~' if emphasis::verify_pre(&pre) => code_node(i),
             b'$' => latex_fragment_node(i),
             b'\\' => entity_node(i).or_else(|_| latex_fragment_node(i)),
             b'^' if subscript_superscript::verify_pre(&pre) => superscript_node(i),
@@ -152,20 +153,18 @@ pub fn minimal_object_nodes(input: Input) -> Vec<GreenElement> {
 /// - Text Markup (bold code strike verbatim underline italic)
 /// - Line Breaks
 /// - Subscript and Superscript
-/// - Cloze (if `syntax-org-fc` is enabled)
-///
-/// // todo:
 /// - Citations
+/// - Cloze (if `syntax-org-fc` is enabled)
 pub fn standard_object_nodes(input: Input) -> Vec<GreenElement> {
     object_nodes(
         ObjectPositions::standard,
         |i: Input, pre: Input| match &i.as_bytes()[0] {
-            b'*' if emphasis::verify_pre(pre.s) => bold_node(i),
-            b'+' if emphasis::verify_pre(pre.s) => strike_node(i),
-            b'/' if emphasis::verify_pre(pre.s) => italic_node(i),
-            b'_' if emphasis::verify_pre(pre.s) => underline_node(i),
-            b'=' if emphasis::verify_pre(pre.s) => verbatim_node(i),
-            b'~' if emphasis::verify_pre(pre.s) => code_node(i),
+            b'*' if emphasis::verify_pre(&pre) => bold_node(i),
+            b'+' if emphasis::verify_pre(&pre) => strike_node(i),
+            b'/' if emphasis::verify_pre(&pre) => italic_node(i),
+            b'_' if emphasis::verify_pre(&pre) => underline_node(i),
+            b'=' if emphasis::verify_pre(&pre) => verbatim_node(i),
+            b'~' if emphasis::verify_pre(&pre) => code_node(i),
             b'@' => snippet_node(i),
             b'{' => {
                 cfg_if::cfg_if! {
@@ -183,11 +182,12 @@ pub fn standard_object_nodes(input: Input) -> Vec<GreenElement> {
             b'[' => cookie_node(i)
                 .or_else(|_| link_node(i))
                 .or_else(|_| fn_ref_node(i))
+                .or_else(|_| citation_node(i))
                 .or_else(|_| timestamp_inactive_node(i)),
             // NOTE: although not specified in document, inline call and inline src follows the
             // same pre tokens rule as text markup
-            b'c' if emphasis::verify_pre(pre.s) => inline_call_node(i),
-            b's' if emphasis::verify_pre(pre.s) => inline_src_node(i),
+            b'c' if emphasis::verify_pre(&pre) => inline_call_node(i),
+            b's' if emphasis::verify_pre(&pre) => inline_src_node(i),
             b'$' => latex_fragment_node(i),
             b'\\' if !pre.s.ends_with('\\') && i.as_bytes()[1] == b'\\' => line_break_node(i),
             b'\\' => entity_node(i).or_else(|_| latex_fragment_node(i)),
@@ -204,16 +204,16 @@ pub fn link_description_object_nodes(input: Input) -> Vec<GreenElement> {
         ObjectPositions::link_description,
         |i: Input<'_>, pre: Input<'_>| match &i.as_bytes()[0] {
             b'@' => snippet_node(i),
-            b'c' if emphasis::verify_pre(pre.s) => inline_call_node(i),
-            b's' if emphasis::verify_pre(pre.s) => inline_src_node(i),
+            b'c' if emphasis::verify_pre(&pre) => inline_call_node(i),
+            b's' if emphasis::verify_pre(&pre) => inline_src_node(i),
             b'{' => macros_node(i),
             b'[' => cookie_node(i),
-            b'*' if emphasis::verify_pre(pre.s) => bold_node(i),
-            b'+' if emphasis::verify_pre(pre.s) => strike_node(i),
-            b'/' if emphasis::verify_pre(pre.s) => italic_node(i),
-            b'_' if emphasis::verify_pre(pre.s) => underline_node(i),
-            b'=' if emphasis::verify_pre(pre.s) => verbatim_node(i),
-            b'~' if emphasis::verify_pre(pre.s) => code_node(i),
+            b'*' if emphasis::verify_pre(&pre) => bold_node(i),
+            b'+' if emphasis::verify_pre(&pre) => strike_node(i),
+            b'/' if emphasis::verify_pre(&pre) => italic_node(i),
+            b'_' if emphasis::verify_pre(&pre) => underline_node(i),
+            b'=' if emphasis::verify_pre(&pre) => verbatim_node(i),
+            b'~' if emphasis::verify_pre(&pre) => code_node(i),
             b'$' => latex_fragment_node(i),
             b'\\' => entity_node(i).or_else(|_| latex_fragment_node(i)),
             b'^' if subscript_superscript::verify_pre(&pre) => superscript_node(i),
@@ -9,13 +9,13 @@ use nom::{
 use super::{
     combinator::{blank_lines, line_ends_iter, node, pipe_token, GreenElement, NodeBuilder},
     input::Input,
-    keyword::tblfm_keyword_nodes,
+    keyword::{affiliated_keyword_nodes, tblfm_keyword_nodes},
     object::standard_object_nodes,
     SyntaxKind::*,
 };
 
 fn org_table_node_base(input: Input) -> IResult<Input, GreenElement, ()> {
-    let mut children = vec![];
+    let (input, mut children) = affiliated_keyword_nodes(input)?;
 
     let mut start = 0;
     for i in line_ends_iter(input.as_str()) {
@@ -234,6 +234,61 @@ r#"|
         TEXT@29..35 " test2"
     "###
     );
+
+    insta::assert_debug_snapshot!(
+        to_org_table("#+CAPTION: numbers\n| 1 |").syntax,
+        @r##"
+    ORG_TABLE@0..24
+      AFFILIATED_KEYWORD@0..19
+        HASH_PLUS@0..2 "#+"
+        TEXT@2..9 "CAPTION"
+        COLON@9..10 ":"
+        TEXT@10..18 " numbers"
+        NEW_LINE@18..19 "\n"
+      ORG_TABLE_STANDARD_ROW@19..24
+        PIPE@19..20 "|"
+        WHITESPACE@20..21 " "
+        ORG_TABLE_CELL@21..22
+          TEXT@21..22 "1"
+        WHITESPACE@22..23 " "
+        PIPE@23..24 "|"
+    "##
+    );
+
+    // affiliated keywords belong to the table, tblfm keywords follow it
+    insta::assert_debug_snapshot!(
+        to_org_table("#+NAME: t\n#+ATTR_HTML: :class wide\n| 1 |\n#+TBLFM: $1=1\n\n").syntax,
+        @r##"
+    ORG_TABLE@0..56
+      AFFILIATED_KEYWORD@0..10
+        HASH_PLUS@0..2 "#+"
+        TEXT@2..6 "NAME"
+        COLON@6..7 ":"
+        TEXT@7..9 " t"
+        NEW_LINE@9..10 "\n"
+      AFFILIATED_KEYWORD@10..35
+        HASH_PLUS@10..12 "#+"
+        TEXT@12..21 "ATTR_HTML"
+        COLON@21..22 ":"
+        TEXT@22..34 " :class wide"
+        NEW_LINE@34..35 "\n"
+      ORG_TABLE_STANDARD_ROW@35..41
+        PIPE@35..36 "|"
+        WHITESPACE@36..37 " "
+        ORG_TABLE_CELL@37..38
+          TEXT@37..38 "1"
+        WHITESPACE@38..39 " "
+        PIPE@39..40 "|"
+        WHITESPACE@40..41 "\n"
+      KEYWORD@41..55
+        HASH_PLUS@41..43 "#+"
+        TEXT@43..48 "TBLFM"
+        COLON@48..49 ":"
+        TEXT@49..54 " $1=1"
+        NEW_LINE@54..55 "\n"
+      BLANK_LINE@55..56 "\n"
+    "##
+    );
 }
 
 #[test]
@@ -0,0 +1,146 @@
+use std::collections::{HashMap, HashSet};
+
+use rowan::ast::AstNode;
+
+use crate::ast::{headline_anchors, OptionValue};
+use crate::export::{Container, Event, ExportTags, TraversalContext, Traverser};
+use crate::{Org, SyntaxNode};
+
+/// Collects table of contents entries while traversing
+struct TocCollector {
+    max_depth: usize,
+    excluded: HashSet<SyntaxNode>,
+    anchors: HashMap<SyntaxNode, String>,
+    entries: Vec<(usize, String, String)>,
+}
+
+impl Traverser for TocCollector {
+    fn event(&mut self, event: Event, ctx: &mut TraversalContext) {
+        match event {
+            Event::Enter(Container::Headline(headline)) => {
+                let level = headline.level();
+
+                if headline.is_commented()
+                    || self.excluded.contains(headline.syntax())
+                    || headline
+                        .property("UNNUMBERED")
+                        .is_some_and(|value| value.trim().eq_ignore_ascii_case("notoc"))
+                    || level > self.max_depth
+                {
+                    return ctx.skip();
+                }
+
+                self.entries.push((
+                    level,
+                    headline.title_raw().trim().to_string(),
+                    self.anchors
+                        .get(headline.syntax())
+                        .cloned()
+                        .unwrap_or_default(),
+                ));
+            }
+            // headlines never appear inside sections
+            Event::Enter(Container::Section(_)) => ctx.skip(),
+            _ => {}
+        }
+    }
+}
+
+impl Org {
+    /// Returns table of contents as `(level, title, anchor)` tuples in document order
+    ///
+    /// Commented headlines, headlines excluded by export tags and headlines
+    /// whose `UNNUMBERED` property is `notoc` are excluded along with their
+    /// subtrees. Export tags are read from the document, see
+    /// [`ExportTags::from_document`]. Other unnumbered headlines are still listed, see
+    /// [`Org::section_numbers`]. Depth is further limited by `toc:N` in
+    /// `#+OPTIONS`, and `toc:nil` disables table of contents entirely.
+    ///
+    /// Anchors are the same ids used by [`HtmlExport`](crate::export::HtmlExport),
+    /// see [Headline::anchor](crate::ast::Headline::anchor).
+    ///
+    /// ```rust
+    /// use orgize::Org;
+    ///
+    /// let org = Org::parse(r#"* Introduction
+    /// ** Motivation
+    /// *** Details
+    /// * COMMENT Draft
+    /// ** Hidden
+    /// * Notes :noexport:
+    /// * Usage
+    /// "#);
+    /// assert_eq!(
+    ///     org.table_of_contents(2),
+    ///     vec![
+    ///         (1, "Introduction".into(), "introduction".into()),
+    ///         (2, "Motivation".into(), "motivation".into()),
+    ///         (1, "Usage".into(), "usage".into()),
+    ///     ]
+    /// );
+    ///
+    /// let org = Org::parse(r#"* Preface
+    /// :PROPERTIES:
+    /// :UNNUMBERED: notoc
+    /// :END:
+    /// ** Thanks
+    /// * Body
+    /// * Appendix
+    /// :PROPERTIES:
+    /// :UNNUMBERED: t
+    /// :END:
+    /// "#);
+    /// assert_eq!(
+    ///     org.table_of_contents(2),
+    ///     vec![
+    ///         (1, "Body".into(), "body".into()),
+    ///         (1, "Appendix".into(), "appendix".into()),
+    ///     ]
+    /// );
+    /// assert_eq!(org.section_numbers(2), vec![("body".into(), "1".into())]);
+    ///
+    /// let org = Org::parse("#+OPTIONS: H:3 toc:1\n* a\n** b");
+    /// assert_eq!(org.table_of_contents(3), vec![(1, "a".into(), "a".into())]);
+    ///
+    /// let org = Org::parse("#+EXPORT_EXCLUDE_TAGS: draft\n* a :draft:\n* b :noexport:");
+    /// assert_eq!(org.table_of_contents(3), vec![(1, "b".into(), "b".into())]);
+    ///
+    /// let org = Org::parse("#+OPTIONS: toc:nil\n* a\n** b");
+    /// assert!(org.table_of_contents(3).is_empty());
+    /// ```
+    pub fn table_of_contents(&self, max_depth: usize) -> Vec<(usize, String, String)> {
+        let max_depth = match self.toc_option() {
+            Some(TocOption::Nil) => return vec![],
+            Some(TocOption::Depth(depth)) => max_depth.min(depth),
+            Some(TocOption::All) | None => max_depth,
+        };
+
+        let tags = ExportTags::from_document(&self.document());
+        let mut collector = TocCollector {
+            max_depth,
+            excluded: tags.excluded_headlines(self.document().syntax()),
+            anchors: headline_anchors(self.document().syntax())
+                .into_iter()
+                .map(|(hdl, anchor)| (hdl.syntax().clone(), anchor))
+                .collect(),
+            entries: vec![],
+        };
+        self.traverse(&mut collector);
+        collector.entries
+    }
+
+    /// Returns the `toc` item in `#+OPTIONS`
+    fn toc_option(&self) -> Option<TocOption> {
+        match self.options().remove("toc")? {
+            OptionValue::Bool(false) => Some(TocOption::Nil),
+            OptionValue::Integer(depth) => Some(TocOption::Depth(depth)),
+            _ => Some(TocOption::All),
+        }
+    }
+}
+
+enum TocOption {
+    Nil,
+    All,
+    Depth(usize),
+}
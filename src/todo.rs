@@ -0,0 +1,175 @@
+use rowan::{ast::AstNode, TextRange, TextSize};
+
+use crate::ast::{Headline, TodoType};
+use crate::{Org, SyntaxKind, SyntaxNode};
+
+impl Org {
+    /// Advances todo keyword of given headline, similar to `org-todo`
+    ///
+    /// Keywords cycle through the configured sequence: no keyword, todo
+    /// keywords in order, the first done keyword, then no keyword again. Other
+    /// done keywords also go back to no keyword.
+    ///
+    /// Entering a done state adds a `CLOSED:` planning timestamp with current
+    /// time, and leaving it removes the timestamp. Current time is local time
+    /// if `chrono` feature is enabled, otherwise UTC.
+    ///
+    /// ```rust
+    /// use orgize::{Org, ast::Headline};
+    ///
+    /// let mut org = Org::parse("* [#A] a :tag:\nSCHEDULED: <2024-01-01>\n");
+    ///
+    /// org.cycle_todo(&org.first_node::<Headline>().unwrap());
+    /// assert_eq!(org.to_org(), "* TODO [#A] a :tag:\nSCHEDULED: <2024-01-01>\n");
+    ///
+    /// org.cycle_todo(&org.first_node::<Headline>().unwrap());
+    /// let hdl = org.first_node::<Headline>().unwrap();
+    /// assert_eq!(hdl.todo_keyword().unwrap(), "DONE");
+    /// assert_eq!(hdl.priority().unwrap(), "A");
+    /// assert_eq!(hdl.tags().count(), 1);
+    /// assert!(hdl.closed().is_some());
+    /// assert!(org.to_org().starts_with("* DONE [#A] a :tag:\nCLOSED: ["));
+    /// assert!(org.to_org().ends_with("] SCHEDULED: <2024-01-01>\n"));
+    ///
+    /// org.cycle_todo(&org.first_node::<Headline>().unwrap());
+    /// assert_eq!(org.to_org(), "* [#A] a :tag:\nSCHEDULED: <2024-01-01>\n");
+    ///
+    /// let mut org = Org::parse("* DONE a\nCLOSED: [2024-01-01 Mon 10:00]\n** b");
+    /// org.cycle_todo(&org.first_node::<Headline>().unwrap());
+    /// assert_eq!(org.to_org(), "* a\n** b");
+    /// ```
+    pub fn cycle_todo(&mut self, headline: &Headline) {
+        let (todo, done) = &self.config.todo_keywords;
+        let keyword = headline.todo_keyword();
+
+        let next = match &keyword {
+            None => todo.first().or(done.first()),
+            Some(kw) => match todo.iter().position(|t| t == kw.as_ref()) {
+                Some(idx) => todo.get(idx + 1).or(done.first()),
+                None => None,
+            },
+        };
+        let was_done = headline.todo_type() == Some(TodoType::Done);
+        let will_be_done = next.is_some_and(|next| done.contains(next));
+
+        let mut edits = vec![];
+
+        match (&keyword, next) {
+            (Some(kw), Some(next)) => edits.push((kw.text_range(), next.clone())),
+            (Some(kw), None) => {
+                let end = kw
+                    .syntax()
+                    .next_sibling_or_token()
+                    .filter(|e| e.kind() == SyntaxKind::WHITESPACE)
+                    .map_or(kw.end(), |e| e.text_range().end());
+                edits.push((TextRange::new(kw.start(), end), String::new()));
+            }
+            (None, Some(next)) => {
+                let offset = headline
+                    .syntax()
+                    .children_with_tokens()
+                    .find(|e| e.kind() == SyntaxKind::WHITESPACE)
+                    .map_or(headline.start() + TextSize::of("*"), |e| {
+                        e.text_range().end()
+                    });
+                edits.push((TextRange::empty(offset), format!("{next} ")));
+            }
+            (None, None) => {}
+        }
+
+        if !was_done && will_be_done {
+            let closed = format!("CLOSED: [{}]", now());
+            match headline.planning() {
+                Some(planning) => {
+                    let item = planning
+                        .syntax()
+                        .first_child()
+                        .and_then(|n| {
+                            n.children_with_tokens()
+                                .find(|e| e.kind() != SyntaxKind::WHITESPACE)
+                        })
+                        .map_or(planning.start(), |e| e.text_range().start());
+                    edits.push((TextRange::empty(item), format!("{closed} ")));
+                }
+                None => match headline
+                    .syntax()
+                    .children_with_tokens()
+                    .find(|e| e.kind() == SyntaxKind::NEW_LINE)
+                {
+                    Some(newline) => edits.push((
+                        TextRange::empty(newline.text_range().end()),
+                        format!("{closed}\n"),
+                    )),
+                    None => edits.push((TextRange::empty(headline.end()), format!("\n{closed}"))),
+                },
+            }
+        } else if was_done && !will_be_done {
+            if let Some(planning) = headline.planning() {
+                let items: Vec<_> = planning
+                    .syntax()
+                    .children()
+                    .filter(|n| {
+                        matches!(
+                            n.kind(),
+                            SyntaxKind::PLANNING_CLOSED
+                                | SyntaxKind::PLANNING_DEADLINE
+                                | SyntaxKind::PLANNING_SCHEDULED
+                        )
+                    })
+                    .collect();
+                let idx = items
+                    .iter()
+                    .position(|n| n.kind() == SyntaxKind::PLANNING_CLOSED);
+
+                match idx {
+                    Some(_) if items.len() == 1 => {
+                        edits.push((planning.syntax().text_range(), String::new()))
+                    }
+                    Some(idx) => {
+                        let first_text = |n: &SyntaxNode| {
+                            n.children_with_tokens()
+                                .find(|e| e.kind() != SyntaxKind::WHITESPACE)
+                                .map_or(n.text_range().start(), |e| e.text_range().start())
+                        };
+                        let range = match items.get(idx + 1) {
+                            // keep indentation of the closed item for the following one
+                            Some(next) => TextRange::new(first_text(&items[idx]), first_text(next)),
+                            None => items[idx].text_range(),
+                        };
+                        edits.push((range, String::new()));
+                    }
+                    None => {}
+                }
+            }
+        }
+
+        self.edit_headline(headline, edits);
+    }
+}
+
+/// Formats current time as the inside of an inactive timestamp
+#[cfg(feature = "chrono")]
+fn now() -> String {
+    chrono::Local::now().format("%Y-%m-%d %a %H:%M").to_string()
+}
+
+/// Formats current time as the inside of an inactive timestamp
+#[cfg(not(feature = "chrono"))]
+fn now() -> String {
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    const WEEKDAYS: [&str; 7] = ["Thu", "Fri", "Sat", "Sun", "Mon", "Tue", "Wed"];
+
+    let secs = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs());
+    let days = (secs / 86400) as i64;
+    let (hour, minute) = (secs % 86400 / 3600, secs % 3600 / 60);
+
+    let (year, month, day) = crate::date::civil_from_days(days);
+
+    format!(
+        "{year:04}-{month:02}-{day:02} {} {hour:02}:{minute:02}",
+        WEEKDAYS[days.rem_euclid(7) as usize]
+    )
+}
@@ -1,4 +1,4 @@
-use orgize::Org;
+use orgize::{export::HtmlExport, Org};
 
 #[test]
 fn emphasis() {
@@ -32,13 +32,13 @@ section 3
 * title 4
 section 4
 "#).to_html(),
-        @r###"
-    <main><h1>title 1</h1><section><p>section 1
-    </p></section><h2>title 2</h2><section><p>section 2
-    </p></section><h1>title 3</h1><section><p>section 3
-    </p></section><h1>title 4</h1><section><p>section 4
+        @r#"
+    <main><h1 id="title-1">title 1</h1><section><p>section 1
+    </p></section><h2 id="title-2">title 2</h2><section><p>section 2
+    </p></section><h1 id="title-3">title 3</h1><section><p>section 3
+    </p></section><h1 id="title-4">title 4</h1><section><p>section 4
     </p></section></main>
-    "###
+    "#
     );
 }
 
@@ -89,13 +89,13 @@ paragraph 3
 
 paragraph 4
 "#).to_html(),
-        @r###"
-    <main><h1>title</h1><section><p></p><p>paragraph 1
+        @r#"
+    <main><h1 id="title">title</h1><section><p></p><p>paragraph 1
     </p><p>paragraph 2
     </p><p>paragraph 3
     </p><p>paragraph 4
     </p></section></main>
-    "###
+    "#
     );
 }
 
@@ -109,7 +109,7 @@ fn table() {
 |   4 |   5 |   6 |
 |-----+-----+-----|
 "#).to_html(),
-        @"<main><section><table><tbody><tr><td>0</td><td>1</td><td>2</td></tr><tr><td>4</td><td>5</td><td>6</td></tr></tbody></table></section></main>"
+        @r#"<main><section><table><tbody><tr><td style="text-align: right">0</td><td style="text-align: right">1</td><td style="text-align: right">2</td></tr><tr><td style="text-align: right">4</td><td style="text-align: right">5</td><td style="text-align: right">6</td></tr></tbody></table></section></main>"#
     );
 
     // has table header
@@ -120,7 +120,7 @@ fn table() {
 |   4 |   5 |   6 |
 |-----+-----+-----|
 "#).to_html(),
-        @"<main><section><table><thead><tr><td>0</td><td>1</td><td>2</td></tr></thead><tbody><tr><td>4</td><td>5</td><td>6</td></tr></tbody></table></section></main>"
+        @r#"<main><section><table><thead><tr><td style="text-align: right">0</td><td style="text-align: right">1</td><td style="text-align: right">2</td></tr></thead><tbody><tr><td style="text-align: right">4</td><td style="text-align: right">5</td><td style="text-align: right">6</td></tr></tbody></table></section></main>"#
     );
 
     // has two table body
@@ -132,7 +132,7 @@ fn table() {
 |-----+-----+-----|
 |   7 |   8 |   9 |
 "#).to_html(),
-        @"<main><section><table><thead><tr><td>0</td><td>1</td><td>2</td></tr></thead><tbody><tr><td>4</td><td>5</td><td>6</td></tr></tbody><tbody><tr><td>7</td><td>8</td><td>9</td></tr></tbody></table></section></main>"
+        @r#"<main><section><table><thead><tr><td style="text-align: right">0</td><td style="text-align: right">1</td><td style="text-align: right">2</td></tr></thead><tbody><tr><td style="text-align: right">4</td><td style="text-align: right">5</td><td style="text-align: right">6</td></tr></tbody><tbody><tr><td style="text-align: right">7</td><td style="text-align: right">8</td><td style="text-align: right">9</td></tr></tbody></table></section></main>"#
     );
 
     // multiple row rule
@@ -143,7 +143,7 @@ fn table() {
 |-----+-----+-----|
 |   4 |   5 |   6 |
 "#).to_html(),
-        @"<main><section><table><thead><tr><td>0</td><td>1</td><td>2</td></tr></thead><tbody><tr><td>4</td><td>5</td><td>6</td></tr></tbody></table></section></main>"
+        @r#"<main><section><table><thead><tr><td style="text-align: right">0</td><td style="text-align: right">1</td><td style="text-align: right">2</td></tr></thead><tbody><tr><td style="text-align: right">4</td><td style="text-align: right">5</td><td style="text-align: right">6</td></tr></tbody></table></section></main>"#
     );
 
     // empty
@@ -179,13 +179,760 @@ fn line_break() {
 fn footnote() {
     insta::assert_debug_snapshot!(
         Org::parse("[fn:1] In particular, the parser requires stars at column 0 to be\n").to_html(),
-        @r##""<main><section><aside class=\"footnote-definition\" ><a href=\"#footnote_1\" class=\"footnote-reference\" >[1]</a><span class=\"footnote-content\" id=\"footnote_1\" > In particular, the parser requires stars at column 0 to be</span></aside></section></main>""##
+        @r#""<main><section></section></main>""#
     );
     // "~org-inlinetask-min-level~[fn:oiml:The default value of \n~org-inlinetask-min-level~ is =15=.]"
     insta::assert_debug_snapshot!(
         Org::parse(
             "~org-inlinetask-min-level~[fn:oiml:The default value of \n~org-inlinetask-min-level~ is =15=.]"
         ).to_html(),
-        @r##""<main><section><p><code>org-inlinetask-min-level</code><a href=\"#footnote_oiml\" class=\"footnote-reference\">[oiml]</a><span class=\"footnote-content\" id=\"footnote_oiml\" >The default value of \n<code>org-inlinetask-min-level</code> is <code>15</code>.</span></p></section></main>""##
+        @r##""<main><section><p><code>org-inlinetask-min-level</code><a id=\"footnote-ref_oiml\" href=\"#footnote_oiml\" class=\"footnote-reference\">[1]</a></p></section><div class=\"footnotes\"><div class=\"footnote-definition\" id=\"footnote_oiml\"><a href=\"#footnote-ref_oiml\" class=\"footnote-reference\">[1]</a><span class=\"footnote-content\">The default value of \n<code>org-inlinetask-min-level</code> is <code>15</code>.</span></div></div></main>""##
+    );
+    insta::assert_snapshot!(
+        Org::parse(r#"a[fn:2] b[fn::anonymous] c[fn:missing] d[fn:1] e[fn:2]
+
+[fn:1] first, see[fn:3]
+
+[fn:2] second
+
+[fn:3] third
+
+[fn:unused] unused
+"#).to_html(),
+        @r##"
+    <main><section><p>a<a id="footnote-ref_2" href="#footnote_2" class="footnote-reference">[1]</a> b<a id="footnote-ref_anonymous-2" href="#footnote_anonymous-2" class="footnote-reference">[2]</a> c[missing] d<a id="footnote-ref_1" href="#footnote_1" class="footnote-reference">[3]</a> e<a href="#footnote_2" class="footnote-reference">[1]</a>
+    </p></section><div class="footnotes"><div class="footnote-definition" id="footnote_2"><a href="#footnote-ref_2" class="footnote-reference">[1]</a><span class="footnote-content"> second</span></div><div class="footnote-definition" id="footnote_anonymous-2"><a href="#footnote-ref_anonymous-2" class="footnote-reference">[2]</a><span class="footnote-content">anonymous</span></div><div class="footnote-definition" id="footnote_1"><a href="#footnote-ref_1" class="footnote-reference">[3]</a><span class="footnote-content"> first, see<a id="footnote-ref_3" href="#footnote_3" class="footnote-reference">[4]</a></span></div><div class="footnote-definition" id="footnote_3"><a href="#footnote-ref_3" class="footnote-reference">[4]</a><span class="footnote-content"> third</span></div></div></main>
+    "##
+    );
+}
+
+#[test]
+fn export_block() {
+    insta::assert_snapshot!(
+        Org::parse(r#"
+#+BEGIN_EXPORT html
+<style>.red { color: red; }</style>
+#+END_EXPORT
+"#).to_html(),
+        @r###"
+    <main><section><style>.red { color: red; }</style>
+    </section></main>
+    "###
+    );
+
+    insta::assert_snapshot!(
+        Org::parse(r#"
+#+BEGIN_EXPORT latex
+\newpage
+#+END_EXPORT
+"#).to_html(),
+        @"<main><section></section></main>"
+    );
+
+    // org export block is parsed and exported again
+    insta::assert_snapshot!(
+        Org::parse(r#"
+#+BEGIN_EXPORT org
+,#+BEGIN_EXPORT html
+<style>.red { color: red; }</style>
+,#+END_EXPORT
+*bold*
+#+END_EXPORT
+"#).to_html(),
+        @r###"
+    <main><section><style>.red { color: red; }</style>
+    <p><b>bold</b>
+    </p></section></main>
+    "###
+    );
+
+    // nested org is parsed with the document config
+    let config = orgize::ParseConfig {
+        todo_keywords: (vec!["WAIT".into()], vec![]),
+        ..Default::default()
+    };
+    insta::assert_snapshot!(
+        config.parse(r#"
+#+BEGIN_EXPORT org
+,* WAIT Review
+#+END_EXPORT
+"#).to_html(),
+        @r#"<main><section><h1 id="review">Review</h1></section></main>"#
+    );
+}
+
+#[test]
+fn internal_link() {
+    insta::assert_snapshot!(
+        Org::parse(r#"
+* Introduction
+:PROPERTIES:
+:CUSTOM_ID: intro
+:END:
+#+NAME: fig
+[[file:a.png]]
+
+#+NAME: tbl
+| 1 |
+
+[[#intro]] [[*Introduction]] [[Introduction]] [[fig][figure]] [[tbl][table]] [[(jump)]] [[missing]]
+"#).to_html(),
+        @r##"
+    <main><h1 id="intro">Introduction</h1><section><p id="fig"><img src="a.png">
+    </p><table id="tbl"><tbody><tr><td style="text-align: right">1</td></tr></tbody></table><p><a href="#intro">#intro</a> <a href="#intro">*Introduction</a> <a href="#intro">Introduction</a> <a href="#fig">figure</a> <a href="#tbl">table</a> <a class="broken">(jump)</a> <a class="broken">missing</a>
+    </p></section></main>
+    "##
+    );
+}
+
+#[test]
+fn emphasis_regexp_components() {
+    use orgize::{config::EmphasisRegexpComponents, ParseConfig};
+
+    let input = "intra*word*emphasis";
+
+    insta::assert_snapshot!(
+        Org::parse(input).to_html(),
+        @"<main><section><p>intra*word*emphasis</p></section></main>"
+    );
+
+    let config = ParseConfig {
+        emphasis_regexp_components: EmphasisRegexpComponents {
+            pre: None,
+            post: None,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    insta::assert_snapshot!(
+        config.clone().parse(input).to_html(),
+        @"<main><section><p>intra<b>word</b>emphasis</p></section></main>"
+    );
+
+    // captions are parsed with the same config
+    insta::assert_snapshot!(
+        config.parse(format!("#+CAPTION: {input}\n[[file:a.png]]")).to_html(),
+        @r#"<main><section><figure><img src="a.png"><figcaption>intra<b>word</b>emphasis</figcaption></figure></section></main>"#
+    );
+}
+
+#[test]
+fn source_block_figure() {
+    insta::assert_snapshot!(
+        Org::parse(r#"#+CAPTION: Sum of <two> numbers
+#+begin_src python :exports both
+print(1 + 1)
+#+end_src
+
+#+RESULTS:
+: 2
+
+#+CAPTION: Code only
+#+begin_src python
+print(1 + 1)
+#+end_src
+
+#+RESULTS:
+: 2
+"#).to_html(),
+        @r#"
+    <main><section><figure><pre><code class="language-python">print(1 + 1)
+    </code></pre>2
+    <figcaption>Sum of &lt;two&gt; numbers</figcaption></figure><pre><code class="language-python">print(1 + 1)
+    </code></pre>2
+    </section></main>
+    "#
+    );
+}
+
+#[test]
+fn headline_anchor() {
+    insta::assert_snapshot!(
+        Org::parse(r#"* Introduction
+* Introduction
+** Über /uns/?
+:PROPERTIES:
+:CUSTOM_ID: about
+:END:
+* Introduction
+
+[[*Introduction]] [[Über /uns/?]]
+"#).to_html(),
+        @r##"
+    <main><h1 id="introduction">Introduction</h1><h1 id="introduction-1">Introduction</h1><h2 id="about">Über <i>uns</i>?</h2><h1 id="introduction-2">Introduction</h1><section><p></p><p><a href="#introduction">*Introduction</a> <a href="#about">Über /uns/?</a>
+    </p></section></main>
+    "##
+    );
+
+    let mut html = HtmlExport::default().with_heading_self_links(true);
+    Org::parse("* a\n* a").traverse(&mut html);
+    insta::assert_snapshot!(html.finish(), @r##"<main><h1 id="a"><a class="anchor" href="#a"></a>a</h1><h1 id="a-1"><a class="anchor" href="#a-1"></a>a</h1></main>"##);
+}
+
+#[test]
+fn checkbox() {
+    insta::assert_snapshot!(
+        Org::parse(r#"Tasks [1/3]
+- [X] done
+- [ ] todo
+- [-] partial
+- plain
+
+Steps
+
+1. [X] first
+2. second
+
+Terms
+
+- [ ] term :: description
+- other :: description
+"#).to_html(),
+        @r#"
+    <main><section><p>Tasks [1/3]
+    </p><ul><li class="checkbox"><input type="checkbox" checked disabled> <p>done
+    </p></li><li class="checkbox"><input type="checkbox" disabled> <p>todo
+    </p></li><li class="checkbox indeterminate"><input type="checkbox" disabled> <p>partial
+    </p></li><li><p>plain
+    </p></li></ul><p>Steps
+    </p><ol><li class="checkbox"><input type="checkbox" checked disabled> <p>first
+    </p></li><li><p>second
+    </p></li></ol><p>Terms
+    </p><dl><dt class="checkbox"><input type="checkbox" disabled> term </dt><dd><p> description
+    </p></dd><dt>other </dt><dd><p> description
+    </p></dd></dl></section></main>
+    "#
+    );
+}
+
+#[test]
+fn table_alignment() {
+    insta::assert_snapshot!(
+        Org::parse(r#"| Name | Qty | Note |
+|------+-----+------|
+| <l>  | <r> | <c>  |
+| a    |   1 | x    |
+| b    |     | y    |
+|      |     | z    |
+| c    |
+"#).to_html(),
+        @r#"<main><section><table><thead><tr><td style="text-align: left">Name</td><td style="text-align: right">Qty</td><td style="text-align: center">Note</td></tr></thead><tbody><tr><td style="text-align: left">a</td><td style="text-align: right">1</td><td style="text-align: center">x</td></tr><tr><td style="text-align: left">b</td><td style="text-align: right"></td><td style="text-align: center">y</td></tr><tr><td style="text-align: left"></td><td style="text-align: right"></td><td style="text-align: center">z</td></tr><tr><td style="text-align: left">c</td><td style="text-align: right"></td><td style="text-align: center"></td></tr></tbody></table></section></main>"#
+    );
+}
+
+#[test]
+fn image_figure() {
+    insta::assert_snapshot!(
+        Org::parse(r#"#+CAPTION: The *planet* [[https://nasa.gov][Jupiter]]
+[[file:jupiter.jpg]]
+
+#+CAPTION: mixed content
+See [[file:mars.png]]
+
+[[file:venus.png]]
+"#).to_html(),
+        @r#"
+    <main><section><figure><img src="jupiter.jpg"><figcaption>The <b>planet</b> <a href="https://nasa.gov">Jupiter</a></figcaption></figure><p>See <img src="mars.png">
+    </p><p><img src="venus.png">
+    </p></section></main>
+    "#
+    );
+}
+
+#[test]
+fn table_caption() {
+    insta::assert_snapshot!(
+        Org::parse(r#"#+CAPTION: Sizes of <b> *planets*
+#+ATTR_HTML: :class data
+| Jupiter | 69911 |
+"#).to_html(),
+        @r#"<main><section><table class="data"><caption>Sizes of &lt;b&gt; <b>planets</b></caption><tbody><tr><td style="text-align: left">Jupiter</td><td style="text-align: right">69911</td></tr></tbody></table></section></main>"#
+    );
+}
+
+#[test]
+fn image_alt() {
+    insta::assert_snapshot!(
+        Org::parse(r#"[[file:a.png][an "alt" text]] [[https://example.com/b.svg]]"#).to_html(),
+        @r#"<main><section><p><img src="a.png" alt="an &quot;alt&quot; text"> <img src="https://example.com/b.svg"></p></section></main>"#
+    );
+}
+
+#[test]
+fn link_protocol() {
+    insta::assert_snapshot!(
+        Org::parse("[[file:docs/a.org::*Intro][a]] [[./b.pdf::12]] [[mailto:me@example.com]] [[file:c.png]]").to_html(),
+        @r#"<main><section><p><a href="docs/a.org">a</a> <a href="./b.pdf">./b.pdf</a> <a href="mailto:me@example.com">mailto:me@example.com</a> <img src="c.png"></p></section></main>"#
+    );
+}
+
+#[test]
+fn link_resolution() {
+    insta::assert_snapshot!(
+        Org::parse(r#"* Setup
+:PROPERTIES:
+:ID: 6c1f
+:END:
+See <<step one>> and
+#+begin_src sh
+make (ref:build)
+#+end_src
+
+[[id:6c1f]] [[step one]] [[(build)]] [[#nowhere]] [[*Missing]] [[id:0000]]
+"#).to_html(),
+        @r##"
+    <main><h1 id="setup">Setup</h1><section><p>See <a id="target-step-one"></a> and
+    </p><pre><code class="language-sh"><span id="coderef-build">make (ref:build)</span>
+    </code></pre><p><a href="#setup">id:6c1f</a> <a href="#target-step-one">step one</a> <a href="#coderef-build">(build)</a> <a class="broken">#nowhere</a> <a class="broken">*Missing</a> <a class="broken">id:0000</a>
+    </p></section></main>
+    "##
+    );
+}
+
+#[test]
+fn planning() {
+    insta::assert_snapshot!(
+        Org::parse(r#"* DONE a
+CLOSED: [2019-04-08 Mon 09:30] SCHEDULED: <2019-04-01 Mon +1w>
+<2019-04-10 Wed>
+** b
+
+DEADLINE: <2019-04-09 Tue>
+"#).to_html(),
+        @r#"
+    <main><h1 id="a">a</h1><p class="planning"><span class="closed">CLOSED: <time datetime="2019-04-08T09:30">[2019-04-08 Mon 09:30]</time></span> <span class="scheduled">SCHEDULED: <time datetime="2019-04-01">&lt;2019-04-01 Mon +1w&gt;</time></span></p><section><p><span class="timestamp-wrapper"><span class="timestamp"><2019-04-10 Wed></span></span>
+    </p></section><h2 id="b">b</h2><section><p></p><p>DEADLINE: <span class="timestamp-wrapper"><span class="timestamp"><2019-04-09 Tue></span></span>
+    </p></section></main>
+    "#
+    );
+}
+
+#[test]
+fn class_prefix() {
+    let mut html = HtmlExport::default().with_class_prefix("org-");
+    Org::parse(
+        r#"* a
+DEADLINE: <2019-04-09 Tue>
+- [-] foo[fn:1] <2019-04-10 Wed>
+#+begin_center
+#+begin_example
+b
+#+end_example
+#+end_center
+[[missing]]
+
+[fn:1] note
+"#,
+    )
+    .traverse(&mut html);
+    insta::assert_snapshot!(html.finish(), @r##"
+    <main><h1 id="a">a</h1><p class="org-planning"><span class="org-deadline">DEADLINE: <time datetime="2019-04-09">&lt;2019-04-09 Tue&gt;</time></span></p><section><ul><li class="org-checkbox org-indeterminate"><input type="checkbox" disabled> <p>foo<a id="footnote-ref_1" href="#footnote_1" class="org-footnote-reference">[1]</a> <span class="org-timestamp-wrapper"><span class="org-timestamp"><2019-04-10 Wed></span></span>
+    </p></li></ul><div class="org-center"><pre class="org-example">b
+    </pre></div><p><a class="org-broken">missing</a>
+    </p></section><div class="org-footnotes"><div class="org-footnote-definition" id="footnote_1"><a href="#footnote-ref_1" class="org-footnote-reference">[1]</a><span class="org-footnote-content"> note</span></div></div></main>
+    "##);
+}
+
+#[test]
+fn source_block_line_numbers() {
+    insta::assert_snapshot!(
+        Org::parse(r#"#+begin_src rust -n 20 -r
+fn main() { (ref:main)
+    println!("<hi>");
+}
+#+end_src
+
+#+begin_src rust +n 2
+tail (ref:tail)
+#+end_src
+
+#+begin_src sh -r
+echo (ref:strip)
+#+end_src
+
+#+begin_src sh
+echo (ref:keep)
+#+end_src
+"#).to_html(),
+        @r#"
+    <main><section><pre><code class="language-rust"><span class="line" data-line="20" id="coderef-main">fn main() {</span>
+    <span class="line" data-line="21">    println!(&quot;&lt;hi&gt;&quot;);</span>
+    <span class="line" data-line="22">}</span>
+    </code></pre><pre><code class="language-rust"><span class="line" data-line="24" id="coderef-tail">tail (ref:tail)</span>
+    </code></pre><pre><code class="language-sh"><span id="coderef-strip">echo</span>
+    </code></pre><pre><code class="language-sh"><span id="coderef-keep">echo (ref:keep)</span>
+    </code></pre></section></main>
+    "#
+    );
+}
+
+#[test]
+fn macros() {
+    insta::assert_snapshot!(
+        Org::parse(r#"#+TITLE: Doc
+#+MACRO: greet Hello, <b>$1</b>!
+#+MACRO: color @@html:<span style="color:$1">@@$2@@html:</span>@@
+#+MACRO: strong *$1*
+{{{title}}}: {{{greet(world)}}} {{{missing}}} {{{color(red, alert)}}} {{{strong(now)}}}
+"#).to_html(),
+        @r#"
+    <main><section><p>Doc: Hello, &lt;b&gt;world&lt;/b&gt;!  <span style="color:red">alert</span> <b>now</b>
+    </p></section></main>
+    "#
+    );
+}
+
+#[test]
+fn radio_target() {
+    insta::assert_snapshot!(
+        Org::parse(r#"* About <<<Org Mode>>>
+Write in org   mode, not in =org mode= or org modes.
+- [[https://orgmode.org][org mode]] and *ORG MODE*
+"#).to_html(),
+        @r##"
+    <main><h1 id="about-org-mode">About <a id="radio-org-mode"></a>Org Mode</h1><section><p>Write in <a href="#radio-org-mode">org   mode</a>, not in <code>org mode</code> or org modes.
+    </p><ul><li><p><a href="https://orgmode.org">org mode</a> and <b><a href="#radio-org-mode">ORG MODE</a></b>
+    </p></li></ul></section></main>
+    "##
+    );
+}
+
+#[test]
+fn math_delimiters() {
+    use orgize::export::MathDelimiters;
+
+    let org = Org::parse(
+        r#"$a$ and \[b\] and \ref{c}
+\begin{equation}
+x
+\end{equation}
+"#,
+    );
+
+    let mut html = HtmlExport::default().with_math_delimiters(MathDelimiters::MathJax);
+    org.traverse(&mut html);
+    insta::assert_snapshot!(html.finish(), @r"
+    <main><section><p>\(a\) and \[b\] and \ref{c}
+    </p>\begin{equation}
+    x
+    \end{equation}
+    </section></main>
+    ");
+
+    let mut html = HtmlExport::default().with_math_delimiters(MathDelimiters::KaTeX);
+    org.traverse(&mut html);
+    insta::assert_snapshot!(html.finish(), @r"
+    <main><section><p>$a$ and $$b$$ and \ref{c}
+    </p>$$\begin{equation}
+    x
+    \end{equation}$$
+    </section></main>
+    ");
+}
+
+#[test]
+fn export_tags() {
+    use orgize::export::ExportTags;
+
+    let org = Org::parse(
+        r#"#+EXPORT_EXCLUDE_TAGS: draft
+* a
+** b :draft:
+text
+** c :noexport:
+* d
+"#,
+    );
+
+    let mut html = HtmlExport::default();
+    org.traverse(&mut html);
+    insta::assert_snapshot!(html.finish(), @r#"
+    <main><section></section><h1 id="a">a</h1><h2 id="b">b </h2><section><p>text
+    </p></section><h2 id="c">c </h2><h1 id="d">d</h1></main>
+    "#);
+
+    let mut html =
+        HtmlExport::default().with_export_tags(ExportTags::from_document(&org.document()));
+    org.traverse(&mut html);
+    insta::assert_snapshot!(html.finish(), @r#"<main><section></section><h1 id="a">a</h1><h2 id="c">c </h2><h1 id="d">d</h1></main>"#);
+}
+
+#[test]
+fn commented_headline() {
+    let org = Org::parse(
+        r#"* a
+** COMMENT b
+text
+*** c
+** TODO [#A] COMMENT d
+** COMMENTS e
+* f
+"#,
+    );
+
+    let mut html = HtmlExport::default();
+    org.traverse(&mut html);
+    insta::assert_snapshot!(html.finish(), @r#"<main><h1 id="a">a</h1><h2 id="comments-e">COMMENTS e</h2><h1 id="f">f</h1></main>"#);
+}
+
+#[test]
+fn table_numeric_alignment() {
+    // numeric columns without cookie are right-aligned, cookies take precedence
+    insta::assert_snapshot!(
+        Org::parse(r#"
+| name  | qty | price | id  |
+|-------+-----+-------+-----|
+| <l>   | <5> | <5>   | <l> |
+| apple | 3   | $1.50 | 1   |
+| pear  | n/a | 20%   | 2   |
+"#).to_html(),
+        @r#"<main><section><table><thead><tr><td style="text-align: left">name</td><td style="text-align: right">qty</td><td style="text-align: right">price</td><td style="text-align: left">id</td></tr></thead><tbody><tr><td style="text-align: left">apple</td><td style="text-align: right">3</td><td style="text-align: right">$1.50</td><td style="text-align: left">1</td></tr><tr><td style="text-align: left">pear</td><td style="text-align: right">n/a</td><td style="text-align: right">20%</td><td style="text-align: left">2</td></tr></tbody></table></section></main>"#
+    );
+}
+
+#[test]
+fn attr_html() {
+    insta::assert_snapshot!(
+        Org::parse(r#"
+#+ATTR_HTML: :width 100 :alt "a cat"
+#+ATTR_LATEX: :width 5cm
+[[file:cat.png][cat]]
+
+#+ATTR_HTML: :class data :border 1
+| a |
+
+#+ATTR_HTML: :class wide
+#+BEGIN_EXAMPLE
+text
+#+END_EXAMPLE
+
+#+ATTR_HTML: :data-lang rust
+#+BEGIN_SRC rust
+fn main() {}
+#+END_SRC
+
+#+ATTR_HTML: :cite https://example.com
+#+BEGIN_QUOTE
+quote
+#+END_QUOTE
+"#).to_html(),
+        @r#"
+    <main><section><p><img src="cat.png" width="100" alt="a cat">
+    </p><table class="data" border="1"><tbody><tr><td style="text-align: left">a</td></tr></tbody></table><pre class="example wide">text
+    </pre><pre data-lang="rust"><code class="language-rust">fn main() {}
+    </code></pre><blockquote cite="https://example.com"><p>quote
+    </p></blockquote></section></main>
+    "#
+    );
+}
+
+#[test]
+fn verse_block() {
+    insta::assert_snapshot!(
+        Org::parse(r#"
+#+BEGIN_VERSE
+  Great *clouds* overhead
+    Tiny [[https://example.com][black]] birds
+
+Rise up
+#+END_VERSE
+"#).to_html(),
+        @r#"
+    <main><section><p class="verse">&nbsp;&nbsp;Great <b>clouds</b> overhead<br/>
+    &nbsp;&nbsp;&nbsp;&nbsp;Tiny <a href="https://example.com">black</a> birds<br/>
+    <br/>
+    Rise up
+    </p></section></main>
+    "#
+    );
+}
+
+#[test]
+fn verse_block_comma_quoted() {
+    insta::assert_snapshot!(
+        Org::parse(r#"
+#+BEGIN_VERSE
+,* not a *headline*
+,#+not a keyword
+#+END_VERSE
+"#).to_html(),
+        @r#"
+    <main><section><p class="verse">* not a <b>headline</b><br/>
+    #+not a keyword
+    </p></section></main>
+    "#
+    );
+}
+
+#[test]
+fn smart_quotes() {
+    let org = Org::parse(
+        r#"
+"Hello," he said -- (and 'paused') ... it's 1990--2000.
+
+#+BEGIN_EXAMPLE
+"as is" -- here
+#+END_EXAMPLE
+"#,
+    );
+    let mut html = HtmlExport::default().with_smart_quotes(true);
+    org.traverse(&mut html);
+    insta::assert_snapshot!(html.finish(), @r#"
+    <main><section><p>“Hello,” he said – (and ‘paused’) … it’s 1990–2000.
+    </p><pre class="example">&quot;as is&quot; -- here
+    </pre></section></main>
+    "#);
+}
+
+#[test]
+fn smart_quotes_large_document() {
+    let input = "#+OPTIONS: ':t\n".to_string()
+        + &"Paragraph *with* \"quotes\" and it's fine.\n\n".repeat(8000);
+    let html = Org::parse(input).to_html();
+    assert_eq!(
+        html.matches("<p>Paragraph <b>with</b> “quotes” and it’s fine.")
+            .count(),
+        8000
+    );
+}
+
+#[test]
+fn render_drawers() {
+    let org = Org::parse(
+        r#"* a
+:PROPERTIES:
+:ID: 1
+:END:
+:NOTES:
+Some *notes*
+- item
+:END:
+text
+"#,
+    );
+
+    let mut html = HtmlExport::default();
+    org.traverse(&mut html);
+    insta::assert_snapshot!(html.finish(), @r#"
+    <main><h1 id="a">a</h1><section><p>text
+    </p></section></main>
+    "#);
+
+    let mut html = HtmlExport::default().with_render_drawers(true);
+    org.traverse(&mut html);
+    insta::assert_snapshot!(html.finish(), @r#"
+    <main><h1 id="a">a</h1><section><div class="drawer"><p>Some <b>notes</b>
+    </p><ul><li><p>item
+    </p></li></ul></div><p>text
+    </p></section></main>
+    "#);
+}
+
+#[test]
+fn dyn_block() {
+    insta::assert_snapshot!(
+        Org::parse(r#"
+#+BEGIN: clocktable :scope file
+| Headline | Time |
+|----------+------|
+| *a*      | 1:00 |
+#+END:
+"#).to_html(),
+        @r#"<main><section><table><thead><tr><td style="text-align: left">Headline</td><td style="text-align: right">Time</td></tr></thead><tbody><tr><td style="text-align: left"><b>a</b></td><td style="text-align: right">1:00</td></tr></tbody></table></section></main>"#
+    );
+}
+
+#[test]
+fn citation() {
+    insta::assert_snapshot!(
+        Org::parse("As shown [cite/t:see @doe2020, p. 3;@roe] & [cite:@a].").to_html(),
+        @r#"<main><section><p>As shown <span class="citation">see @doe2020, p. 3;@roe</span> &amp; <span class="citation">@a</span>.</p></section></main>"#
+    );
+}
+
+#[test]
+fn inline_task() {
+    insta::assert_snapshot!(
+        Org::parse(r#"* headline
+text
+*************** TODO *inline* task
+body
+*************** END
+more text
+*************** single line task
+"#).to_html(),
+        @r#"
+    <main><h1 id="headline">headline</h1><section><p>text
+    </p><div class="inlinetask"><b><span class="todo">TODO</span> <b>inline</b> task</b><br/><p>body
+    </p></div><p>more text
+    </p><div class="inlinetask"><b>single line task</b><br/></div></section></main>
+    "#
+    );
+}
+
+#[test]
+fn section_numbers() {
+    insta::assert_snapshot!(
+        Org::parse(r#"#+OPTIONS: num:2
+* Intro
+** Background
+*** Details
+* Appendix
+:PROPERTIES:
+:UNNUMBERED: t
+:END:
+** Notes
+* Usage
+"#).to_html(),
+        @r#"<main><section></section><h1 id="intro"><span class="section-number">1</span> Intro</h1><h2 id="background"><span class="section-number">1.1</span> Background</h2><h3 id="details">Details</h3><h1 id="appendix">Appendix</h1><h2 id="notes">Notes</h2><h1 id="usage"><span class="section-number">2</span> Usage</h1></main>"#
+    );
+}
+
+#[test]
+fn crlf() {
+    insta::assert_snapshot!(
+        Org::parse("#+begin_src c -n\r\na\r\nb\r\n#+end_src\r\n#+begin_verse\r\nx\r\ny\r\n#+end_verse\r\n").to_html().replace('\r', "\\r"),
+        @r#"
+    <main><section><pre><code class="language-c"><span class="line" data-line="1">a</span>\r
+    <span class="line" data-line="2">b</span>\r
+    </code></pre><p class="verse">x<br/>
+    y\r
+    </p></section></main>
+    "#
+    );
+}
+
+#[test]
+fn user_entity_escape() {
+    use orgize::{
+        ast::EntityRepresentation, entities::EntityInfo, export::HtmlExport, ParseConfig,
+    };
+
+    let config = ParseConfig {
+        user_entities: vec![EntityInfo {
+            name: "tag".into(),
+            latex: "<tag>".into(),
+            latex_math: false,
+            html: "<b>tag</b>".into(),
+            ascii: "<tag>".into(),
+            latin1: "<tag>".into(),
+            utf8: "<tag>".into(),
+        }],
+        ..Default::default()
+    };
+    let org = config.parse("\\tag");
+
+    insta::assert_snapshot!(
+        org.to_html(),
+        @"<main><section><p><b>tag</b></p></section></main>"
+    );
+
+    let mut html = HtmlExport::default()
+        .with_user_entities(org.config().user_entities.clone())
+        .with_entity_representation(EntityRepresentation::Utf8);
+    org.traverse(&mut html);
+    insta::assert_snapshot!(
+        html.finish(),
+        @"<main><section><p>&lt;tag&gt;</p></section></main>"
     );
 }
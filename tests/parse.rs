@@ -35,3 +35,62 @@ fn parse() {
         let _ = orgize::Org::parse(input);
     }
 }
+
+#[test]
+fn crlf() {
+    use orgize::rowan::{ast::AstNode, NodeOrToken, WalkEvent};
+    use orgize::{Org, SyntaxKind};
+
+    const INPUT: &str = r#"#+TITLE: crlf
+* TODO headline :tag:
+  SCHEDULED: <2023-01-01 Sun>
+  :PROPERTIES:
+  :ID: abc
+  :END:
+  :LOGBOOK:
+  CLOCK: [2023-01-01 Sun 10:00]--[2023-01-01 Sun 11:00] =>  1:00
+  :END:
+
+paragraph *bold* [fn:1] line\\
+break
+- item
+  - [X] nested
+    1. ordered
+- tag :: description
+
+
+| a | b |
+|---+---|
+#+TBLFM: $2=$1
+#+BEGIN_SRC rust -n
+fn main() {}
+#+END_SRC
+#+BEGIN: clocktable
+#+END:
+: fixed width
+# comment
+-----
+[fn:1] footnote
+*************** inline task
+*************** END
+"#;
+
+    fn shape(input: &str) -> Vec<(SyntaxKind, String)> {
+        let org = Org::parse(input);
+        org.document()
+            .syntax()
+            .preorder_with_tokens()
+            .filter_map(|event| match event {
+                WalkEvent::Enter(NodeOrToken::Node(n)) => Some((n.kind(), String::new())),
+                WalkEvent::Enter(NodeOrToken::Token(t)) => {
+                    Some((t.kind(), t.text().replace("\r\n", "\n")))
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
+    let crlf = INPUT.replace('\n', "\r\n");
+    assert_eq!(shape(INPUT), shape(&crlf));
+    assert_eq!(Org::parse(&crlf).to_org(), crlf);
+}
@@ -1,8 +1,12 @@
 use orgize::{
-    export::{from_fn, Container, Event},
-    rowan::ast::AstNode,
-    Org as Inner,
+    ast::Headline,
+    config::UseSubSuperscript,
+    export::{from_fn, Container, Event, MarkdownExport},
+    rowan::{ast::AstNode, TextRange},
+    Org as Inner, ParseConfig, SyntaxKind, SyntaxNode,
 };
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
 use std::fmt::Write;
 
 use wasm_bindgen::prelude::*;
@@ -10,14 +14,124 @@ use wasm_bindgen::prelude::*;
 #[wasm_bindgen]
 pub struct Org {
     inner: Inner,
+    config: ParseConfig,
+}
+
+/// Parse options passed from javascript, all fields are optional
+///
+/// A malformed field is skipped on its own, so it doesn't discard the others.
+#[derive(Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+struct Options {
+    #[serde(deserialize_with = "lenient")]
+    todo_keywords: Option<Vec<String>>,
+    #[serde(deserialize_with = "lenient")]
+    done_keywords: Option<Vec<String>>,
+    #[serde(deserialize_with = "lenient")]
+    use_sub_superscript: Option<SubSuperscript>,
+    #[serde(deserialize_with = "lenient")]
+    affiliated_keywords: Option<Vec<String>>,
+}
+
+/// Deserializes an optional field, treating malformed value as missing
+fn lenient<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    Ok(T::deserialize(deserializer).ok())
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum SubSuperscript {
+    Nil,
+    Brace,
+    True,
+}
+
+impl Options {
+    /// Converts options object to parse config, invalid or missing options fall back to defaults
+    fn parse_config(options: JsValue) -> ParseConfig {
+        let options: Options = if options.is_undefined() || options.is_null() {
+            Options::default()
+        } else {
+            serde_wasm_bindgen::from_value(options).unwrap_or_default()
+        };
+        options.into_config()
+    }
+
+    /// Applies given options on top of default parse config
+    fn into_config(self) -> ParseConfig {
+        let mut config = ParseConfig::default();
+        if let Some(todo) = self.todo_keywords {
+            config.todo_keywords.0 = todo;
+        }
+        if let Some(done) = self.done_keywords {
+            config.todo_keywords.1 = done;
+        }
+        if let Some(mode) = self.use_sub_superscript {
+            config.use_sub_superscript = match mode {
+                SubSuperscript::Nil => UseSubSuperscript::Nil,
+                SubSuperscript::Brace => UseSubSuperscript::Brace,
+                SubSuperscript::True => UseSubSuperscript::True,
+            };
+        }
+        if let Some(keywords) = self.affiliated_keywords {
+            config.affiliated_keywords = keywords;
+        }
+        config
+    }
+}
+
+/// Syntax node returned to javascript
+#[derive(Serialize)]
+struct Node {
+    kind: String,
+    start: u32,
+    end: u32,
+}
+
+/// Headline returned to javascript
+#[derive(Serialize)]
+struct HeadlineInfo {
+    title: String,
+    level: usize,
+    start: u32,
+    end: u32,
+}
+
+/// Element in the tree returned by `traverseJson()`
+#[derive(Serialize)]
+struct TreeNode {
+    kind: Cow<'static, str>,
+    start: u32,
+    end: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    subtype: Option<String>,
+    children: Vec<TreeNode>,
+}
+
+impl TreeNode {
+    fn new(kind: Cow<'static, str>, range: TextRange, subtype: Option<String>) -> Self {
+        TreeNode {
+            kind,
+            start: range.start().into(),
+            end: range.end().into(),
+            subtype,
+            children: vec![],
+        }
+    }
 }
 
 #[wasm_bindgen]
 impl Org {
     #[wasm_bindgen(constructor)]
-    pub fn parse(input: &str) -> Self {
+    pub fn parse(input: &str, options: JsValue) -> Self {
+        let config = Options::parse_config(options);
         Org {
-            inner: Inner::parse(input),
+            inner: config.clone().parse(input),
+            config,
         }
     }
 
@@ -25,6 +139,13 @@ impl Org {
         self.inner.to_html()
     }
 
+    pub fn markdown(&self) -> String {
+        let mut markdown =
+            MarkdownExport::default().with_user_entities(self.config.user_entities.clone());
+        self.inner.traverse(&mut markdown);
+        markdown.finish()
+    }
+
     pub fn org(&self) -> String {
         self.inner.to_org()
     }
@@ -34,7 +155,59 @@ impl Org {
     }
 
     pub fn update(&mut self, s: &str) {
-        self.inner = Inner::parse(s);
+        self.inner = self.config.clone().parse(s);
+    }
+
+    #[wasm_bindgen(js_name = "updateWithConfig")]
+    pub fn update_with_config(&mut self, s: &str, options: JsValue) {
+        self.config = Options::parse_config(options);
+        self.update(s);
+    }
+
+    /// Returns kind and range of the innermost node at given offset, or `null` if
+    /// offset is out of range
+    ///
+    /// Offset and returned range are in UTF-8 bytes, same as `traverse()`.
+    #[wasm_bindgen(js_name = "nodeAtOffset")]
+    pub fn node_at_offset(&self, offset: u32) -> JsValue {
+        self.innermost_node(offset)
+            .and_then(|node| {
+                let range = node.text_range();
+                to_js(&Node {
+                    kind: format!("{:?}", node.kind()),
+                    start: range.start().into(),
+                    end: range.end().into(),
+                })
+            })
+            .unwrap_or(JsValue::NULL)
+    }
+
+    /// Returns title, level and range of the innermost headline containing given offset,
+    /// or `null` if offset is not inside any headline
+    #[wasm_bindgen(js_name = "headlineAt")]
+    pub fn headline_at(&self, offset: u32) -> JsValue {
+        self.innermost_node(offset)
+            .and_then(|node| node.ancestors().find_map(Headline::cast))
+            .and_then(|headline| {
+                let range = headline.syntax().text_range();
+                to_js(&HeadlineInfo {
+                    title: headline.title_raw(),
+                    level: headline.level(),
+                    start: range.start().into(),
+                    end: range.end().into(),
+                })
+            })
+            .unwrap_or(JsValue::NULL)
+    }
+
+    fn innermost_node(&self, offset: u32) -> Option<SyntaxNode> {
+        let root = self.inner.document().syntax().clone();
+        if offset > u32::from(root.text_range().end()) {
+            return None;
+        }
+        root.token_at_offset(offset.into())
+            .right_biased()
+            .and_then(|token| token.parent())
     }
 
     pub fn traverse(&self) -> String {
@@ -42,66 +215,12 @@ impl Org {
         let mut ident = 0;
         let mut handler = from_fn(|event| {
             let (name, range) = match &event {
-                Event::Enter(container) => match container {
-                    Container::Document(x) => ("Document", x.text_range()),
-                    Container::Section(x) => ("Section", x.text_range()),
-                    Container::Paragraph(x) => ("Paragraph", x.text_range()),
-                    Container::Headline(x) => ("Headline", x.text_range()),
-                    Container::OrgTable(x) => ("OrgTable", x.text_range()),
-                    Container::OrgTableRow(x) => ("OrgTableRow", x.text_range()),
-                    Container::OrgTableCell(x) => ("OrgTableCell", x.text_range()),
-                    Container::TableEl(x) => ("TableEl", x.text_range()),
-                    Container::List(x) => ("List", x.text_range()),
-                    Container::ListItem(x) => ("ListItem", x.text_range()),
-                    Container::Drawer(x) => ("Drawer", x.text_range()),
-                    Container::DynBlock(x) => ("DynBlock", x.text_range()),
-                    Container::FnDef(x) => ("FnDef", x.text_range()),
-                    Container::Comment(x) => ("Comment", x.text_range()),
-                    Container::FixedWidth(x) => ("FixedWidth", x.text_range()),
-                    Container::SpecialBlock(x) => ("SpecialBlock", x.text_range()),
-                    Container::QuoteBlock(x) => ("QuoteBlock", x.text_range()),
-                    Container::CenterBlock(x) => ("CenterBlock", x.text_range()),
-                    Container::VerseBlock(x) => ("VerseBlock", x.text_range()),
-                    Container::CommentBlock(x) => ("CommentBlock", x.text_range()),
-                    Container::ExampleBlock(x) => ("ExampleBlock", x.text_range()),
-                    Container::ExportBlock(x) => ("ExportBlock", x.text_range()),
-                    Container::SourceBlock(x) => ("SourceBlock", x.text_range()),
-                    Container::Link(x) => ("Link", x.text_range()),
-                    Container::RadioTarget(x) => ("RadioTarget", x.text_range()),
-                    Container::FnRef(x) => ("FnRef", x.text_range()),
-                    Container::Target(x) => ("Target", x.text_range()),
-                    Container::Bold(x) => ("Bold", x.text_range()),
-                    Container::Strike(x) => ("Strike", x.text_range()),
-                    Container::Italic(x) => ("Italic", x.text_range()),
-                    Container::Underline(x) => ("Underline", x.text_range()),
-                    Container::Verbatim(x) => ("Verbatim", x.text_range()),
-                    Container::Code(x) => ("Code", x.text_range()),
-                    Container::Superscript(x) => ("Superscript", x.text_range()),
-                    Container::Subscript(x) => ("Subscript", x.text_range()),
-                    Container::BabelCall(x) => ("BabelCall", x.text_range()),
-                    Container::PropertyDrawer(x) => ("PropertyDrawer", x.text_range()),
-                    Container::AffiliatedKeyword(x) => ("AffiliatedKeyword", x.text_range()),
-                    Container::Keyword(x) => ("Keyword", x.text_range()),
-                    _ => unreachable!(),
-                },
+                Event::Enter(container) => container_name(container),
                 Event::Leave(_) => {
                     ident -= 2;
                     return;
                 }
-                Event::Text(x) => ("Text", x.text_range()),
-                Event::Macros(x) => ("Macros", x.text_range()),
-                Event::Cookie(x) => ("Cookie", x.text_range()),
-                Event::InlineCall(x) => ("InlineCall", x.text_range()),
-                Event::InlineSrc(x) => ("InlineSrc", x.text_range()),
-                Event::Clock(x) => ("Clock", x.text_range()),
-                Event::LineBreak(x) => ("LineBreak", x.text_range()),
-                Event::Snippet(x) => ("Snippet", x.text_range()),
-                Event::Rule(x) => ("Rule", x.text_range()),
-                Event::Timestamp(x) => ("Timestamp", x.text_range()),
-                Event::LatexFragment(x) => ("LatexFragment", x.text_range()),
-                Event::LatexEnvironment(x) => ("LatexEnvironment", x.text_range()),
-                Event::Entity(x) => ("Entity", x.text_range()),
-                _ => unreachable!(),
+                event => leaf_name(event),
             };
 
             let _ = writeln!(
@@ -121,6 +240,36 @@ impl Org {
         result
     }
 
+    /// Returns the same events as `traverse()`, but as an array of nested
+    /// `{ kind, start, end, subtype?, children }` objects
+    ///
+    /// `subtype` is present for timestamps (e.g. `active-range`), cookies
+    /// (`percent` or `fraction`) and entities (entity name).
+    #[wasm_bindgen(js_name = "traverseJson")]
+    pub fn traverse_json(&self) -> JsValue {
+        let mut stack: Vec<TreeNode> = vec![TreeNode::new("".into(), TextRange::default(), None)];
+        let mut handler = from_fn(|event| match &event {
+            Event::Enter(container) => {
+                let (kind, range) = container_name(container);
+                stack.push(TreeNode::new(kind, range, None));
+            }
+            Event::Leave(_) => {
+                if stack.len() > 1 {
+                    let node = stack.pop().unwrap();
+                    stack.last_mut().unwrap().children.push(node);
+                }
+            }
+            event => {
+                let (kind, range) = leaf_name(event);
+                let node = TreeNode::new(kind, range, leaf_subtype(event));
+                stack.last_mut().unwrap().children.push(node);
+            }
+        });
+        self.inner.traverse(&mut handler);
+
+        to_js(&stack.swap_remove(0).children).unwrap_or(JsValue::NULL)
+    }
+
     #[wasm_bindgen(getter, js_name = "buildTime")]
     pub fn build_time() -> String {
         env!("CARGO_BUILD_TIME").into()
@@ -131,3 +280,174 @@ impl Org {
         env!("CARGO_GIT_HASH").into()
     }
 }
+
+fn to_js(value: &impl Serialize) -> Option<JsValue> {
+    serde_wasm_bindgen::to_value(value).ok()
+}
+
+fn container_name(container: &Container) -> (Cow<'static, str>, TextRange) {
+    match container {
+        Container::Document(x) => ("Document".into(), x.text_range()),
+        Container::Section(x) => ("Section".into(), x.text_range()),
+        Container::Paragraph(x) => ("Paragraph".into(), x.text_range()),
+        Container::Headline(x) => ("Headline".into(), x.text_range()),
+        Container::InlineTask(x) => ("InlineTask".into(), x.text_range()),
+        Container::OrgTable(x) => ("OrgTable".into(), x.text_range()),
+        Container::OrgTableRow(x) => ("OrgTableRow".into(), x.text_range()),
+        Container::OrgTableCell(x) => ("OrgTableCell".into(), x.text_range()),
+        Container::TableEl(x) => ("TableEl".into(), x.text_range()),
+        Container::List(x) => ("List".into(), x.text_range()),
+        Container::ListItem(x) => ("ListItem".into(), x.text_range()),
+        Container::Drawer(x) => ("Drawer".into(), x.text_range()),
+        Container::DynBlock(x) => ("DynBlock".into(), x.text_range()),
+        Container::FnDef(x) => ("FnDef".into(), x.text_range()),
+        Container::FnContent(x) => ("FnContent".into(), x.text_range()),
+        Container::Comment(x) => ("Comment".into(), x.text_range()),
+        Container::FixedWidth(x) => ("FixedWidth".into(), x.text_range()),
+        Container::SpecialBlock(x) => ("SpecialBlock".into(), x.text_range()),
+        Container::QuoteBlock(x) => ("QuoteBlock".into(), x.text_range()),
+        Container::CenterBlock(x) => ("CenterBlock".into(), x.text_range()),
+        Container::VerseBlock(x) => ("VerseBlock".into(), x.text_range()),
+        Container::CommentBlock(x) => ("CommentBlock".into(), x.text_range()),
+        Container::ExampleBlock(x) => ("ExampleBlock".into(), x.text_range()),
+        Container::ExportBlock(x) => ("ExportBlock".into(), x.text_range()),
+        Container::SourceBlock(x) => ("SourceBlock".into(), x.text_range()),
+        Container::Link(x) => ("Link".into(), x.text_range()),
+        Container::RadioTarget(x) => ("RadioTarget".into(), x.text_range()),
+        Container::FnRef(x) => ("FnRef".into(), x.text_range()),
+        Container::Target(x) => ("Target".into(), x.text_range()),
+        Container::Bold(x) => ("Bold".into(), x.text_range()),
+        Container::Strike(x) => ("Strike".into(), x.text_range()),
+        Container::Italic(x) => ("Italic".into(), x.text_range()),
+        Container::Underline(x) => ("Underline".into(), x.text_range()),
+        Container::Verbatim(x) => ("Verbatim".into(), x.text_range()),
+        Container::Code(x) => ("Code".into(), x.text_range()),
+        Container::Superscript(x) => ("Superscript".into(), x.text_range()),
+        Container::Subscript(x) => ("Subscript".into(), x.text_range()),
+        Container::BabelCall(x) => ("BabelCall".into(), x.text_range()),
+        Container::PropertyDrawer(x) => ("PropertyDrawer".into(), x.text_range()),
+        Container::AffiliatedKeyword(x) => ("AffiliatedKeyword".into(), x.text_range()),
+        Container::Keyword(x) => ("Keyword".into(), x.text_range()),
+        container => (
+            kind_name(container.syntax().kind()),
+            container.syntax().text_range(),
+        ),
+    }
+}
+
+fn leaf_name(event: &Event) -> (Cow<'static, str>, TextRange) {
+    match event {
+        Event::Text(x) => ("Text".into(), x.text_range()),
+        Event::Macros(x) => ("Macros".into(), x.text_range()),
+        Event::Cookie(x) => ("Cookie".into(), x.text_range()),
+        Event::InlineCall(x) => ("InlineCall".into(), x.text_range()),
+        Event::InlineSrc(x) => ("InlineSrc".into(), x.text_range()),
+        Event::Clock(x) => ("Clock".into(), x.text_range()),
+        Event::LineBreak(x) => ("LineBreak".into(), x.text_range()),
+        Event::Snippet(x) => ("Snippet".into(), x.text_range()),
+        Event::Rule(x) => ("Rule".into(), x.text_range()),
+        Event::Timestamp(x) => ("Timestamp".into(), x.text_range()),
+        Event::LatexFragment(x) => ("LatexFragment".into(), x.text_range()),
+        Event::LatexEnvironment(x) => ("LatexEnvironment".into(), x.text_range()),
+        Event::Entity(x) => ("Entity".into(), x.text_range()),
+        Event::FnLabel(x) => ("FnLabel".into(), x.text_range()),
+        Event::Citation(x) => ("Citation".into(), x.text_range()),
+        event => (kind_name(event.kind()), event.text_range()),
+    }
+}
+
+/// Converts syntax kind into pascal case, e.g. `INLINE_TASK` to `InlineTask`
+fn kind_name(kind: SyntaxKind) -> Cow<'static, str> {
+    let name = format!("{kind:?}");
+    Cow::Owned(
+        name.split('_')
+            .map(|word| {
+                let mut chars = word.chars();
+                chars
+                    .next()
+                    .into_iter()
+                    .chain(chars.flat_map(char::to_lowercase))
+                    .collect::<String>()
+            })
+            .collect(),
+    )
+}
+
+fn leaf_subtype(event: &Event) -> Option<String> {
+    match event {
+        Event::Timestamp(x) => {
+            let kind = if x.is_diary() {
+                "diary"
+            } else if x.is_active() {
+                "active"
+            } else {
+                "inactive"
+            };
+            Some(if x.is_range() {
+                format!("{kind}-range")
+            } else {
+                kind.into()
+            })
+        }
+        Event::Cookie(x) => Some(
+            if x.is_percent() {
+                "percent"
+            } else {
+                "fraction"
+            }
+            .into(),
+        ),
+        Event::Entity(x) => Some(x.name().to_string()),
+        _ => None,
+    }
+}
+
+#[test]
+fn names() {
+    let mut names = vec![];
+    let org = Inner::parse("a[fn:1]\n\n[fn:1] b\n\n[fn:2: inline]");
+    org.traverse(&mut from_fn(|event| match &event {
+        Event::Enter(container) => names.push(container_name(container).0),
+        Event::Leave(_) => {}
+        event => names.push(leaf_name(event).0),
+    }));
+    assert!(names.contains(&"FnContent".into()));
+    assert!(names.contains(&"FnLabel".into()));
+
+    let mut names = vec![];
+    Inner::parse("[cite:@key]").traverse(&mut from_fn(|event| {
+        if !matches!(event, Event::Enter(_) | Event::Leave(_)) {
+            names.push(leaf_name(&event).0);
+        }
+    }));
+    assert_eq!(names, ["Citation"]);
+
+    let mut names = vec![];
+    Inner::parse("*************** task").traverse(&mut from_fn(|event| {
+        if let Event::Enter(container) = event {
+            names.push(container_name(&container).0);
+        }
+    }));
+    assert_eq!(names, ["Document", "Section", "InlineTask"]);
+
+    assert_eq!(kind_name(SyntaxKind::INLINE_TASK), "InlineTask");
+    assert_eq!(kind_name(SyntaxKind::BOLD), "Bold");
+}
+
+#[test]
+fn lenient_options() {
+    use serde::de::value::{Error, MapDeserializer};
+
+    let options = Options::deserialize(MapDeserializer::<_, Error>::new(
+        [
+            ("todoKeywords", "not a list"),
+            ("useSubSuperscript", "brace"),
+        ]
+        .into_iter(),
+    ))
+    .unwrap();
+    assert!(options.todo_keywords.is_none());
+    let config = options.into_config();
+    assert!(config.use_sub_superscript.is_brace());
+    assert_eq!(config.todo_keywords, ParseConfig::default().todo_keywords);
+}